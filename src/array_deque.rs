@@ -3,28 +3,61 @@ extern crate alloc;
 
 #[cfg(not(feature = "std"))]
 use alloc::{
-    alloc::{Layout, alloc, dealloc},
+    alloc::{Layout, handle_alloc_error},
+    boxed::Box,
     collections::VecDeque,
     vec::Vec,
 };
 
 #[cfg(feature = "std")]
 use std::{
-    alloc::{Layout, alloc, dealloc},
+    alloc::{Layout, handle_alloc_error},
+    boxed::Box,
     collections::VecDeque,
     vec::Vec,
 };
 
+#[cfg(feature = "stats")]
+use crate::Stats;
+use crate::{
+    ArrayDequeView, ArrayDequeViewMut, ArrayWindows, Chunks, ChunksExact, GetManyMutError,
+    OverflowPolicy, TryExtendError, TryReserveError, Windows, check_disjoint, resolve_range,
+    sub_slices, sub_slices_mut,
+};
+use allocator_api2::alloc::{Allocator, Global};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::iter::{self, Chain, FusedIterator};
 use core::marker::PhantomData;
-use core::ops::{Index, IndexMut};
-use core::{fmt, ptr};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Index, IndexMut, RangeBounds};
+use core::ptr::{self, NonNull};
+use core::{fmt, slice};
 
+#[cfg(feature = "arbitrary")]
+use crate::ARBITRARY_HEADER_BYTES;
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, MaxRecursionReached, Unstructured, size_hint};
+#[cfg(feature = "bincode")]
+use bincode::{
+    BorrowDecode, Decode, Encode,
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+};
+#[cfg(feature = "serde")]
+use serde::de::{self, DeserializeSeed, SeqAccess, Visitor};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// A fixed-capacity, heap-allocated double-ended queue backed by a circular buffer.
 ///
-/// `ArrayDeque<T>` allocates a buffer on the heap with the given capacity. All
+/// `ArrayDeque<T, A>` allocates a buffer with the given capacity from the
+/// allocator `A`, which defaults to [`Global`]. All
 /// insertions and removals at either end run in O(1) time. Once full, further
 /// `push_back` calls overwrite the oldest front element, and `push_front` calls
 /// overwrite the oldest back element (FIFO overwrite behavior).
@@ -45,15 +78,22 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// dq.push_front("x");
 /// assert_eq!(dq.pop_back(), Some("a"));
 /// ```
-pub struct ArrayDeque<T> {
-    /// Raw pointer to the allocated memory
-    ptr: *mut T,
+pub struct ArrayDeque<T, A: Allocator = Global> {
+    /// Pointer to the allocated memory
+    ptr: NonNull<T>,
     /// Maximum capacity of the deque
     cap: usize,
     /// Current number of elements
     len: usize,
     /// Index of the front element
     idx: usize,
+    /// Allocator that owns the buffer
+    alloc: A,
+    /// What pushes do once the deque is full
+    policy: OverflowPolicy,
+    /// Push and eviction counters
+    #[cfg(feature = "stats")]
+    stats: Stats,
     /// Marker for the generic type
     _marker: PhantomData<T>,
 }
@@ -63,11 +103,14 @@ impl<T> ArrayDeque<T> {
     ///
     /// # Arguments
     ///
-    /// * `cap` - The fixed capacity of the deque. Must be greater than zero.
+    /// * `cap` - The fixed capacity of the deque. A zero-capacity deque never
+    ///   allocates and drops every element pushed into it.
     ///
     /// # Panics
     ///
-    /// Panics if `cap` is zero or if memory allocation fails.
+    /// Panics if the required buffer size overflows `isize::MAX` bytes. If the
+    /// allocator fails, [`handle_alloc_error`] is called. Use
+    /// [`try_new`](Self::try_new) to handle either case.
     ///
     /// # Examples
     ///
@@ -79,33 +122,340 @@ impl<T> ArrayDeque<T> {
     /// assert!(deque.is_empty());
     /// ```
     pub fn new(cap: usize) -> Self {
-        assert!(cap > 0, "Capacity must be greater than zero");
+        Self::new_in(cap, Global)
+    }
+
+    /// Creates a new `ArrayDeque` with the specified capacity, returning an
+    /// error instead of panicking if the buffer cannot be allocated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError::CapacityOverflow`] if the buffer size would
+    /// overflow `isize::MAX` bytes, and [`TryReserveError::AllocError`] if the
+    /// allocator fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{ArrayDeque, TryReserveError};
+    ///
+    /// let deque: ArrayDeque<i32> = ArrayDeque::try_new(10).unwrap();
+    /// assert_eq!(deque.capacity(), 10);
+    ///
+    /// let huge = ArrayDeque::<u64>::try_new(usize::MAX);
+    /// assert_eq!(huge.unwrap_err(), TryReserveError::CapacityOverflow);
+    /// ```
+    pub fn try_new(cap: usize) -> Result<Self, TryReserveError> {
+        Self::try_new_in(cap, Global)
+    }
+
+    /// Creates a new `ArrayDeque` with the specified capacity that handles
+    /// pushes onto a full deque according to `policy`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`new`](Self::new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{ArrayDeque, OverflowPolicy};
+    ///
+    /// let mut commands = ArrayDeque::with_policy(2, OverflowPolicy::Reject);
+    /// assert_eq!(commands.extend_back(["start", "stop", "reset"]), 2);
+    /// assert_eq!(commands, ["start", "stop"]);
+    /// assert_eq!(commands.try_push_back("reset"), Err("reset"));
+    /// ```
+    pub fn with_policy(cap: usize, policy: OverflowPolicy) -> Self {
+        let mut deque = Self::new(cap);
+        deque.policy = policy;
+        deque
+    }
 
-        let layout = Layout::array::<T>(cap).expect("Invalid layout");
-        let ptr = unsafe { alloc(layout) as *mut T };
+    /// Creates a full `ArrayDeque` with every slot set to a clone of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`new`](Self::new). If cloning
+    /// `value` panics, the elements written so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let dq = ArrayDeque::filled(3, 0.0);
+    /// assert!(dq.is_full());
+    /// assert_eq!(dq, [0.0, 0.0, 0.0]);
+    /// ```
+    pub fn filled(cap: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut deque = Self::new(cap);
+        if cap == 0 {
+            return deque;
+        }
+        for i in 0..cap - 1 {
+            unsafe { deque.ptr.as_ptr().add(i).write(value.clone()) };
+            // Counting each slot as it is written keeps the deque droppable
+            // if the next clone panics.
+            deque.len += 1;
+        }
+        unsafe { deque.ptr.as_ptr().add(cap - 1).write(value) };
+        deque.len = cap;
+        deque
+    }
 
-        if ptr.is_null() {
-            panic!("Failed to allocate memory");
+    /// Creates a full `ArrayDeque` whose element at each logical index `i` is
+    /// `f(i)`, mirroring [`core::array::from_fn`].
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`new`](Self::new). If `f` panics,
+    /// the elements created so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let dq = ArrayDeque::from_fn(4, |i| i * i);
+    /// assert_eq!(dq, [0, 1, 4, 9]);
+    /// ```
+    pub fn from_fn<F>(cap: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        let mut deque = Self::new(cap);
+        for i in 0..cap {
+            unsafe { deque.ptr.as_ptr().add(i).write(f(i)) };
+            deque.len += 1;
         }
+        deque
+    }
 
-        Self {
+    /// Creates an `ArrayDeque` with capacity `cap` holding the last `cap`
+    /// items of `iter`, front to back.
+    ///
+    /// The items are pushed to the back in order, overwriting the oldest ones
+    /// once the deque is full, exactly as [`extend`](Extend::extend) would. So
+    /// if `iter` yields `n` items, the deque ends up with the last
+    /// `min(n, cap)` of them, and its capacity is `cap` regardless of `n`.
+    /// Unlike [`collect`](Iterator::collect), whose capacity is the number of
+    /// items, the deque is ready to keep rolling.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`new`](Self::new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let recent = ArrayDeque::from_iter_with_capacity(3, 1..=10);
+    /// assert_eq!(recent, [8, 9, 10]);
+    ///
+    /// let mut short = ArrayDeque::from_iter_with_capacity(3, [1, 2]);
+    /// assert_eq!(short.capacity(), 3);
+    /// short.push_back(3);
+    /// assert_eq!(short, [1, 2, 3]);
+    /// ```
+    pub fn from_iter_with_capacity<I>(cap: usize, iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut deque = Self::new(cap);
+        deque.extend(iter);
+        deque
+    }
+
+    /// Converts the deque into a `Vec<T>` (front to back) without allocating.
+    ///
+    /// The contents are shifted to the start of the existing buffer, which is
+    /// then handed over to the vector. The vector's capacity equals the
+    /// deque's capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 2, 3, 4, 5]);
+    /// let vec = dq.into_vec();
+    /// assert_eq!(vec, [2, 3, 4, 5]);
+    /// assert_eq!(vec.capacity(), 4);
+    /// ```
+    pub fn into_vec(self) -> Vec<T> {
+        let mut deque = ManuallyDrop::new(self);
+        deque.make_contiguous();
+        if deque.idx != 0 {
+            unsafe {
+                ptr::copy(
+                    deque.ptr.as_ptr().add(deque.idx),
+                    deque.ptr.as_ptr(),
+                    deque.len,
+                );
+            }
+        }
+        // SAFETY: the buffer was allocated by the global allocator with the
+        // layout of `[T; cap]`, and its first `len` slots are initialized. The
+        // deque is never dropped, so ownership passes to the vector.
+        unsafe { Vec::from_raw_parts(deque.ptr.as_ptr(), deque.len, deque.cap) }
+    }
+
+    /// Converts the deque into a boxed slice (front to back), dropping the
+    /// ring bookkeeping.
+    ///
+    /// The buffer is handed over as with [`into_vec`](Self::into_vec), and
+    /// only reallocated to shrink it when the deque is not full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 2, 3, 4, 5]);
+    /// let window: Box<[i32]> = dq.into_boxed_slice();
+    /// assert_eq!(*window, [2, 3, 4, 5]);
+    /// ```
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        self.into_vec().into_boxed_slice()
+    }
+}
+
+impl<T, A: Allocator> ArrayDeque<T, A> {
+    /// Creates a new `ArrayDeque` with the specified capacity whose buffer is
+    /// allocated from `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the required buffer size overflows `isize::MAX` bytes. If the
+    /// allocator fails, [`handle_alloc_error`] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "unstable", feature(allocator_api))]
+    /// use array_deque::{ArrayDeque, Global};
+    ///
+    /// let mut deque = ArrayDeque::new_in(2, Global);
+    /// deque.push_back(1);
+    /// assert_eq!(deque[0], 1);
+    /// ```
+    pub fn new_in(cap: usize, alloc: A) -> Self {
+        match Self::try_new_in(cap, alloc) {
+            Ok(deque) => deque,
+            Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(TryReserveError::AllocError { layout }) => handle_alloc_error(layout),
+        }
+    }
+
+    /// Creates a new `ArrayDeque` with the specified capacity whose buffer is
+    /// allocated from `alloc`, returning an error instead of panicking if the
+    /// buffer cannot be allocated.
+    ///
+    /// # Errors
+    ///
+    /// See [`try_new`](ArrayDeque::try_new).
+    pub fn try_new_in(cap: usize, alloc: A) -> Result<Self, TryReserveError> {
+        let layout = Layout::array::<T>(cap).map_err(|_| TryReserveError::CapacityOverflow)?;
+        // Zero-sized element types need no storage, so the allocator is never
+        // touched and a dangling, well-aligned pointer stands in for the buffer.
+        let ptr = if layout.size() == 0 {
+            NonNull::dangling()
+        } else {
+            alloc
+                .allocate(layout)
+                .map_err(|_| TryReserveError::AllocError { layout })?
+                .cast()
+        };
+
+        Ok(Self {
             ptr,
             cap,
             len: 0,
             idx: 0,
+            alloc,
+            policy: OverflowPolicy::OverwriteOldest,
+            #[cfg(feature = "stats")]
+            stats: Stats::new(),
             _marker: PhantomData,
+        })
+    }
+
+    /// Returns a reference to the underlying allocator.
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns how pushes onto a full deque are handled.
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Changes how pushes onto a full deque are handled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{ArrayDeque, OverflowPolicy};
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2]);
+    /// deque.set_policy(OverflowPolicy::Reject);
+    /// deque.push_front(0);
+    /// assert_eq!(deque, [1, 2]);
+    /// ```
+    pub fn set_policy(&mut self, policy: OverflowPolicy) {
+        self.policy = policy;
+    }
+
+    /// Decides what happens to an element pushed onto a full deque: returns
+    /// `true` if an element should be evicted to make room, or `false` if the
+    /// new one is rejected.
+    ///
+    /// # Panics
+    ///
+    /// Panics under [`OverflowPolicy::Panic`].
+    fn evict_on_overflow(&self) -> bool {
+        match self.policy {
+            OverflowPolicy::OverwriteOldest => true,
+            OverflowPolicy::Reject => false,
+            OverflowPolicy::Panic => panic!("deque is full (capacity {})", self.cap),
         }
     }
 
+    /// Returns `true` if a single pushed element cannot be stored: the deque
+    /// has zero capacity, or it is full and the policy rejects the element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is full under [`OverflowPolicy::Panic`].
+    fn rejects_push(&self) -> bool {
+        (self.is_full() && !self.evict_on_overflow()) || self.cap == 0
+    }
+
     /// Appends an element to the back of the deque.
     ///
     /// If the deque is at capacity, this will overwrite the front element
-    /// and advance the front pointer.
+    /// and advance the front pointer, unless the deque's [`OverflowPolicy`]
+    /// says otherwise.
     ///
     /// # Arguments
     ///
     /// * `value` - The element to append
     ///
+    /// A zero-capacity deque, or a full one under [`OverflowPolicy::Reject`],
+    /// drops `value`; use [`try_push_back`](Self::try_push_back) to get it
+    /// back instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is full under [`OverflowPolicy::Panic`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -117,30 +467,28 @@ impl<T> ArrayDeque<T> {
     /// assert_eq!(deque.len(), 2);
     /// ```
     pub fn push_back(&mut self, value: T) {
-        let write_idx = (self.idx + self.len) % self.cap;
-        if self.len == self.cap {
-            unsafe {
-                ptr::drop_in_place(self.ptr.add(write_idx));
-            }
-        }
-        unsafe {
-            ptr::write(self.ptr.add(write_idx), value);
-        }
-        if self.len == self.cap {
-            self.idx = (self.idx + 1) % self.cap;
-        } else {
-            self.len += 1;
+        if !self.rejects_push() {
+            drop(self.push_back_unchecked(value));
         }
     }
 
     /// Prepends an element to the front of the deque.
     ///
-    /// If the deque is at capacity, this will overwrite the back element.
+    /// If the deque is at capacity, this will overwrite the back element,
+    /// unless the deque's [`OverflowPolicy`] says otherwise.
     ///
     /// # Arguments
     ///
     /// * `value` - The element to prepend
     ///
+    /// A zero-capacity deque, or a full one under [`OverflowPolicy::Reject`],
+    /// drops `value`; use [`try_push_front`](Self::try_push_front) to get it
+    /// back instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is full under [`OverflowPolicy::Panic`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -153,18 +501,196 @@ impl<T> ArrayDeque<T> {
     /// assert_eq!(deque[1], 1);
     /// ```
     pub fn push_front(&mut self, value: T) {
-        self.idx = (self.idx + self.cap - 1) % self.cap;
+        if !self.rejects_push() {
+            drop(self.push_front_unchecked(value));
+        }
+    }
+
+    /// Appends an element to the back of the deque, returning the evicted
+    /// front element if the deque was full.
+    ///
+    /// # Returns
+    ///
+    /// `Some(T)` with the overwritten front element if the deque was at
+    /// capacity, `None` otherwise. A zero-capacity deque returns `value`
+    /// itself.
+    ///
+    /// Asking for the evicted element opts into eviction, so this ignores the
+    /// deque's [`OverflowPolicy`]: it evicts even under
+    /// [`Reject`](OverflowPolicy::Reject) or [`Panic`](OverflowPolicy::Panic).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(2);
+    /// assert_eq!(deque.push_back_evict(1), None);
+    /// assert_eq!(deque.push_back_evict(2), None);
+    /// assert_eq!(deque.push_back_evict(3), Some(1));
+    /// assert_eq!(deque[0], 2);
+    /// ```
+    pub fn push_back_evict(&mut self, value: T) -> Option<T> {
+        if self.cap == 0 {
+            return Some(value);
+        }
+        self.push_back_unchecked(value)
+    }
+
+    /// Appends `value`, evicting and returning the front element if the deque
+    /// is full, whatever the policy. The capacity must not be zero.
+    fn push_back_unchecked(&mut self, value: T) -> Option<T> {
+        let write_idx = self.physical_index(self.len);
         if self.len == self.cap {
-            let drop_idx = (self.idx + self.len) % self.cap;
+            let evicted = unsafe { ptr::read(self.ptr.as_ptr().add(write_idx)) };
             unsafe {
-                ptr::drop_in_place(self.ptr.add(drop_idx));
+                ptr::write(self.ptr.as_ptr().add(write_idx), value);
             }
+            self.idx = self.wrap_add(self.idx, 1);
+            self.record_pushes(1, self.len);
+            Some(evicted)
         } else {
+            unsafe {
+                ptr::write(self.ptr.as_ptr().add(write_idx), value);
+            }
             self.len += 1;
+            self.record_pushes(1, self.len - 1);
+            None
+        }
+    }
+
+    /// Prepends an element to the front of the deque, returning the evicted
+    /// back element if the deque was full.
+    ///
+    /// # Returns
+    ///
+    /// `Some(T)` with the overwritten back element if the deque was at
+    /// capacity, `None` otherwise. A zero-capacity deque returns `value`
+    /// itself.
+    ///
+    /// Asking for the evicted element opts into eviction, so this ignores the
+    /// deque's [`OverflowPolicy`]: it evicts even under
+    /// [`Reject`](OverflowPolicy::Reject) or [`Panic`](OverflowPolicy::Panic).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(2);
+    /// assert_eq!(deque.push_front_evict(1), None);
+    /// assert_eq!(deque.push_front_evict(2), None);
+    /// assert_eq!(deque.push_front_evict(3), Some(1));
+    /// assert_eq!(deque[0], 3);
+    /// ```
+    pub fn push_front_evict(&mut self, value: T) -> Option<T> {
+        if self.cap == 0 {
+            return Some(value);
         }
+        self.push_front_unchecked(value)
+    }
+
+    /// Prepends `value`, evicting and returning the back element if the deque
+    /// is full, whatever the policy. The capacity must not be zero.
+    fn push_front_unchecked(&mut self, value: T) -> Option<T> {
+        let old_len = self.len;
+        self.idx = self.wrap_sub(self.idx, 1);
+        let evicted = if self.len == self.cap {
+            // The new front slot is the one holding the old back element.
+            Some(unsafe { ptr::read(self.ptr.as_ptr().add(self.idx)) })
+        } else {
+            self.len += 1;
+            None
+        };
         unsafe {
-            ptr::write(self.ptr.add(self.idx), value);
+            ptr::write(self.ptr.as_ptr().add(self.idx), value);
+        }
+        self.record_pushes(1, old_len);
+        evicted
+    }
+
+    /// Appends an element to the back of the deque without overwriting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the deque is full, leaving its contents untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(1);
+    /// assert_eq!(deque.try_push_back(1), Ok(()));
+    /// assert_eq!(deque.try_push_back(2), Err(2));
+    /// assert_eq!(deque[0], 1);
+    /// ```
+    pub fn try_push_back(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.push_back(value);
+        Ok(())
+    }
+
+    /// Prepends an element to the front of the deque without overwriting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the deque is full, leaving its contents untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(1);
+    /// assert_eq!(deque.try_push_front(1), Ok(()));
+    /// assert_eq!(deque.try_push_front(2), Err(2));
+    /// assert_eq!(deque[0], 1);
+    /// ```
+    pub fn try_push_front(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.push_front(value);
+        Ok(())
+    }
+
+    /// Appends every element of `iter` to the back without overwriting,
+    /// stopping at the first element that does not fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryExtendError`] holding the rejected element and the rest of
+    /// the iterator. Elements already in the deque are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.push_back(1);
+    /// let err = dq.try_extend([2, 3, 4, 5]).unwrap_err();
+    /// assert_eq!(err.element, 4);
+    /// assert_eq!(err.remaining.collect::<Vec<_>>(), [5]);
+    /// assert_eq!(dq.len(), 3);
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryExtendError<I::IntoIter>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        while let Some(item) = iter.next() {
+            if let Err(element) = self.try_push_back(item) {
+                return Err(TryExtendError {
+                    element,
+                    remaining: iter,
+                });
+            }
         }
+        Ok(())
     }
 
     /// Removes and returns the last element from the deque.
@@ -189,9 +715,9 @@ impl<T> ArrayDeque<T> {
         if self.len == 0 {
             return None;
         }
-        let tail_idx = (self.idx + self.len - 1) % self.cap;
+        let tail_idx = self.physical_index(self.len - 1);
         self.len -= 1;
-        Some(unsafe { ptr::read(self.ptr.add(tail_idx)) })
+        Some(unsafe { ptr::read(self.ptr.as_ptr().add(tail_idx)) })
     }
 
     /// Removes and returns the first element from the deque.
@@ -217,623 +743,6839 @@ impl<T> ArrayDeque<T> {
             return None;
         }
         let front_idx = self.idx;
-        self.idx = (self.idx + 1) % self.cap;
+        self.idx = self.wrap_add(self.idx, 1);
         self.len -= 1;
-        Some(unsafe { ptr::read(self.ptr.add(front_idx)) })
+        Some(unsafe { ptr::read(self.ptr.as_ptr().add(front_idx)) })
     }
 
-    /// Returns a reference to the front element without removing it.
+    /// Removes and returns the front element if `predicate` returns `true`
+    /// for it, or leaves the deque untouched and returns `None` otherwise.
+    ///
+    /// Returns `None` without calling `predicate` if the deque is empty.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut dq = ArrayDeque::new(3);
-    /// assert_eq!(dq.front(), None);
-    /// dq.push_back(42);
-    /// assert_eq!(dq.front(), Some(&42));
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.pop_front_if(|x| *x > 1), None);
+    /// assert_eq!(deque.pop_front_if(|x| *x == 1), Some(1));
+    /// assert_eq!(deque.len(), 2);
     /// ```
-    pub fn front(&self) -> Option<&T> {
-        if self.is_empty() {
-            None
+    pub fn pop_front_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        let first = self.get_mut(0)?;
+        if predicate(first) {
+            self.pop_front()
         } else {
-            Some(unsafe { &*self.ptr.add(self.idx) })
+            None
         }
     }
 
-    /// Returns a reference to the back element without removing it.
+    /// Removes and returns the back element if `predicate` returns `true`
+    /// for it, or leaves the deque untouched and returns `None` otherwise.
+    ///
+    /// Returns `None` without calling `predicate` if the deque is empty.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut dq = ArrayDeque::new(3);
-    /// dq.push_back(1);
-    /// dq.push_back(2);
-    /// assert_eq!(dq.back(), Some(&2));
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.pop_back_if(|x| *x < 3), None);
+    /// assert_eq!(deque.pop_back_if(|x| *x == 3), Some(3));
+    /// assert_eq!(deque.len(), 2);
     /// ```
-    pub fn back(&self) -> Option<&T> {
-        if self.is_empty() {
-            None
+    pub fn pop_back_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        let last = self.get_back_mut(0)?;
+        if predicate(last) {
+            self.pop_back()
         } else {
-            let back_idx = if self.idx + self.len <= self.cap {
-                self.idx + self.len - 1
-            } else {
-                (self.idx + self.len - 1) % self.cap
-            };
-            Some(unsafe { &*self.ptr.add(back_idx) })
+            None
         }
     }
 
-    /// Returns an iterator over the elements of the deque (front to back).
+    /// Returns an iterator that pops front elements for as long as
+    /// `predicate` returns `true`, stopping at the first one it rejects.
+    ///
+    /// Elements are popped one at a time as the iterator advances, so
+    /// dropping it early leaves the remaining elements in the deque.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut dq = ArrayDeque::new(3);
-    /// dq.push_back(1);
-    /// dq.push_back(2);
-    /// let v: Vec<_> = dq.iter().cloned().collect();
-    /// assert_eq!(v, vec![1,2]);
+    /// let mut deque = ArrayDeque::new(4);
+    /// deque.extend([1, 2, 5, 3]);
+    /// assert!(deque.pop_front_while(|x| *x < 4).eq([1, 2]));
+    /// assert_eq!(deque.front(), Some(&5));
     /// ```
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
-        (0..self.len).map(move |i| {
-            let idx = (self.idx + i) % self.cap;
-            unsafe { &*self.ptr.add(idx) }
-        })
+    pub fn pop_front_while<F>(&mut self, mut predicate: F) -> impl FusedIterator<Item = T>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        iter::from_fn(move || self.pop_front_if(&mut predicate)).fuse()
     }
 
-    /// Returns the maximum capacity of the deque.
+    /// Returns an iterator that pops back elements for as long as
+    /// `predicate` returns `true`, stopping at the first one it rejects.
+    ///
+    /// Elements are popped one at a time as the iterator advances, so
+    /// dropping it early leaves the remaining elements in the deque.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let dq: ArrayDeque<i32> = ArrayDeque::new(5);
-    /// assert_eq!(dq.capacity(), 5);
+    /// let mut deque = ArrayDeque::new(4);
+    /// deque.extend([1, 5, 3, 2]);
+    /// assert!(deque.pop_back_while(|x| *x < 4).eq([2, 3]));
+    /// assert_eq!(deque.back(), Some(&5));
     /// ```
-    pub fn capacity(&self) -> usize {
-        self.cap
+    pub fn pop_back_while<F>(&mut self, mut predicate: F) -> impl FusedIterator<Item = T>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        iter::from_fn(move || self.pop_back_if(&mut predicate)).fuse()
     }
 
-    /// Returns the number of elements currently in the deque.
+    /// Inserts an element at `index` (0 is front), shifting whichever side of
+    /// the deque holds fewer elements.
+    ///
+    /// If the deque is full, the back element is evicted first to make room,
+    /// so inserting at `len()` replaces the back element. A zero-capacity deque,
+    /// or a full one under [`OverflowPolicy::Reject`], drops `value`; use
+    /// [`try_insert`](Self::try_insert) to get it back instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()`, or if the deque is full under
+    /// [`OverflowPolicy::Panic`].
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut dq = ArrayDeque::new(3);
-    /// assert_eq!(dq.len(), 0);
-    /// dq.push_back(1);
-    /// assert_eq!(dq.len(), 1);
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.push_back(1);
+    /// deque.push_back(3);
+    /// deque.insert(1, 2);
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    ///
+    /// // Full: the back element (3) is evicted.
+    /// deque.insert(0, 0);
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
     /// ```
-    pub fn len(&self) -> usize {
-        self.len
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len, "Index out of bounds");
+        if self.rejects_push() {
+            return;
+        }
+        let old_len = self.len;
+        if self.is_full() {
+            self.pop_back();
+        }
+        let index = index.min(self.len);
+
+        if index < self.len - index {
+            self.idx = self.wrap_sub(self.idx, 1);
+            for i in 0..index {
+                unsafe { self.move_slot(i + 1, i) };
+            }
+        } else {
+            for i in (index..self.len).rev() {
+                unsafe { self.move_slot(i, i + 1) };
+            }
+        }
+        self.len += 1;
+        unsafe {
+            ptr::write(self.ptr.as_ptr().add(self.physical_index(index)), value);
+        }
+        self.record_pushes(1, old_len);
     }
 
-    /// Returns `true` if the deque contains no elements.
+    /// Inserts an element at `index` (0 is front) without evicting anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the deque is full, leaving its contents untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()`.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut dq = ArrayDeque::new(3);
-    /// assert!(dq.is_empty());
-    /// dq.push_back(1);
-    /// assert!(!dq.is_empty());
+    /// let mut deque = ArrayDeque::new(2);
+    /// deque.push_back(1);
+    /// assert_eq!(deque.try_insert(0, 0), Ok(()));
+    /// assert_eq!(deque.try_insert(1, 5), Err(5));
+    /// assert_eq!(deque[0], 0);
+    /// assert_eq!(deque[1], 1);
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), T> {
+        assert!(index <= self.len, "Index out of bounds");
+        if self.is_full() {
+            return Err(value);
+        }
+        self.insert(index, value);
+        Ok(())
     }
 
-    /// Returns `true` if the deque has reached its maximum capacity.
+    /// Removes and returns the element at `index` (0 is front), shifting
+    /// whichever side of the deque holds fewer elements.
+    ///
+    /// # Returns
+    ///
+    /// `Some(T)` if `index < len()`, `None` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut dq = ArrayDeque::new(2);
-    /// dq.push_back(1);
-    /// dq.push_back(2);
-    /// assert!(dq.is_full());
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.remove(1), Some(2));
+    /// assert_eq!(deque.remove(5), None);
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
     /// ```
-    pub fn is_full(&self) -> bool {
-        self.len == self.cap
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let value = unsafe { ptr::read(self.ptr.as_ptr().add(self.physical_index(index))) };
+
+        if index < self.len - 1 - index {
+            for i in (0..index).rev() {
+                unsafe { self.move_slot(i, i + 1) };
+            }
+            self.idx = self.wrap_add(self.idx, 1);
+        } else {
+            for i in index + 1..self.len {
+                unsafe { self.move_slot(i, i - 1) };
+            }
+        }
+        self.len -= 1;
+        Some(value)
     }
 
-    /// Removes all elements from the deque, properly dropping them,
-    /// and resets it to an empty state.
+    /// Returns an iterator that removes and yields the elements for which
+    /// `pred` returns `true`, front to back, keeping the rest in order.
+    ///
+    /// Elements are tested and removed lazily as the iterator is advanced.
+    /// If it is dropped early, the elements it has not reached yet stay in the
+    /// deque. `pred` gets a mutable reference, so it may also modify the
+    /// elements it keeps.
+    ///
+    /// If `pred` panics, the element it was testing and every element after it
+    /// stay in the deque. If the iterator is leaked (e.g. with
+    /// [`mem::forget`](core::mem::forget)), the deque may lose an arbitrary
+    /// number of its elements, but never exposes moved-out values.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut dq = ArrayDeque::new(3);
-    /// dq.push_back(1);
-    /// dq.clear();
-    /// assert!(dq.is_empty());
-    /// assert_eq!(dq.len(), 0);
+    /// let mut pending = ArrayDeque::new(5);
+    /// pending.extend([(1, 30), (2, 5), (3, 80), (4, 12)]);
+    /// let timed_out: Vec<_> = pending.extract_if(|&mut (_, age)| age > 20).collect();
+    /// assert_eq!(timed_out, [(1, 30), (3, 80)]);
+    /// assert_eq!(pending, [(2, 5), (4, 12)]);
     /// ```
-    pub fn clear(&mut self) {
-        for i in 0..self.len {
-            let idx = (self.idx + i) % self.cap;
-            unsafe {
-                ptr::drop_in_place(self.ptr.add(idx));
-            }
-        }
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F, A>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.len;
+        // Until the iterator is dropped, the deque looks empty, so leaking it
+        // can only leak elements rather than expose holes left by removals.
         self.len = 0;
-        self.idx = 0;
-    }
-}
-
-impl<T> Drop for ArrayDeque<T> {
-    /// Drops all elements and deallocates the heap buffer.
-    fn drop(&mut self) {
-        self.clear();
-        let layout = Layout::array::<T>(self.cap).expect("Invalid layout");
-        unsafe {
-            dealloc(self.ptr.cast(), layout);
+        ExtractIf {
+            deque: self,
+            pred,
+            pos: 0,
+            removed: 0,
+            old_len,
         }
     }
-}
-
-impl<T: fmt::Debug> fmt::Debug for ArrayDeque<T> {
-    /// Formats the deque as a debug list (front to back).
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
-    }
-}
 
-impl<T: Clone> Clone for ArrayDeque<T> {
-    /// Creates a deep copy of the deque with identical capacity and contents.
+    /// Returns a cursor pointing at the front element, or at the "ghost"
+    /// position if the deque is empty.
+    ///
+    /// A cursor walks the deque by position and inserts or removes elements
+    /// where it stands; see [`CursorMut`].
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut deque = ArrayDeque::new(3);
-    /// deque.push_back(1);
-    /// deque.push_back(2);
-    /// let cloned = deque.clone();
-    /// assert_eq!(deque.len(), cloned.len());
-    /// assert_eq!(deque[0], cloned[0]);
+    /// let mut lines = ArrayDeque::new(8);
+    /// lines.extend(["fn main() {", "}"]);
+    /// let mut cursor = lines.cursor_front_mut();
+    /// cursor.insert_after("    println!();");
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), Some(&mut "    println!();"));
+    /// assert_eq!(lines, ["fn main() {", "    println!();", "}"]);
     /// ```
-    fn clone(&self) -> Self {
-        let mut new = ArrayDeque::new(self.cap);
-        for item in self.iter() {
-            new.push_back(item.clone());
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            index: 0,
+            deque: self,
         }
-        new
-    }
-}
-
-impl<T: PartialEq> PartialEq for ArrayDeque<T> {
-    /// Two deques are equal if they have the same length
-    /// and each element compares equal in order.
-    fn eq(&self, other: &Self) -> bool {
-        self.len == other.len && self.iter().eq(other.iter())
     }
-}
 
-impl<T: Eq> Eq for ArrayDeque<T> {}
-
-impl<T> Index<usize> for ArrayDeque<T> {
-    type Output = T;
-
-    /// Indexed access into the deque (0 is front).
+    /// Returns a cursor pointing at the back element, or at the "ghost"
+    /// position if the deque is empty.
     ///
-    /// # Panics
+    /// # Examples
     ///
-    /// Panics if `index >= len()`.
-    fn index(&self, index: usize) -> &Self::Output {
-        assert!(index < self.len, "Index out of bounds");
-        let actual_idx = self.idx + index;
-        let actual_idx = if actual_idx >= self.cap {
-            actual_idx - self.cap
-        } else {
-            actual_idx
-        };
-        unsafe { &*self.ptr.add(actual_idx) }
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(4);
+    /// deque.extend([1, 2, 3]);
+    /// let mut cursor = deque.cursor_back_mut();
+    /// assert_eq!(cursor.remove_current(), Some(3));
+    /// assert_eq!(cursor.current(), None);
+    /// assert_eq!(cursor.peek_prev(), Some(&mut 2));
+    /// ```
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            index: self.len.saturating_sub(1),
+            deque: self,
+        }
     }
-}
 
-impl<T> IndexMut<usize> for ArrayDeque<T> {
-    /// Mutable indexed access into the deque (0 is front).
+    /// Swaps the elements at indices `i` and `j` (0 is front).
     ///
     /// # Panics
     ///
-    /// Panics if `index >= len()`.
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        assert!(index < self.len);
-        let idx = (self.idx + index) % self.cap;
-        unsafe { &mut *self.ptr.add(idx) }
-    }
-}
-
-impl<T> Extend<T> for ArrayDeque<T> {
-    /// Extends the deque by pushing each item of the iterator to the back.
+    /// Panics if either index is out of bounds.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let mut dq = ArrayDeque::new(5);
-    /// dq.extend([1,2,3]);
-    /// assert_eq!(dq.len(), 3);
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3]);
+    /// deque.swap(0, 2);
+    /// assert_eq!(deque[0], 3);
+    /// assert_eq!(deque[2], 1);
     /// ```
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for item in iter {
-            self.push_back(item);
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len && j < self.len, "Index out of bounds");
+        unsafe {
+            ptr::swap(
+                self.ptr.as_ptr().add(self.physical_index(i)),
+                self.ptr.as_ptr().add(self.physical_index(j)),
+            );
         }
     }
-}
 
-impl<T> FromIterator<T> for ArrayDeque<T> {
-    /// Creates a deque from an iterator by collecting all items.
-    /// Capacity == number of items (min 1).
+    /// Reverses the order of the elements in place, with `len() / 2` swaps and
+    /// no allocation.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::ArrayDeque;
     ///
-    /// let dq: ArrayDeque<_> = (0..3).collect();
-    /// assert_eq!(dq.len(), 3);
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.reverse();
+    /// assert_eq!(deque, [4, 3, 2]);
     /// ```
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let vec: Vec<T> = iter.into_iter().collect();
-        let mut deque = ArrayDeque::new(vec.len().max(1));
-        deque.extend(vec);
-        deque
-    }
-}
-
-impl<T: Clone> From<&[T]> for ArrayDeque<T> {
-    /// Clones all elements from a slice into a new deque.
-    fn from(slice: &[T]) -> Self {
-        let mut deque = ArrayDeque::new(slice.len().max(1));
-        for item in slice {
-            deque.push_back(item.clone());
+    pub fn reverse(&mut self) {
+        let base = self.ptr.as_ptr();
+        for i in 0..self.len / 2 {
+            unsafe {
+                ptr::swap_nonoverlapping(
+                    base.add(self.physical_index(i)),
+                    base.add(self.physical_index(self.len - 1 - i)),
+                    1,
+                );
+            }
         }
-        deque
     }
-}
 
-impl<T, const N: usize> From<[T; N]> for ArrayDeque<T> {
-    /// Takes ownership of each element in the array.
-    fn from(array: [T; N]) -> Self {
-        let mut deque = ArrayDeque::new(N.max(1));
-        for item in array {
-            deque.push_back(item);
+    /// Removes the element at `index` and returns it, replacing it with the
+    /// front element. This does not preserve ordering, but is O(1).
+    ///
+    /// # Returns
+    ///
+    /// `Some(T)` if `index < len()`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.swap_remove_front(2), Some(3));
+    /// assert_eq!(deque[0], 2);
+    /// assert_eq!(deque[1], 1);
+    /// ```
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
         }
-        deque
-    }
-}
-
-impl<T> From<Vec<T>> for ArrayDeque<T> {
-    /// Takes ownership of each element in the vector.
-    fn from(vec: Vec<T>) -> Self {
-        let mut deque = ArrayDeque::new(vec.len().max(1));
-        for item in vec {
-            deque.push_back(item);
+        if index != 0 {
+            self.swap(index, 0);
         }
-        deque
+        self.pop_front()
     }
-}
 
-impl<T> From<VecDeque<T>> for ArrayDeque<T> {
-    /// Takes ownership of elements from a `VecDeque` (front to back).
-    fn from(mut vec_deque: VecDeque<T>) -> Self {
-        let mut deque = ArrayDeque::new(vec_deque.len().max(1));
-        while let Some(item) = vec_deque.pop_front() {
-            deque.push_back(item);
+    /// Removes the element at `index` and returns it, replacing it with the
+    /// back element. This does not preserve ordering, but is O(1).
+    ///
+    /// # Returns
+    ///
+    /// `Some(T)` if `index < len()`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.swap_remove_back(0), Some(1));
+    /// assert_eq!(deque[0], 3);
+    /// assert_eq!(deque[1], 2);
+    /// ```
+    pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
         }
-        deque
+        let last = self.len - 1;
+        if index != last {
+            self.swap(index, last);
+        }
+        self.pop_back()
     }
-}
 
-impl<T> From<ArrayDeque<T>> for VecDeque<T> {
-    /// Converts this deque into a `VecDeque`, preserving order.
-    fn from(deque: ArrayDeque<T>) -> Self {
-        deque.into_iter().collect()
+    /// Returns a reference to the front element without removing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// assert_eq!(dq.front(), None);
+    /// dq.push_back(42);
+    /// assert_eq!(dq.front(), Some(&42));
+    /// ```
+    pub fn front(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { &*self.ptr.as_ptr().add(self.idx) })
+        }
     }
-}
 
-impl<T: Clone> From<&ArrayDeque<T>> for VecDeque<T> {
+    /// Returns a reference to the back element without removing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.push_back(1);
+    /// dq.push_back(2);
+    /// assert_eq!(dq.back(), Some(&2));
+    /// ```
+    pub fn back(&self) -> Option<&T> {
+        self.get_back(0)
+    }
+
+    /// Returns a reference to the element `index` places from the back (0 is
+    /// back), or `None` if `index >= len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3, 4]);
+    /// assert_eq!(dq.get_back(0), Some(&4));
+    /// assert_eq!(dq.get_back(2), Some(&2));
+    /// assert_eq!(dq.get_back(3), None);
+    /// ```
+    pub fn get_back(&self, index: usize) -> Option<&T> {
+        let slot = self.back_slot(index)?;
+        Some(unsafe { &*self.ptr.as_ptr().add(slot) })
+    }
+
+    /// Returns a mutable reference to the element `index` places from the
+    /// back (0 is back), or `None` if `index >= len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3]);
+    /// *dq.get_back_mut(1).unwrap() = 20;
+    /// assert_eq!(dq, [1, 20, 3]);
+    /// ```
+    pub fn get_back_mut(&mut self, index: usize) -> Option<&mut T> {
+        let slot = self.back_slot(index)?;
+        Some(unsafe { &mut *self.ptr.as_ptr().add(slot) })
+    }
+
+    /// Returns a reference to the element at `index` (0 is front), or `None`
+    /// if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.push_back(1);
+    /// dq.push_back(2);
+    /// assert_eq!(dq.get(1), Some(&2));
+    /// assert_eq!(dq.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(unsafe { &*self.ptr.as_ptr().add(self.physical_index(index)) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at `index` (0 is front), or
+    /// `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.push_back(1);
+    /// if let Some(x) = dq.get_mut(0) {
+    ///     *x = 10;
+    /// }
+    /// assert_eq!(dq[0], 10);
+    /// assert_eq!(dq.get_mut(1), None);
+    /// ```
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            Some(unsafe { &mut *self.ptr.as_ptr().add(self.physical_index(index)) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the element at `index` (0 is front) without
+    /// bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `len()`. Debug builds assert this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(2);
+    /// dq.push_back(1);
+    /// dq.push_back(2);
+    /// dq.push_back(3);
+    /// assert_eq!(unsafe { *dq.get_unchecked(0) }, 2);
+    /// ```
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        debug_assert!(index < self.len, "Index out of bounds");
+        unsafe { &*self.ptr.as_ptr().add(self.physical_index(index)) }
+    }
+
+    /// Returns a mutable reference to the element at `index` (0 is front)
+    /// without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `len()`. Debug builds assert this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(2);
+    /// dq.push_back(1);
+    /// unsafe { *dq.get_unchecked_mut(0) = 10 };
+    /// assert_eq!(dq[0], 10);
+    /// ```
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        debug_assert!(index < self.len, "Index out of bounds");
+        unsafe { &mut *self.ptr.as_ptr().add(self.physical_index(index)) }
+    }
+
+    /// Returns mutable references to the elements at each of `indices` (0 is
+    /// front) at once.
+    ///
+    /// Takes O(K²) time to check the indices, like the slice method of the
+    /// same shape.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GetManyMutError::IndexOutOfBounds`] if any index is not less
+    /// than `len()`, and [`GetManyMutError::OverlappingIndices`] if any index
+    /// appears twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{ArrayDeque, GetManyMutError};
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3, 4]);
+    /// let [prev, cur, next] = dq.get_many_mut([0, 1, 2]).unwrap();
+    /// *cur = (*prev + *cur + *next) / 3;
+    /// assert_eq!(dq, [2, 3, 4]);
+    /// assert_eq!(dq.get_many_mut([1, 1]), Err(GetManyMutError::OverlappingIndices));
+    /// assert_eq!(dq.get_many_mut([0, 3]), Err(GetManyMutError::IndexOutOfBounds));
+    /// ```
+    pub fn get_many_mut<const K: usize>(
+        &mut self,
+        indices: [usize; K],
+    ) -> Result<[&mut T; K], GetManyMutError> {
+        check_disjoint(&indices, self.len)?;
+        let ptr = self.ptr.as_ptr();
+        // Distinct logical indices below `len` map to distinct initialized
+        // slots, even when they fall on both sides of the wrap point.
+        Ok(indices.map(|index| unsafe { &mut *ptr.add(self.physical_index(index)) }))
+    }
+
+    /// Returns an iterator over the elements of the deque (front to back).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.push_back(1);
+    /// dq.push_back(2);
+    /// let v: Vec<_> = dq.iter().cloned().collect();
+    /// assert_eq!(v, vec![1,2]);
+    /// ```
+    pub fn iter(&self) -> ArrayDequeIter<'_, T, A> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` elements,
+    /// front to back.
+    ///
+    /// Since the contents may wrap around the end of the buffer, each window
+    /// is a pair of slices, like [`as_slices`](Self::as_slices): the first
+    /// slice followed by the second. If the deque holds fewer than `size`
+    /// elements, the iterator is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3, 4]);
+    /// let sums: Vec<i32> = deque
+    ///     .windows(2)
+    ///     .map(|(a, b)| a.iter().chain(b).sum())
+    ///     .collect();
+    /// assert_eq!(sums, [5, 7]);
+    /// ```
+    pub fn windows(&self, size: usize) -> Windows<'_, T> {
+        let (front, back) = self.as_slices();
+        Windows::new(front, back, size)
+    }
+
+    /// Returns an iterator over all overlapping windows of `K` elements,
+    /// front to back, each copied into an array.
+    ///
+    /// If the deque holds fewer than `K` elements, the iterator is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3, 4]);
+    /// let mut windows = deque.array_windows();
+    /// assert_eq!(windows.next(), Some([2, 3, 4]));
+    /// assert_eq!(windows.next(), None);
+    /// ```
+    pub fn array_windows<const K: usize>(&self) -> ArrayWindows<'_, T, K>
+    where
+        T: Copy,
+    {
+        let (front, back) = self.as_slices();
+        ArrayWindows::new(front, back)
+    }
+
+    /// Returns an iterator over non-overlapping chunks of `size` elements,
+    /// front to back. The last chunk is shorter if `size` does not divide the
+    /// length.
+    ///
+    /// As with [`windows`](Self::windows), each chunk is a pair of slices: the
+    /// first slice followed by the second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(5);
+    /// deque.extend([1, 2, 3, 4, 5, 6]);
+    /// let batches: Vec<Vec<i32>> = deque
+    ///     .chunks(2)
+    ///     .map(|(a, b)| a.iter().chain(b).copied().collect())
+    ///     .collect();
+    /// assert_eq!(batches, [vec![2, 3], vec![4, 5], vec![6]]);
+    /// ```
+    pub fn chunks(&self, size: usize) -> Chunks<'_, T> {
+        let (front, back) = self.as_slices();
+        Chunks::new(front, back, size)
+    }
+
+    /// Returns an iterator over non-overlapping chunks of exactly `size`
+    /// elements, front to back. Elements left over at the back are skipped and
+    /// available through [`ChunksExact::remainder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(5);
+    /// deque.extend([1, 2, 3, 4, 5, 6]);
+    /// let chunks = deque.chunks_exact(2);
+    /// assert_eq!(chunks.remainder(), (&[6][..], &[][..]));
+    /// assert_eq!(chunks.len(), 2);
+    /// ```
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact<'_, T> {
+        let (front, back) = self.as_slices();
+        ChunksExact::new(front, back, size)
+    }
+
+    /// Returns the contents of the deque as a pair of slices (front to back).
+    ///
+    /// The second slice is non-empty only when the contents wrap around the
+    /// end of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3]);
+    /// dq.push_back(4);
+    /// assert_eq!(dq.as_slices(), (&[2, 3][..], &[4][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let head_len = (self.cap - self.idx).min(self.len);
+        unsafe {
+            (
+                slice::from_raw_parts(self.ptr.as_ptr().add(self.idx), head_len),
+                slice::from_raw_parts(self.ptr.as_ptr(), self.len - head_len),
+            )
+        }
+    }
+
+    /// Returns the contents of the deque as a pair of mutable slices (front to back).
+    ///
+    /// The second slice is non-empty only when the contents wrap around the
+    /// end of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3]);
+    /// dq.push_back(4);
+    /// let (front, back) = dq.as_mut_slices();
+    /// front[0] = 20;
+    /// back[0] = 40;
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), vec![20, 3, 40]);
+    /// ```
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let head_len = (self.cap - self.idx).min(self.len);
+        unsafe {
+            (
+                slice::from_raw_parts_mut(self.ptr.as_ptr().add(self.idx), head_len),
+                slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len - head_len),
+            )
+        }
+    }
+
+    /// Returns a view of the elements in `range`, which can be indexed,
+    /// iterated and narrowed further without copying.
+    ///
+    /// `Index<Range<usize>>` cannot be implemented because the elements may
+    /// wrap around the end of the buffer, so no single `&[T]` covers them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is inverted or extends past the end of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 2, 3, 4, 5, 6]);
+    /// let view = dq.slice(1..3);
+    /// assert_eq!(view, [4, 5]);
+    /// assert_eq!(view[1], 5);
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> ArrayDequeView<'_, T> {
+        let range = resolve_range(range, self.len);
+        let (front, back) = sub_slices(self.as_slices(), range.start, range.end);
+        ArrayDequeView::new(front, back)
+    }
+
+    /// Returns a mutable view of the elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is inverted or extends past the end of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 2, 3, 4, 5, 6]);
+    /// for value in dq.slice_mut(2..).iter_mut() {
+    ///     *value *= 10;
+    /// }
+    /// assert_eq!(dq, [3, 4, 50, 60]);
+    /// ```
+    pub fn slice_mut<R: RangeBounds<usize>>(&mut self, range: R) -> ArrayDequeViewMut<'_, T> {
+        let range = resolve_range(range, self.len);
+        let (front, back) = sub_slices_mut(self.as_mut_slices(), range.start, range.end);
+        ArrayDequeViewMut::new(front, back)
+    }
+
+    /// Returns an iterator over the elements in `range`, front to back.
+    ///
+    /// The range is resolved against the deque once, so iterating does no
+    /// further bounds checks or wrap-around arithmetic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is inverted or extends past the end of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 2, 3, 4, 5, 6]);
+    /// assert!(dq.range(1..3).eq(&[4, 5]));
+    /// assert!(dq.range(2..).rev().eq(&[6, 5]));
+    /// ```
+    pub fn range<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        self.slice(range).iter()
+    }
+
+    /// Returns an iterator that allows modifying the elements in `range`,
+    /// front to back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is inverted or extends past the end of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 2, 3, 4, 5, 6]);
+    /// for value in dq.range_mut(..2) {
+    ///     *value = 0;
+    /// }
+    /// assert_eq!(dq, [0, 0, 5, 6]);
+    /// ```
+    pub fn range_mut<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+    ) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        let range = resolve_range(range, self.len);
+        let (front, back) = sub_slices_mut(self.as_mut_slices(), range.start, range.end);
+        front.iter_mut().chain(back)
+    }
+
+    /// Returns the free slots behind the back element as a pair of slices,
+    /// in the order that [`push_back`](Self::push_back) would fill them.
+    ///
+    /// The second slice is non-empty only when the free region wraps around
+    /// the end of the buffer. Together they hold `capacity() - len()` slots.
+    /// Write elements into them, then call [`advance_back`](Self::advance_back)
+    /// or [`advance_front`](Self::advance_front) to make them part of the
+    /// deque. Until then, writes are not visible and values written are
+    /// never dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(5);
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.pop_front();
+    /// deque.pop_front();
+    /// // One slot is free at the end of the buffer and two at its start.
+    /// let (first, second) = deque.spare_capacity_mut();
+    /// assert_eq!((first.len(), second.len()), (1, 2));
+    /// first[0].write(5);
+    /// second[0].write(6);
+    /// unsafe { deque.advance_back(2) };
+    /// assert!(deque.iter().eq(&[3, 4, 5, 6]));
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let free = self.cap - self.len;
+        let start = self.physical_index(self.len);
+        let head_len = (self.cap - start).min(free);
+        let ptr = self.ptr.as_ptr().cast::<MaybeUninit<T>>();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(start), head_len),
+                slice::from_raw_parts_mut(ptr, free - head_len),
+            )
+        }
+    }
+
+    /// Appends the first `n` free slots returned by
+    /// [`spare_capacity_mut`](Self::spare_capacity_mut) to the back of the
+    /// deque, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `capacity() - len()`.
+    ///
+    /// # Safety
+    ///
+    /// The first `n` slots of the spare capacity, counting through the first
+    /// slice and then the second, must hold initialized values of `T`. The
+    /// deque takes ownership of them and will drop them.
+    pub unsafe fn advance_back(&mut self, n: usize) {
+        assert!(
+            n <= self.cap - self.len,
+            "cannot advance past the end of the free space"
+        );
+        self.len += n;
+        self.record_pushes(n, self.len - n);
+    }
+
+    /// Prepends the last `n` free slots returned by
+    /// [`spare_capacity_mut`](Self::spare_capacity_mut) to the front of the
+    /// deque, in order.
+    ///
+    /// The free region runs from just behind the back around to just before
+    /// the front, so its last `n` slots are the ones
+    /// [`push_front`](Self::push_front) would fill, and they become the first
+    /// `n` elements in the same order as they appear in the spare slices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `capacity() - len()`.
+    ///
+    /// # Safety
+    ///
+    /// The last `n` slots of the spare capacity, counting through the first
+    /// slice and then the second, must hold initialized values of `T`. The
+    /// deque takes ownership of them and will drop them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(5);
+    /// deque.extend([3, 4]);
+    /// let (first, second) = deque.spare_capacity_mut();
+    /// assert_eq!((first.len(), second.len()), (3, 0));
+    /// first[1].write(1);
+    /// first[2].write(2);
+    /// unsafe { deque.advance_front(2) };
+    /// assert!(deque.iter().eq(&[1, 2, 3, 4]));
+    /// ```
+    pub unsafe fn advance_front(&mut self, n: usize) {
+        assert!(
+            n <= self.cap - self.len,
+            "cannot advance past the end of the free space"
+        );
+        // The new front lies `n` slots before the old one, which is at most
+        // `capacity()` away, so a single wrap brings it back into range.
+        self.idx = self.physical_index(self.cap - n);
+        self.len += n;
+        self.record_pushes(n, self.len - n);
+    }
+
+    /// Clones the contents into a new `Vec` (front to back).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3, 4]);
+    /// assert_eq!(dq.to_vec(), vec![2, 3, 4]);
+    /// ```
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let (front, back) = self.as_slices();
+        let mut vec = Vec::with_capacity(self.len);
+        vec.extend_from_slice(front);
+        vec.extend_from_slice(back);
+        vec
+    }
+
+    /// Copies the contents into `dst` (front to back) without modifying the
+    /// deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst.len()` differs from the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3, 4]);
+    /// let mut buf = [0; 3];
+    /// dq.copy_to_slice(&mut buf);
+    /// assert_eq!(buf, [2, 3, 4]);
+    /// ```
+    pub fn copy_to_slice(&self, dst: &mut [T])
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            dst.len(),
+            self.len,
+            "destination length does not match deque length"
+        );
+        let (front, back) = self.as_slices();
+        let (dst_front, dst_back) = dst.split_at_mut(front.len());
+        dst_front.copy_from_slice(front);
+        dst_back.copy_from_slice(back);
+    }
+
+    /// Clones the contents into `dst` (front to back) without modifying the
+    /// deque. The previous values in `dst` are dropped as they are overwritten.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst.len()` differs from the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend(["a", "b", "c", "d"].map(String::from));
+    /// let mut buf = vec![String::new(); 3];
+    /// dq.clone_into_slice(&mut buf);
+    /// assert_eq!(buf, ["b", "c", "d"]);
+    /// ```
+    pub fn clone_into_slice(&self, dst: &mut [T])
+    where
+        T: Clone,
+    {
+        assert_eq!(
+            dst.len(),
+            self.len,
+            "destination length does not match deque length"
+        );
+        let (front, back) = self.as_slices();
+        let (dst_front, dst_back) = dst.split_at_mut(front.len());
+        dst_front.clone_from_slice(front);
+        dst_back.clone_from_slice(back);
+    }
+
+    /// Returns `true` if the deque contains an element equal to `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3]);
+    /// assert!(dq.contains(&2));
+    /// assert!(!dq.contains(&4));
+    /// ```
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+        front.contains(x) || back.contains(x)
+    }
+
+    /// Returns the index (0 is front) of the first element equal to `x`, or
+    /// `None` if there is no such element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3, 2]);
+    /// assert_eq!(dq.index_of(&2), Some(0));
+    /// assert_eq!(dq.index_of(&3), Some(1));
+    /// assert_eq!(dq.index_of(&1), None);
+    /// ```
+    pub fn index_of(&self, x: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+        front
+            .iter()
+            .position(|e| e == x)
+            .or_else(|| back.iter().position(|e| e == x).map(|i| i + front.len()))
+    }
+
+    /// Binary searches a sorted deque for `x`.
+    ///
+    /// Returns `Ok(i)` with the front-relative index of a matching element, or
+    /// `Err(i)` with the index where `x` could be inserted to keep the deque
+    /// sorted. If several elements match, any one of them may be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 3, 5, 7]);
+    /// assert_eq!(dq.binary_search(&5), Ok(2));
+    /// assert_eq!(dq.binary_search(&4), Err(2));
+    /// ```
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|e| e.cmp(x))
+    }
+
+    /// Binary searches a sorted deque with a comparator function.
+    ///
+    /// The comparator returns the ordering of each element relative to the
+    /// target. Results follow the same convention as [`binary_search`].
+    ///
+    /// [`binary_search`]: ArrayDeque::binary_search
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 3, 5, 7]);
+    /// assert_eq!(dq.binary_search_by(|e| e.cmp(&7)), Ok(3));
+    /// assert_eq!(dq.binary_search_by(|e| e.cmp(&8)), Err(4));
+    /// ```
+    pub fn binary_search_by<'a, F>(&'a self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a T) -> Ordering,
+    {
+        let (front, back) = self.as_slices();
+        match back.first().map(&mut f) {
+            Some(Ordering::Equal) => Ok(front.len()),
+            Some(Ordering::Less) => back
+                .binary_search_by(f)
+                .map(|i| i + front.len())
+                .map_err(|i| i + front.len()),
+            _ => front.binary_search_by(f),
+        }
+    }
+
+    /// Binary searches a deque sorted by the key extracted with `f`.
+    ///
+    /// Results follow the same convention as [`binary_search`].
+    ///
+    /// [`binary_search`]: ArrayDeque::binary_search
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([(1, 'a'), (2, 'b'), (4, 'c')]);
+    /// assert_eq!(dq.binary_search_by_key(&2, |&(k, _)| k), Ok(1));
+    /// assert_eq!(dq.binary_search_by_key(&3, |&(k, _)| k), Err(2));
+    /// ```
+    pub fn binary_search_by_key<'a, B, F>(&'a self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a T) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|e| f(e).cmp(b))
+    }
+
+    /// Returns the index of the partition point of a deque partitioned by
+    /// `pred`: the front-relative index of the first element for which `pred`
+    /// returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(5);
+    /// dq.extend([1, 2, 3, 8, 9]);
+    /// assert_eq!(dq.partition_point(|&x| x <= 3), 3);
+    /// ```
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (front, back) = self.as_slices();
+        if let Some(true) = back.first().map(&mut pred) {
+            back.partition_point(pred) + front.len()
+        } else {
+            front.partition_point(pred)
+        }
+    }
+
+    /// Sorts the deque front to back, preserving the order of equal elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([3, 1, 4, 1, 5]);
+    /// dq.sort();
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), vec![1, 1, 4, 5]);
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.make_contiguous().sort();
+    }
+
+    /// Sorts the deque front to back with a comparator function, preserving
+    /// the order of equal elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 3, 2]);
+    /// dq.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.make_contiguous().sort_by(compare);
+    }
+
+    /// Sorts the deque front to back with a comparator function, without
+    /// preserving the order of equal elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 3, 2]);
+    /// dq.sort_unstable_by(|a, b| b.cmp(a));
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.make_contiguous().sort_unstable_by(compare);
+    }
+
+    /// Sorts the deque front to back by the key extracted with `f`, without
+    /// preserving the order of equal elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([-3, 1, -2]);
+    /// dq.sort_unstable_by_key(|x: &i32| x.abs());
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), vec![1, -2, -3]);
+    /// ```
+    pub fn sort_unstable_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.make_contiguous().sort_unstable_by_key(f);
+    }
+
+    /// Returns the maximum capacity of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let dq: ArrayDeque<i32> = ArrayDeque::new(5);
+    /// assert_eq!(dq.capacity(), 5);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns the number of elements currently in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// assert_eq!(dq.len(), 0);
+    /// dq.push_back(1);
+    /// assert_eq!(dq.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// assert!(dq.is_empty());
+    /// dq.push_back(1);
+    /// assert!(!dq.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the deque has reached its maximum capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(2);
+    /// dq.push_back(1);
+    /// dq.push_back(2);
+    /// assert!(dq.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.len == self.cap
+    }
+
+    /// Returns the number of elements stored by pushes, inserts, extends and
+    /// writes since the deque was created or the statistics were last reset,
+    /// including elements that have since been overwritten or removed.
+    ///
+    /// Pushes that store nothing, such as a failed `try_push_back`, are not
+    /// counted. The counter wraps around on overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3, 4, 5]);
+    /// assert_eq!(deque.total_pushed(), 5);
+    /// assert_eq!(deque.total_evicted(), 2);
+    /// assert_eq!(deque.max_len_seen(), 3);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn total_pushed(&self) -> u64 {
+        self.stats.pushed
+    }
+
+    /// Returns the number of elements that were overwritten to make room for
+    /// new ones since the deque was created or the statistics were last reset.
+    ///
+    /// Elements removed by pops, [`clear`](Self::clear) or truncation are not
+    /// evictions. The counter wraps around on overflow.
+    #[cfg(feature = "stats")]
+    pub fn total_evicted(&self) -> u64 {
+        self.stats.evicted
+    }
+
+    /// Returns the largest number of elements the deque has held since it was
+    /// created or the statistics were last reset.
+    #[cfg(feature = "stats")]
+    pub fn max_len_seen(&self) -> usize {
+        self.stats.max_len.max(self.len)
+    }
+
+    /// Resets [`total_pushed`](Self::total_pushed) and
+    /// [`total_evicted`](Self::total_evicted) to zero, and
+    /// [`max_len_seen`](Self::max_len_seen) to the current length.
+    ///
+    /// The statistics are only ever reset by this method; in particular,
+    /// [`clear`](Self::clear) leaves them untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.clear();
+    /// assert_eq!(deque.total_pushed(), 4);
+    /// deque.reset_stats();
+    /// deque.push_back(5);
+    /// assert_eq!(deque.total_pushed(), 1);
+    /// assert_eq!(deque.total_evicted(), 0);
+    /// assert_eq!(deque.max_len_seen(), 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.stats = Stats::new();
+    }
+
+    /// Removes all elements from the deque, properly dropping them,
+    /// and resets it to an empty state.
+    ///
+    /// With the `stats` feature, the counters are not reset; use
+    /// `reset_stats` for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.push_back(1);
+    /// dq.clear();
+    /// assert!(dq.is_empty());
+    /// assert_eq!(dq.len(), 0);
+    /// ```
+    pub fn clear(&mut self) {
+        for i in 0..self.len {
+            let idx = self.physical_index(i);
+            unsafe {
+                ptr::drop_in_place(self.ptr.as_ptr().add(idx));
+            }
+        }
+        self.len = 0;
+        self.idx = 0;
+    }
+
+    /// Shortens the deque, keeping the first `len` elements and dropping the
+    /// rest from the back.
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 2, 3, 4]);
+    /// dq.truncate(2);
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let old_len = self.len;
+        self.len = len;
+        for i in len..old_len {
+            unsafe {
+                ptr::drop_in_place(self.ptr.as_ptr().add(self.physical_index(i)));
+            }
+        }
+    }
+
+    /// Shortens the deque, keeping the last `len` elements and dropping the
+    /// rest from the front.
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([1, 2, 3, 4]);
+    /// dq.truncate_front(2);
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+    /// ```
+    pub fn truncate_front(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let removed = self.len - len;
+        let old_idx = self.idx;
+        self.idx = self.physical_index(removed);
+        self.len = len;
+        for i in 0..removed {
+            unsafe {
+                ptr::drop_in_place(self.ptr.as_ptr().add(self.wrap_add(old_idx, i)));
+            }
+        }
+    }
+
+    /// Pushes every item of `iter` to the back, as the [`Extend`] impl does,
+    /// and returns how many were pushed.
+    ///
+    /// Under [`OverflowPolicy::Reject`], items are only taken from the iterator
+    /// while there is free space, so passing `iter.by_ref()` keeps the rest.
+    ///
+    /// # Returns
+    ///
+    /// The number of items pushed, including any that were evicted again. A
+    /// zero-capacity deque stores nothing and returns 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an item does not fit under [`OverflowPolicy::Panic`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{ArrayDeque, OverflowPolicy};
+    ///
+    /// let mut commands = ArrayDeque::with_policy(3, OverflowPolicy::Reject);
+    /// let mut pending = ["start", "load", "run", "stop"].into_iter();
+    /// assert_eq!(commands.extend_back(pending.by_ref()), 3);
+    /// assert_eq!(pending.next(), Some("stop"));
+    /// ```
+    pub fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> usize {
+        let mut iter = iter.into_iter();
+        let mut count = 0;
+        if self.policy == OverflowPolicy::OverwriteOldest
+            && self.cap != 0
+            && let (lower, Some(upper)) = iter.size_hint()
+            && lower == upper
+            && lower >= self.cap
+        {
+            let old_len = self.len;
+            self.clear();
+            if lower > self.cap {
+                iter.nth(lower - self.cap - 1);
+            }
+            // The skipped items count as pushed and immediately overwritten.
+            self.record_pushes(lower - self.cap, old_len);
+            count = lower - self.cap;
+        }
+        loop {
+            if self.is_full() && self.policy == OverflowPolicy::Reject {
+                break;
+            }
+            let Some(item) = iter.next() else { break };
+            if self.rejects_push() {
+                // Only a zero-capacity deque gets here.
+                break;
+            }
+            drop(self.push_back_unchecked(item));
+            count += 1;
+        }
+        count
+    }
+
+    /// Prepends every item of `iter`, so that the first item ends up at index 0
+    /// and the existing elements follow the new ones in their original order.
+    ///
+    /// If the result would exceed the capacity, elements are evicted from the back as
+    /// with [`push_front`](Self::push_front): existing elements go first, then
+    /// any items beyond the first `capacity()`, which are consumed and dropped.
+    /// Under [`OverflowPolicy::Reject`], items are only taken from the iterator
+    /// while there is free space, so passing `iter.by_ref()` keeps the rest.
+    ///
+    /// # Returns
+    ///
+    /// The number of items pushed, including any that were evicted again. A
+    /// zero-capacity deque stores nothing and returns 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an item does not fit under [`OverflowPolicy::Panic`]. The
+    /// items pushed before that stay at the front, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend([4, 5]);
+    /// assert_eq!(dq.extend_front([1, 2, 3]), 3);
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    /// ```
+    pub fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> usize {
+        /// Pushing to the front reverses the new items; this puts the first
+        /// `count` elements back in order when dropped, so the order also
+        /// holds if the iterator or the overflow policy panics.
+        struct Unreverse<'d, T, A: Allocator> {
+            deque: &'d mut ArrayDeque<T, A>,
+            count: usize,
+        }
+
+        impl<T, A: Allocator> Drop for Unreverse<'_, T, A> {
+            fn drop(&mut self) {
+                let (mut i, mut j) = (0, self.count);
+                while i + 1 < j {
+                    j -= 1;
+                    self.deque.swap(i, j);
+                    i += 1;
+                }
+            }
+        }
+
+        let overwrite = self.policy == OverflowPolicy::OverwriteOldest;
+        let mut iter = iter.into_iter();
+        let mut guard = Unreverse {
+            deque: self,
+            count: 0,
+        };
+        while !overwrite || guard.count < guard.deque.cap {
+            if guard.deque.is_full() && guard.deque.policy == OverflowPolicy::Reject {
+                break;
+            }
+            let Some(item) = iter.next() else { break };
+            if guard.deque.rejects_push() {
+                // Only a zero-capacity deque gets here.
+                break;
+            }
+            drop(guard.deque.push_front_unchecked(item));
+            guard.count += 1;
+        }
+        let mut count = guard.count;
+        drop(guard);
+        if overwrite {
+            let dropped = iter.map(drop).count();
+            self.record_pushes(dropped, self.len);
+            if self.cap != 0 {
+                count += dropped;
+            }
+        }
+        count
+    }
+
+    /// Clones every element of `slice` onto the back of the deque.
+    ///
+    /// As with repeated [`push_back`](Self::push_back), the oldest elements are
+    /// overwritten once the deque is full; if `slice` is longer than the
+    /// capacity, only its last `capacity()` elements are cloned. For `Copy`
+    /// element types, [`extend_from_copy_slice`](Self::extend_from_copy_slice)
+    /// does the same with at most two memory copies.
+    ///
+    /// Under [`OverflowPolicy::Reject`] only the leading elements that fit in
+    /// the free space are cloned. Under [`OverflowPolicy::Panic`] it panics,
+    /// without modifying the deque, if `slice` does not fit.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements of `slice` pushed, including any that were
+    /// evicted again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// dq.extend_from_slice(&[1, 2, 3]);
+    /// dq.extend_from_slice(&[4, 5]);
+    /// assert_eq!(dq, [2, 3, 4, 5]);
+    /// dq.extend_from_slice(&[6, 7, 8, 9, 10, 11]);
+    /// assert_eq!(dq, [8, 9, 10, 11]);
+    /// ```
+    pub fn extend_from_slice(&mut self, slice: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        let Some((slice, old_len, pushed)) = self.make_room_for(slice) else {
+            return 0;
+        };
+        let start = self.physical_index(self.len);
+        let (head, tail) = slice.split_at((self.cap - start).min(slice.len()));
+        self.write_cloned(start, head);
+        self.write_cloned(0, tail);
+        self.record_pushes(pushed, old_len);
+        pushed
+    }
+
+    /// Copies every element of `slice` onto the back of the deque.
+    ///
+    /// Behaves exactly like [`extend_from_slice`](Self::extend_from_slice),
+    /// including the overflow policy and return value, but since `T` is `Copy` the elements are
+    /// written with at most two `memcpy`s, one on each side of the wrap point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut millivolts = ArrayDeque::new(4);
+    /// millivolts.extend_from_copy_slice(&[3300, 3290, 3310]);
+    /// millivolts.extend_from_copy_slice(&[3305, 3295]);
+    /// assert_eq!(millivolts, [3290, 3310, 3305, 3295]);
+    /// ```
+    pub fn extend_from_copy_slice(&mut self, slice: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let Some((slice, old_len, pushed)) = self.make_room_for(slice) else {
+            return 0;
+        };
+        let start = self.physical_index(self.len);
+        let (head, tail) = slice.split_at((self.cap - start).min(slice.len()));
+        self.copy_in(start, head);
+        self.copy_in(0, tail);
+        self.record_pushes(pushed, old_len);
+        pushed
+    }
+
+    /// Frees space at the back for `slice` as the overflow policy dictates.
+    ///
+    /// Returns the part of `slice` to write right after the back element,
+    /// together with the length before any eviction and the number of
+    /// elements to count as pushed, or `None` if nothing is to be written.
+    fn make_room_for<'s>(&mut self, slice: &'s [T]) -> Option<(&'s [T], usize, usize)> {
+        let free = self.cap - self.len;
+        let slice = if slice.len() > free && !self.evict_on_overflow() {
+            &slice[..free]
+        } else {
+            slice
+        };
+        if slice.is_empty() || self.cap == 0 {
+            return None;
+        }
+        let (old_len, pushed) = (self.len, slice.len());
+        let slice = if slice.len() >= self.cap {
+            self.clear();
+            &slice[slice.len() - self.cap..]
+        } else {
+            self.truncate_front(self.cap - slice.len());
+            slice
+        };
+        Some((slice, old_len, pushed))
+    }
+
+    /// Clones `src` into the free slots starting at physical index `at`,
+    /// counting each one as it is written so a panicking clone leaks nothing.
+    /// The caller must ensure the run fits before the end of the buffer and
+    /// that it starts right after the current back element.
+    fn write_cloned(&mut self, at: usize, src: &[T])
+    where
+        T: Clone,
+    {
+        debug_assert!(at + src.len() <= self.cap);
+        let dst = unsafe { self.ptr.as_ptr().add(at) };
+        for (i, item) in src.iter().enumerate() {
+            unsafe { dst.add(i).write(item.clone()) };
+            self.len += 1;
+        }
+    }
+
+    /// Copies `src` into the free slots starting at physical index `at`, with
+    /// the same requirements as [`write_cloned`](Self::write_cloned).
+    fn copy_in(&mut self, at: usize, src: &[T])
+    where
+        T: Copy,
+    {
+        debug_assert!(at + src.len() <= self.cap);
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), self.ptr.as_ptr().add(at), src.len());
+        }
+        self.len += src.len();
+    }
+
+    /// Moves all elements into a new deque, leaving `self` empty with the same
+    /// capacity.
+    ///
+    /// The existing buffer is handed to the returned deque as-is and `self`
+    /// receives a fresh allocation, so no element is moved or dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`new`](ArrayDeque::new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2]);
+    /// let batch = dq.take();
+    /// assert_eq!(batch, [1, 2]);
+    /// assert!(dq.is_empty());
+    /// assert_eq!(dq.capacity(), 3);
+    /// ```
+    pub fn take(&mut self) -> Self
+    where
+        A: Clone,
+    {
+        let mut empty = Self::new_in(self.cap, self.alloc.clone());
+        empty.policy = self.policy;
+        #[cfg(feature = "stats")]
+        core::mem::swap(&mut empty.stats, &mut self.stats);
+        core::mem::replace(self, empty)
+    }
+
+    /// Changes the capacity of the deque, moving its contents into a newly
+    /// allocated buffer.
+    ///
+    /// Growing keeps every element. Shrinking below `len()` keeps the newest
+    /// `new_cap` elements and drops the oldest ones from the front, just as
+    /// pushing onto a full deque would.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new buffer cannot be allocated, in which case
+    /// the deque is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3]);
+    /// dq.set_capacity(5).unwrap();
+    /// dq.push_back(4);
+    /// assert_eq!(dq, [1, 2, 3, 4]);
+    ///
+    /// dq.set_capacity(2).unwrap();
+    /// assert_eq!(dq, [3, 4]);
+    /// ```
+    pub fn set_capacity(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        if new_cap == self.cap {
+            return Ok(());
+        }
+        let new_layout =
+            Layout::array::<T>(new_cap).map_err(|_| TryReserveError::CapacityOverflow)?;
+        let new_ptr: NonNull<T> = if new_layout.size() == 0 {
+            NonNull::dangling()
+        } else {
+            self.alloc
+                .allocate(new_layout)
+                .map_err(|_| TryReserveError::AllocError { layout: new_layout })?
+                .cast()
+        };
+
+        self.truncate_front(new_cap);
+        let (front, back) = self.as_slices();
+        unsafe {
+            ptr::copy_nonoverlapping(front.as_ptr(), new_ptr.as_ptr(), front.len());
+            ptr::copy_nonoverlapping(back.as_ptr(), new_ptr.as_ptr().add(front.len()), back.len());
+        }
+
+        let old_layout = Layout::array::<T>(self.cap).expect("Invalid layout");
+        if old_layout.size() != 0 {
+            unsafe {
+                self.alloc.deallocate(self.ptr.cast(), old_layout);
+            }
+        }
+        self.ptr = new_ptr;
+        self.cap = new_cap;
+        self.idx = 0;
+        Ok(())
+    }
+
+    /// Consumes the deque and returns a deque of the same capacity, allocator
+    /// and [`OverflowPolicy`] holding `f` applied to each element, in the
+    /// same order.
+    ///
+    /// Unlike `into_iter().map(f).collect()`, which sizes the new deque to
+    /// its length, this keeps the original capacity.
+    ///
+    /// If `f` panics, the elements mapped so far and those not yet mapped are
+    /// all dropped, and both buffers are freed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer for `capacity()` elements of `U` overflows
+    /// `isize::MAX` bytes. If the allocator fails, [`handle_alloc_error`] is
+    /// called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut raw = ArrayDeque::new(4);
+    /// raw.extend([512u16, 1024, 2048]);
+    /// let millivolts = raw.map(|x| u32::from(x) * 3300 / 4096);
+    /// assert_eq!(millivolts, [412, 825, 1650]);
+    /// assert_eq!(millivolts.capacity(), 4);
+    /// ```
+    pub fn map<U, F>(self, mut f: F) -> ArrayDeque<U, A>
+    where
+        F: FnMut(T) -> U,
+    {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so the allocator is moved out of it
+        // exactly once, and ownership of the buffer passes to `source`.
+        let alloc = unsafe { ptr::read(&this.alloc) };
+        // Both deques borrow the allocator while elements move across, so a
+        // panic in `f` unwinds through their destructors, which drop whatever
+        // each holds and free both buffers.
+        let mut source = ArrayDeque {
+            ptr: this.ptr,
+            cap: this.cap,
+            len: this.len,
+            idx: this.idx,
+            alloc: &alloc,
+            policy: this.policy,
+            #[cfg(feature = "stats")]
+            stats: this.stats,
+            _marker: PhantomData,
+        };
+        let mut target = ArrayDeque::new_in(this.cap, &alloc);
+        while let Some(value) = source.pop_front() {
+            target.push_back(f(value));
+        }
+        drop(source);
+        let target = ManuallyDrop::new(target);
+        ArrayDeque {
+            ptr: target.ptr,
+            cap: target.cap,
+            len: target.len,
+            idx: target.idx,
+            alloc,
+            policy: this.policy,
+            #[cfg(feature = "stats")]
+            stats: this.stats,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Consumes and leaks the deque, returning a mutable slice of its
+    /// contents (front to back), like `Vec::leak`.
+    ///
+    /// The contents are first rearranged into a single contiguous run. The
+    /// buffer, including any spare capacity, and the allocator are never
+    /// freed, so this is meant for data that lives for the rest of the
+    /// program. Leaking repeatedly leaks memory each time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut window = ArrayDeque::new(3);
+    /// window.extend([1, 2, 3, 4]);
+    /// let table: &'static mut [i32] = window.leak();
+    /// assert_eq!(table, [2, 3, 4]);
+    /// ```
+    pub fn leak<'a>(self) -> &'a mut [T]
+    where
+        A: 'a,
+    {
+        let mut deque = ManuallyDrop::new(self);
+        let contents = deque.make_contiguous();
+        let (ptr, len) = (contents.as_mut_ptr(), contents.len());
+        // SAFETY: the deque is never dropped, so the buffer stays allocated
+        // and its `len` initialized slots are never touched again except
+        // through the returned slice.
+        unsafe { slice::from_raw_parts_mut(ptr, len) }
+    }
+
+    /// Compares the contents against a slice in logical (front-to-back) order.
+    fn eq_slice<U>(&self, other: &[U]) -> bool
+    where
+        T: PartialEq<U>,
+    {
+        let (front, back) = self.as_slices();
+        self.len == other.len() && front == &other[..front.len()] && back == &other[front.len()..]
+    }
+
+    /// Rearranges the buffer so the contents occupy a single contiguous run
+    /// and returns it as a slice.
+    fn make_contiguous(&mut self) -> &mut [T] {
+        if self.idx + self.len > self.cap {
+            // Rotating the whole buffer only moves bytes around, so the
+            // uninitialized slots can be shuffled along with the live ones.
+            let buf = unsafe {
+                slice::from_raw_parts_mut(self.ptr.as_ptr() as *mut MaybeUninit<T>, self.cap)
+            };
+            buf.rotate_left(self.idx);
+            self.idx = 0;
+        }
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr().add(self.idx), self.len) }
+    }
+
+    /// Drops all elements and returns the buffer to the allocator. Only called
+    /// from `Drop`.
+    fn drop_and_dealloc(&mut self) {
+        self.clear();
+        let layout = Layout::array::<T>(self.cap).expect("Invalid layout");
+        if layout.size() != 0 {
+            unsafe {
+                self.alloc.deallocate(self.ptr.cast(), layout);
+            }
+        }
+    }
+
+    /// Takes the deque apart into its buffer, capacity, length, front index and
+    /// allocator, without dropping anything. The caller becomes responsible for
+    /// the elements and the allocation.
+    #[cfg(feature = "spsc")]
+    pub(crate) fn into_raw_parts(self) -> (NonNull<T>, usize, usize, usize, A) {
+        let deque = ManuallyDrop::new(self);
+        // SAFETY: `deque` is never used or dropped again, so the allocator is
+        // moved out exactly once.
+        let alloc = unsafe { ptr::read(&deque.alloc) };
+        (deque.ptr, deque.cap, deque.len, deque.idx, alloc)
+    }
+
+    /// Updates the `stats` counters after `pushed` elements were stored in a
+    /// deque that held `old_len` elements. Whatever the length fell short of
+    /// `old_len + pushed` is counted as evicted.
+    #[inline]
+    fn record_pushes(&mut self, pushed: usize, old_len: usize) {
+        #[cfg(feature = "stats")]
+        self.stats
+            .record(pushed, old_len + pushed - self.len, self.len);
+        #[cfg(not(feature = "stats"))]
+        let _ = (pushed, old_len);
+    }
+
+    /// Maps a logical index (0 is front) to its slot in the buffer.
+    #[inline]
+    fn physical_index(&self, index: usize) -> usize {
+        self.wrap_add(self.idx, index)
+    }
+
+    /// Returns the slot `b` places after slot `a`, where `a < capacity()` and
+    /// `b <= capacity()`. A compare and subtract stands in for `%`, which
+    /// would compile to a division since the capacity is only known at
+    /// runtime.
+    #[inline]
+    fn wrap_add(&self, a: usize, b: usize) -> usize {
+        let idx = a + b;
+        if idx >= self.cap { idx - self.cap } else { idx }
+    }
+
+    /// Returns the slot `b` places before slot `a`, where `a < capacity()`
+    /// and `b <= capacity()`.
+    #[inline]
+    fn wrap_sub(&self, a: usize, b: usize) -> usize {
+        if a >= b { a - b } else { a + self.cap - b }
+    }
+
+    /// Maps a position counted from the back (0 is back) to its slot, or
+    /// returns `None` if it is out of bounds.
+    #[inline]
+    fn back_slot(&self, index: usize) -> Option<usize> {
+        (index < self.len).then(|| self.physical_index(self.len - 1 - index))
+    }
+
+    /// Bitwise-moves the element at logical index `from` into logical index `to`.
+    ///
+    /// # Safety
+    ///
+    /// `from` must hold an initialized element, and the caller must treat it as
+    /// uninitialized afterwards. Whatever was in `to` is overwritten without
+    /// being dropped.
+    #[inline]
+    unsafe fn move_slot(&mut self, from: usize, to: usize) {
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.ptr.as_ptr().add(self.physical_index(from)),
+                self.ptr.as_ptr().add(self.physical_index(to)),
+                1,
+            );
+        }
+    }
+}
+
+// SAFETY: `ArrayDeque<T, A>` uniquely owns its allocation and the `T`s stored
+// in it, exactly like `Vec<T, A>`. Moving the deque to another thread moves
+// those values and the allocator with it, and `&ArrayDeque<T, A>` only hands
+// out `&T` and `&A`.
+unsafe impl<T: Send, A: Allocator + Send> Send for ArrayDeque<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for ArrayDeque<T, A> {}
+
+#[cfg(not(feature = "unstable"))]
+impl<T, A: Allocator> Drop for ArrayDeque<T, A> {
+    /// Drops all elements and deallocates the heap buffer.
+    fn drop(&mut self) {
+        self.drop_and_dealloc();
+    }
+}
+
+// SAFETY: dropping the deque only drops the `T`s it owns (which `PhantomData<T>`
+// tells dropck about) and frees the buffer; it never reads through a `T`, so
+// borrows held by the elements are allowed to dangle at this point, as with `Vec`.
+#[cfg(feature = "unstable")]
+unsafe impl<#[may_dangle] T, A: Allocator> Drop for ArrayDeque<T, A> {
+    /// Drops all elements and deallocates the heap buffer.
+    fn drop(&mut self) {
+        self.drop_and_dealloc();
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for ArrayDeque<T, A> {
+    /// Formats the deque as a debug list (front to back).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format, A: Allocator> defmt::Format for ArrayDeque<T, A> {
+    /// Formats the deque as a list (front to back), like its `Debug` output.
+    fn format(&self, fmt: defmt::Formatter<'_>) {
+        defmt::write!(fmt, "[");
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                defmt::write!(fmt, ", ");
+            }
+            defmt::write!(fmt, "{}", item);
+        }
+        defmt::write!(fmt, "]");
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for ArrayDeque<T, A> {
+    /// Creates a deep copy of the deque with identical capacity and contents,
+    /// allocated from a clone of the same allocator.
+    ///
+    /// The two contiguous halves of the source are cloned straight into the
+    /// start of the new buffer, so the copy is linearized. If an element's
+    /// `clone` panics, the elements cloned so far are dropped and the new
+    /// buffer is freed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::new(3);
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    /// let cloned = deque.clone();
+    /// assert_eq!(deque.len(), cloned.len());
+    /// assert_eq!(deque[0], cloned[0]);
+    /// ```
+    fn clone(&self) -> Self {
+        let mut new = ArrayDeque::new_in(self.cap, self.alloc.clone());
+        new.policy = self.policy;
+        let (front, back) = self.as_slices();
+        new.write_cloned(0, front);
+        new.write_cloned(front.len(), back);
+        #[cfg(feature = "stats")]
+        {
+            new.stats = self.stats;
+        }
+        new
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq for ArrayDeque<T, A> {
+    /// Two deques are equal if they have the same length
+    /// and each element compares equal in order.
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for ArrayDeque<T, A> {}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<[U]> for ArrayDeque<T, A> {
+    /// Compares the deque's elements, front to back, with the slice.
+    fn eq(&self, other: &[U]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<&[U]> for ArrayDeque<T, A> {
+    /// Compares the deque's elements, front to back, with the slice.
+    fn eq(&self, other: &&[U]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<&mut [U]> for ArrayDeque<T, A> {
+    /// Compares the deque's elements, front to back, with the slice.
+    fn eq(&self, other: &&mut [U]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator, const N: usize> PartialEq<[U; N]> for ArrayDeque<T, A> {
+    /// Compares the deque's elements, front to back, with the array.
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator, const N: usize> PartialEq<&[U; N]> for ArrayDeque<T, A> {
+    /// Compares the deque's elements, front to back, with the array.
+    fn eq(&self, other: &&[U; N]) -> bool {
+        self.eq_slice(*other)
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<Vec<U>> for ArrayDeque<T, A> {
+    /// Compares the deque's elements, front to back, with the vector.
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<ArrayDeque<U, A>> for [T] {
+    /// Compares the slice with the deque's elements, front to back.
+    fn eq(&self, other: &ArrayDeque<U, A>) -> bool {
+        let (front, back) = other.as_slices();
+        self.len() == other.len() && &self[..front.len()] == front && &self[front.len()..] == back
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator, const N: usize> PartialEq<ArrayDeque<U, A>> for [T; N] {
+    /// Compares the array with the deque's elements, front to back.
+    fn eq(&self, other: &ArrayDeque<U, A>) -> bool {
+        self[..] == *other
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<ArrayDeque<U, A>> for Vec<T> {
+    /// Compares the vector with the deque's elements, front to back.
+    fn eq(&self, other: &ArrayDeque<U, A>) -> bool {
+        self[..] == *other
+    }
+}
+
+impl<T: PartialOrd, A: Allocator> PartialOrd for ArrayDeque<T, A> {
+    /// Compares the deques lexicographically from front to back, with the
+    /// shorter deque ordered first when one is a prefix of the other.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, A: Allocator> Ord for ArrayDeque<T, A> {
+    /// Compares the deques lexicographically from front to back, with the
+    /// shorter deque ordered first when one is a prefix of the other.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash, A: Allocator> Hash for ArrayDeque<T, A> {
+    /// Hashes the length followed by each element from front to back, so equal
+    /// deques hash identically regardless of where the buffer wraps.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        self.iter().for_each(|item| item.hash(state));
+    }
+}
+
+impl<T, A: Allocator> Index<usize> for ArrayDeque<T, A> {
+    type Output = T;
+
+    /// Indexed access into the deque (0 is front).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("Index out of bounds")
+    }
+}
+
+impl<T, A: Allocator> IndexMut<usize> for ArrayDeque<T, A> {
+    /// Mutable indexed access into the deque (0 is front).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("Index out of bounds")
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for ArrayDeque<T, A> {
+    /// Extends the deque by pushing each item of the iterator to the back.
+    ///
+    /// When the iterator reports an exact length (`size_hint` with equal bounds)
+    /// of at least `capacity()`, the result is known up front: the current contents
+    /// are dropped, the leading items that would only be overwritten are
+    /// consumed and dropped without being written into the buffer, and the last
+    /// `capacity()` items are stored. Every item is still produced exactly once.
+    ///
+    /// Under [`OverflowPolicy::Reject`], items are only taken from the
+    /// iterator while there is free space. Under [`OverflowPolicy::Panic`],
+    /// the first item that does not fit causes a panic. Use
+    /// [`extend_back`](ArrayDeque::extend_back) to learn how many items were
+    /// pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(5);
+    /// dq.extend([1,2,3]);
+    /// assert_eq!(dq.len(), 3);
+    /// ```
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.extend_back(iter);
+    }
+}
+
+impl<'a, T: Copy + 'a, A: Allocator> Extend<&'a T> for ArrayDeque<T, A> {
+    /// Extends the deque by pushing a copy of each referenced item to the back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq: ArrayDeque<i32> = ArrayDeque::new(3);
+    /// dq.extend([1, 2, 3, 4].iter());
+    /// assert_eq!(dq, [2, 3, 4]);
+    /// ```
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> FromIterator<T> for ArrayDeque<T> {
+    /// Creates a deque from an iterator by collecting all items.
+    /// Capacity == number of items.
+    ///
+    /// When the iterator reports an exact length, the buffer is allocated
+    /// once and the items are written straight into it. Otherwise they are
+    /// collected into a `Vec` whose buffer the deque then takes over, as
+    /// with `From<Vec<T>>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let dq: ArrayDeque<_> = (0..3).collect();
+    /// assert_eq!(dq.len(), 3);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let (lower, upper) = iter.size_hint();
+        if upper != Some(lower) {
+            return ArrayDeque::from(iter.collect::<Vec<T>>());
+        }
+
+        let mut deque = ArrayDeque::new(lower);
+        while !deque.is_full() {
+            match iter.next() {
+                Some(item) => deque.push_back(item),
+                // The hint was too large; `From<Vec<T>>` shrinks the buffer.
+                None => return ArrayDeque::from(deque.into_vec()),
+            }
+        }
+        match iter.next() {
+            Some(item) => {
+                // The hint was too small; finish in a `Vec` instead.
+                let mut vec = deque.into_vec();
+                vec.push(item);
+                vec.extend(iter);
+                ArrayDeque::from(vec)
+            }
+            None => deque,
+        }
+    }
+}
+
+impl<T: Clone> From<&[T]> for ArrayDeque<T> {
+    /// Clones all elements from a slice into a new deque.
+    fn from(slice: &[T]) -> Self {
+        let mut deque = ArrayDeque::new(slice.len());
+        for item in slice {
+            deque.push_back(item.clone());
+        }
+        deque
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArrayDeque<T> {
+    /// Takes ownership of each element in the array.
+    fn from(array: [T; N]) -> Self {
+        let mut deque = ArrayDeque::new(N);
+        for item in array {
+            deque.push_back(item);
+        }
+        deque
+    }
+}
+
+impl<T> From<Vec<T>> for ArrayDeque<T> {
+    /// Takes ownership of the vector's elements, with capacity equal to its
+    /// length.
+    ///
+    /// The vector is shrunk to fit, after which its buffer is taken over
+    /// without copying. If the allocator leaves spare capacity behind, the
+    /// elements are instead moved to a new buffer with a single bulk copy.
+    fn from(mut vec: Vec<T>) -> Self {
+        let len = vec.len();
+        vec.shrink_to_fit();
+        if vec.capacity() != len {
+            let mut deque = ArrayDeque::new(len);
+            unsafe {
+                ptr::copy_nonoverlapping(vec.as_ptr(), deque.ptr.as_ptr(), len);
+                // The elements now belong to the deque; the vector only frees its buffer.
+                vec.set_len(0);
+            }
+            deque.len = len;
+            return deque;
+        }
+
+        let mut vec = ManuallyDrop::new(vec);
+        let mut deque = ArrayDeque::new(0);
+        // SAFETY: the buffer was allocated by the global allocator with the
+        // layout of `[T; len]`, which is what the deque frees it with, and
+        // all `len` slots are initialized. The vector is never dropped, so
+        // ownership passes to the deque.
+        deque.ptr = unsafe { NonNull::new_unchecked(vec.as_mut_ptr()) };
+        deque.cap = len;
+        deque.len = len;
+        deque
+    }
+}
+
+impl<T> From<ArrayDeque<T>> for Vec<T> {
+    /// Converts the deque into a vector, reusing its buffer. See
+    /// [`ArrayDeque::into_vec`].
+    fn from(deque: ArrayDeque<T>) -> Self {
+        deque.into_vec()
+    }
+}
+
+impl<T> From<VecDeque<T>> for ArrayDeque<T> {
+    /// Takes ownership of elements from a `VecDeque` (front to back).
+    ///
+    /// The source is first made contiguous in place, then its buffer is taken
+    /// over as with `From<Vec<T>>`.
+    fn from(vec_deque: VecDeque<T>) -> Self {
+        ArrayDeque::from(Vec::from(vec_deque))
+    }
+}
+
+impl<T, A: Allocator> From<ArrayDeque<T, A>> for VecDeque<T> {
+    /// Converts this deque into a `VecDeque`, preserving order.
+    ///
+    /// Both halves of the ring are moved into the new buffer with bulk copies.
+    fn from(mut deque: ArrayDeque<T, A>) -> Self {
+        let mut vec = Vec::with_capacity(deque.len);
+        let (front, back) = deque.as_slices();
+        unsafe {
+            ptr::copy_nonoverlapping(front.as_ptr(), vec.as_mut_ptr(), front.len());
+            ptr::copy_nonoverlapping(back.as_ptr(), vec.as_mut_ptr().add(front.len()), back.len());
+            vec.set_len(deque.len);
+        }
+        // The elements were moved out, so the deque must not drop them again.
+        deque.len = 0;
+        VecDeque::from(vec)
+    }
+}
+
+impl<T: Clone, A: Allocator> From<&ArrayDeque<T, A>> for VecDeque<T> {
     /// Clones elements into a `VecDeque`, preserving order.
-    fn from(deque: &ArrayDeque<T>) -> Self {
+    fn from(deque: &ArrayDeque<T, A>) -> Self {
+        deque.iter().cloned().collect()
+    }
+}
+
+impl<T: Clone> From<&Vec<T>> for ArrayDeque<T> {
+    /// Clones each element from the vector.
+    fn from(vec: &Vec<T>) -> Self {
+        let mut deque = ArrayDeque::new(vec.len());
+        for item in vec {
+            deque.push_back(item.clone());
+        }
+        deque
+    }
+}
+
+impl<T: Clone, const N: usize> From<&[T; N]> for ArrayDeque<T> {
+    /// Clones each element from the array reference.
+    fn from(array: &[T; N]) -> Self {
+        let mut deque = ArrayDeque::new(N);
+        for item in array {
+            deque.push_back(item.clone());
+        }
+        deque
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize, A: Allocator> Serialize for ArrayDeque<T, A> {
+    /// Serializes the deque as a sequence (front to back).
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        for item in self.iter() {
+            seq.serialize_element(item)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArrayDeque<T> {
+    /// Deserializes a sequence into a deque (capacity == item count).
+    ///
+    /// When the format reports the sequence length up front, as most binary
+    /// formats do, the deque is allocated at that capacity and elements are
+    /// written into it directly. Otherwise (as with JSON) the elements are
+    /// first collected into a `Vec` and then moved into the deque. Since the
+    /// reported length is untrusted, at most about 1 MiB is allocated up
+    /// front; longer sequences also go through a `Vec`.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(ArrayDequeVisitor(PhantomData))
+    }
+}
+
+/// Limits a sequence length reported by the input to about 1 MiB worth of
+/// elements, like serde's own `size_hint::cautious`, so that a forged length
+/// cannot make the deserializer allocate more than the input can fill.
+#[cfg(feature = "serde")]
+fn cautious_capacity<T>(hint: usize) -> usize {
+    const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+    hint.min(MAX_PREALLOC_BYTES / mem::size_of::<T>().max(1))
+}
+
+/// Visits a sequence, writing straight into a deque when its length is known.
+#[cfg(feature = "serde")]
+struct ArrayDequeVisitor<T>(PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ArrayDequeVisitor<T> {
+    type Value = ArrayDeque<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        // The hint comes from the input, so trust it only up to a bounded
+        // preallocation; a missing or capped hint falls back to a `Vec` below.
+        let cap = seq.size_hint().map_or(0, cautious_capacity::<T>);
+        let mut deque = ArrayDeque::try_new(cap).map_err(de::Error::custom)?;
+        while let Some(item) = seq.next_element()? {
+            if deque.is_full() {
+                // The hint was missing, too small or capped; finish in a
+                // `Vec`, which grows as elements arrive.
+                let mut vec = deque.into_vec();
+                vec.push(item);
+                while let Some(item) = seq.next_element()? {
+                    vec.push(item);
+                }
+                return Ok(ArrayDeque::from(vec));
+            }
+            deque.push_back(item);
+        }
+        if !deque.is_full() {
+            deque.set_capacity(deque.len).map_err(de::Error::custom)?;
+        }
+        Ok(deque)
+    }
+}
+
+/// A [`DeserializeSeed`] that reads a sequence into an [`ArrayDeque`] whose
+/// capacity is chosen by the caller rather than taken from the input.
+///
+/// Sequences shorter than the capacity leave the rest of the deque free. What
+/// happens to a longer sequence depends on how the seed was created:
+///
+/// - [`with_capacity`](Self::with_capacity) fails with an `invalid_length`
+///   error.
+/// - [`overwriting`](Self::overwriting) keeps the last `cap` elements, as if
+///   each had been pushed with [`push_back`](ArrayDeque::push_back).
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::{ArrayDeque, ArrayDequeSeed};
+/// use serde::de::DeserializeSeed;
+///
+/// let mut json = serde_json::Deserializer::from_str("[1, 2, 3, 4, 5]");
+/// let recent: ArrayDeque<u32> = ArrayDequeSeed::overwriting(3)
+///     .deserialize(&mut json)
+///     .unwrap();
+/// assert_eq!(recent.capacity(), 3);
+/// assert_eq!(recent, [3, 4, 5]);
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct ArrayDequeSeed<T> {
+    /// Capacity of the deque to allocate
+    cap: usize,
+    /// Whether a longer sequence evicts the oldest elements instead of erroring
+    overwrite: bool,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> ArrayDequeSeed<T> {
+    /// Creates a seed that allocates `cap` slots and rejects longer sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{ArrayDeque, ArrayDequeSeed};
+    /// use serde::de::DeserializeSeed;
+    ///
+    /// let mut json = serde_json::Deserializer::from_str("[1, 2]");
+    /// let deque: ArrayDeque<u32> = ArrayDequeSeed::with_capacity(4)
+    ///     .deserialize(&mut json)
+    ///     .unwrap();
+    /// assert_eq!(deque.capacity(), 4);
+    /// assert_eq!(deque, [1, 2]);
+    ///
+    /// let mut json = serde_json::Deserializer::from_str("[1, 2, 3]");
+    /// assert!(ArrayDequeSeed::<u32>::with_capacity(2).deserialize(&mut json).is_err());
+    /// ```
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            cap,
+            overwrite: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a seed that allocates `cap` slots and keeps only the last
+    /// `cap` elements of longer sequences.
+    pub fn overwriting(cap: usize) -> Self {
+        Self {
+            cap,
+            overwrite: true,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for ArrayDequeSeed<T> {
+    type Value = ArrayDeque<T>;
+
+    /// Deserializes a sequence into a deque of the seed's capacity.
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ArrayDequeSeed<T> {
+    type Value = ArrayDeque<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.overwrite {
+            f.write_str("a sequence")
+        } else {
+            write!(f, "a sequence of at most {} elements", self.cap)
+        }
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        if let Some(len) = seq
+            .size_hint()
+            .filter(|&len| !self.overwrite && len > self.cap)
+        {
+            return Err(de::Error::invalid_length(len, &self));
+        }
+        let mut deque = ArrayDeque::try_new(self.cap).map_err(de::Error::custom)?;
+        while let Some(item) = seq.next_element()? {
+            if deque.is_full() && !self.overwrite {
+                return Err(de::Error::invalid_length(self.cap + 1, &self));
+            }
+            deque.push_back(item);
+        }
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<T: Encode, A: Allocator> Encode for ArrayDeque<T, A> {
+    /// Encodes the length followed by the elements from front to back.
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        (self.len as u64).encode(encoder)?;
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the length prefix written by `Encode` and allocates a deque of
+/// exactly that capacity.
+#[cfg(feature = "bincode")]
+fn bincode_allocate<T, D: Decoder>(decoder: &mut D) -> Result<ArrayDeque<T>, DecodeError> {
+    let len = u64::decode(decoder)?;
+    let len = usize::try_from(len).map_err(|_| DecodeError::OutsideUsizeRange(len))?;
+    decoder.claim_container_read::<T>(len)?;
+    ArrayDeque::try_new(len).map_err(|_| DecodeError::Other("failed to allocate ArrayDeque"))
+}
+
+#[cfg(feature = "bincode")]
+impl<Context, T: Decode<Context>> Decode<Context> for ArrayDeque<T> {
+    /// Decodes a deque whose capacity equals the encoded length.
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let mut deque = bincode_allocate(decoder)?;
+        while !deque.is_full() {
+            // The whole container was claimed up front; each element claims
+            // its own bytes again as it is decoded.
+            decoder.unclaim_bytes_read(size_of::<T>());
+            deque.push_back(T::decode(decoder)?);
+        }
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<'de, Context, T: BorrowDecode<'de, Context>> BorrowDecode<'de, Context> for ArrayDeque<T> {
+    /// Decodes a deque whose capacity equals the encoded length.
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let mut deque = bincode_allocate(decoder)?;
+        while !deque.is_full() {
+            decoder.unclaim_bytes_read(size_of::<T>());
+            deque.push_back(T::borrow_decode(decoder)?);
+        }
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T, A: Allocator> ArrayDeque<T, A> {
+    /// Overwrites every slot not currently holding an element with zeros.
+    ///
+    /// Removing or moving elements does not scrub the slots they leave behind:
+    /// after [`pop_front`](Self::pop_front), [`truncate`](Self::truncate),
+    /// [`truncate_front`](Self::truncate_front), a rotation and similar operations, stale
+    /// copies of the old values stay in the buffer until overwritten. Call
+    /// this afterwards to wipe them. Overwriting pushes replace the evicted
+    /// element in place, so they leave no copy behind (though the evicted
+    /// value itself is returned or dropped as usual).
+    ///
+    /// The writes are volatile, so they are not optimized away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut nonces = ArrayDeque::new(4);
+    /// nonces.extend([[1u8; 32], [2; 32], [3; 32]]);
+    /// let used = nonces.pop_front();
+    /// nonces.zeroize_spare_capacity();
+    /// assert_eq!(used, Some([1; 32]));
+    /// assert_eq!(nonces, [[2; 32], [3; 32]]);
+    /// ```
+    pub fn zeroize_spare_capacity(&mut self) {
+        for i in self.len..self.cap {
+            let slot = unsafe {
+                &mut *self
+                    .ptr
+                    .as_ptr()
+                    .add(self.physical_index(i))
+                    .cast::<MaybeUninit<T>>()
+            };
+            slot.zeroize();
+        }
+    }
+}
+
+/// Zeroizes every element, empties the deque, and then wipes the whole buffer
+/// with [`zeroize_spare_capacity`](ArrayDeque::zeroize_spare_capacity), so
+/// stale copies left by earlier operations are cleared as well.
+///
+/// To scrub a deque automatically when it is dropped, wrap it in
+/// [`zeroize::Zeroizing`].
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::ArrayDeque;
+/// use zeroize::{Zeroize, Zeroizing};
+///
+/// let mut shares = ArrayDeque::new(2);
+/// shares.push_back([7u8; 32]);
+/// shares.zeroize();
+/// assert!(shares.is_empty());
+/// assert_eq!(shares.capacity(), 2);
+///
+/// let mut recent = Zeroizing::new(ArrayDeque::new(2));
+/// recent.push_back([9u8; 32]);
+/// // Zeroized here, as `recent` goes out of scope.
+/// ```
+#[cfg(feature = "zeroize")]
+impl<T: Zeroize, A: Allocator> Zeroize for ArrayDeque<T, A> {
+    /// Zeroizes all elements and the spare capacity, leaving the deque empty.
+    fn zeroize(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back).for_each(Zeroize::zeroize);
+        self.clear();
+        self.zeroize_spare_capacity();
+    }
+}
+
+/// Largest capacity picked when generating an arbitrary deque.
+#[cfg(feature = "arbitrary")]
+const ARBITRARY_MAX_CAPACITY: usize = 64;
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for ArrayDeque<T> {
+    /// Generates a deque with a capacity of at most 64, an arbitrary front
+    /// position so that wrapped layouts are exercised, and as many elements
+    /// as the input allows.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let cap = u.int_in_range(0..=ARBITRARY_MAX_CAPACITY)?;
+        let mut deque = ArrayDeque::new(cap);
+        if cap > 0 {
+            deque.idx = u.int_in_range(0..=cap - 1)?;
+        }
+        let len = u.arbitrary_len::<T>()?.min(cap);
+        for _ in 0..len {
+            deque.push_back(T::arbitrary(u)?);
+        }
+        Ok(deque)
+    }
+
+    /// Bounds the number of input bytes consumed by [`arbitrary`](Self::arbitrary).
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Self::try_size_hint(depth).unwrap_or_default()
+    }
+
+    /// Like [`size_hint`](Self::size_hint), but reports hitting the recursion
+    /// limit.
+    fn try_size_hint(depth: usize) -> Result<(usize, Option<usize>), MaxRecursionReached> {
+        size_hint::try_recursion_guard(depth, |depth| {
+            let (_, element) = T::try_size_hint(depth)?;
+            let header = (0, Some(ARBITRARY_HEADER_BYTES));
+            let elements = (0, element.map(|e| e * ARBITRARY_MAX_CAPACITY));
+            Ok(size_hint::and(header, elements))
+        })
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T: quickcheck::Arbitrary> quickcheck::Arbitrary for ArrayDeque<T> {
+    /// Generates a deque with a capacity of at most `g.size()`, an arbitrary
+    /// fill level, and an arbitrary front position so that wrapped layouts
+    /// are exercised.
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let cap = <usize as quickcheck::Arbitrary>::arbitrary(g) % (g.size() + 1);
+        let len = <usize as quickcheck::Arbitrary>::arbitrary(g) % (cap + 1);
+        let mut deque = ArrayDeque::new(cap);
+        if cap > 0 {
+            deque.idx = <usize as quickcheck::Arbitrary>::arbitrary(g) % cap;
+        }
+        for _ in 0..len {
+            deque.push_back(<T as quickcheck::Arbitrary>::arbitrary(g));
+        }
+        deque
+    }
+
+    /// Yields the same contents unwrapped, then with no spare capacity, then
+    /// with shrunk contents (keeping the same number of spare slots).
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let spare = self.cap - self.len;
+        let from_vec = move |items: Vec<T>| {
+            let mut deque = ArrayDeque::new(items.len() + spare);
+            deque.extend(items);
+            deque
+        };
+        let items = self.to_vec();
+        let unwrapped = (self.idx != 0).then(|| from_vec(items.clone()));
+        let tight = (spare > 0).then(|| ArrayDeque::from(items.clone()));
+        Box::new(
+            unwrapped
+                .into_iter()
+                .chain(tight)
+                .chain(items.shrink().map(from_vec)),
+        )
+    }
+}
+
+impl<A: Allocator> ArrayDeque<u8, A> {
+    /// Reads bytes from `reader` straight into the free space at the back of
+    /// the deque and returns how many were appended.
+    ///
+    /// The free space (at most two contiguous regions) is offered to the
+    /// reader in a single [`read_vectored`](io::Read::read_vectored) call, so
+    /// no intermediate buffer is involved. Buffered bytes are never
+    /// overwritten: if the deque is already full, the reader is not called and
+    /// `Ok(0)` is returned. Reads failing with
+    /// [`Interrupted`](io::ErrorKind::Interrupted) are retried; any other
+    /// error is returned with the deque unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let mut dq = ArrayDeque::new(4);
+    /// let mut input: &[u8] = b"abcdef";
+    /// assert_eq!(dq.read_from(&mut input).unwrap(), 4);
+    /// assert_eq!(dq, *b"abcd");
+    /// assert_eq!(dq.read_from(&mut input).unwrap(), 0);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_from<R: io::Read + ?Sized>(&mut self, reader: &mut R) -> io::Result<usize> {
+        let free = self.cap - self.len;
+        if free == 0 {
+            return Ok(0);
+        }
+        let start = self.physical_index(self.len);
+        let head_len = (self.cap - start).min(free);
+        // `Read` requires initialized buffers, so zero the free slots first.
+        let (head, tail) = unsafe {
+            let ptr = self.ptr.as_ptr();
+            ptr.add(start).write_bytes(0, head_len);
+            ptr.write_bytes(0, free - head_len);
+            (
+                slice::from_raw_parts_mut(ptr.add(start), head_len),
+                slice::from_raw_parts_mut(ptr, free - head_len),
+            )
+        };
+        let mut bufs = [io::IoSliceMut::new(head), io::IoSliceMut::new(tail)];
+        let read = loop {
+            match reader.read_vectored(&mut bufs) {
+                Ok(read) => break read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        };
+        let stored = read.min(free);
+        self.len += stored;
+        self.record_pushes(stored, self.len - stored);
+        Ok(read)
+    }
+
+    /// Copies as much of `src` as fits into the free space, without
+    /// overwriting anything, and returns how many bytes were copied.
+    #[cfg(feature = "embedded-io")]
+    fn push_bytes(&mut self, src: &[u8]) -> usize {
+        let n = src.len().min(self.cap - self.len);
+        self.extend_from_copy_slice(&src[..n]);
+        n
+    }
+
+    /// Moves bytes from the front into `dst` until either it is full or the
+    /// deque is empty, and returns how many were moved.
+    #[cfg(any(feature = "std", feature = "embedded-io"))]
+    pub(crate) fn pop_bytes(&mut self, dst: &mut [u8]) -> usize {
+        let n = dst.len().min(self.len);
+        let (front, back) = self.as_slices();
+        let split = n.min(front.len());
+        dst[..split].copy_from_slice(&front[..split]);
+        dst[split..n].copy_from_slice(&back[..n - split]);
+        self.truncate_front(self.len - n);
+        n
+    }
+}
+
+/// Writes append bytes to the back of the deque with the usual overwrite
+/// semantics: once the deque is full, the oldest bytes are silently discarded
+/// to make room, so the deque always holds the most recent `capacity()` bytes
+/// written. Writes never fail and always report the whole buffer as written.
+///
+/// The deque's [`OverflowPolicy`] changes this. Under `Reject`, writes stop
+/// at the free space and report a short write (`Ok(0)` once full, which makes
+/// `write_all` fail with [`WriteZero`](io::ErrorKind::WriteZero)). Under
+/// `Panic`, a write that does not fit panics.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::{ArrayDeque, OverflowPolicy};
+/// use std::io::Write;
+///
+/// let mut log = ArrayDeque::new(8);
+/// write!(log, "exit status: {}", 127).unwrap();
+/// assert_eq!(log, *b"tus: 127");
+///
+/// let mut frame = ArrayDeque::with_policy(4, OverflowPolicy::Reject);
+/// assert_eq!(frame.write(b"abcdef").unwrap(), 4);
+/// assert!(frame.write_all(b"g").is_err());
+/// assert_eq!(frame, *b"abcd");
+/// ```
+#[cfg(feature = "std")]
+impl<A: Allocator> io::Write for ArrayDeque<u8, A> {
+    /// Appends `buf` and reports how much of it was written: all of it,
+    /// unless the policy is [`OverflowPolicy::Reject`] and it does not fit.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let pushed = self.extend_from_copy_slice(buf);
+        Ok(match self.policy {
+            OverflowPolicy::Reject => pushed,
+            _ => buf.len(),
+        })
+    }
+
+    /// Appends each buffer in turn and reports how much was written, stopping
+    /// at the first one that does not fit completely.
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            let n = self.write(buf)?;
+            written += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Appends `buf`, failing with [`WriteZero`](io::ErrorKind::WriteZero)
+    /// if the policy is [`OverflowPolicy::Reject`] and it does not all fit.
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        if self.write(buf)? < buf.len() {
+            return Err(io::Error::new(io::ErrorKind::WriteZero, "deque is full"));
+        }
+        Ok(())
+    }
+
+    /// Does nothing, as there is nothing to flush.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads pop bytes from the front of the deque, copying out of at most two
+/// contiguous runs. Reading from an empty deque returns `Ok(0)`.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::ArrayDeque;
+/// use std::io::Read;
+///
+/// let mut dq = ArrayDeque::from(*b"hello");
+/// let mut buf = [0; 3];
+/// assert_eq!(dq.read(&mut buf).unwrap(), 3);
+/// assert_eq!(&buf, b"hel");
+/// assert_eq!(dq, *b"lo");
+/// ```
+#[cfg(feature = "std")]
+impl<A: Allocator> io::Read for ArrayDeque<u8, A> {
+    /// Moves up to `buf.len()` bytes from the front of the deque into `buf`.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.pop_bytes(buf))
+    }
+
+    /// Fills `buf` from the front of the deque, or fails with
+    /// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) without consuming
+    /// anything if fewer than `buf.len()` bytes are buffered.
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        if buf.len() > self.len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.read(buf).map(drop)
+    }
+}
+
+/// The deque is its own buffer: [`fill_buf`](io::BufRead::fill_buf) borrows
+/// the front bytes in place and [`consume`](io::BufRead::consume) advances the
+/// front, with no copying.
+///
+/// When the contents wrap around the end of the ring, `fill_buf` returns only
+/// the first contiguous run; once that is consumed, the next call returns the
+/// rest. Helpers such as `read_line` loop over `fill_buf`/`consume` and so
+/// still see every byte in order.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::ArrayDeque;
+/// use std::io::BufRead;
+///
+/// let mut dq = ArrayDeque::new(16);
+/// dq.extend_from_slice(b"first\nsecond\n");
+/// let lines: Vec<String> = dq.lines().map(Result::unwrap).collect();
+/// assert_eq!(lines, ["first", "second"]);
+/// ```
+#[cfg(feature = "std")]
+impl<A: Allocator> io::BufRead for ArrayDeque<u8, A> {
+    /// Returns the first contiguous run of buffered bytes, which is empty only
+    /// when the deque is empty.
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+
+    /// Removes `amt` bytes from the front of the deque (or all of them if
+    /// fewer are buffered).
+    fn consume(&mut self, amt: usize) {
+        self.truncate_front(self.len - amt.min(self.len));
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<A: Allocator> embedded_io::ErrorType for ArrayDeque<u8, A> {
+    type Error = crate::FullError;
+}
+
+/// Reads pop bytes from the front of the deque. Reading from an empty deque
+/// returns `Ok(0)`.
+#[cfg(feature = "embedded-io")]
+impl<A: Allocator> embedded_io::Read for ArrayDeque<u8, A> {
+    /// Moves up to `buf.len()` bytes from the front of the deque into `buf`.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.pop_bytes(buf))
+    }
+}
+
+/// Writes append bytes to the back of the deque without ever overwriting
+/// buffered data, accepting only as many bytes as there is free space for.
+///
+/// Writing a non-empty buffer to a full deque fails with
+/// [`FullError`](crate::FullError), whose kind is
+/// [`WriteZero`](embedded_io::ErrorKind::WriteZero), so `write_all` returns
+/// an error if the deque fills up before everything is written. Reads never
+/// fail.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "embedded-io")] {
+/// use array_deque::ArrayDeque;
+/// use embedded_io::{Read, Write, WriteReady};
+///
+/// let mut fifo = ArrayDeque::new(4);
+/// assert_eq!(fifo.write(b"abcdef"), Ok(4));
+/// assert_eq!(fifo.write_ready(), Ok(false));
+/// assert_eq!(fifo.write(b"g"), Err(array_deque::FullError));
+/// let mut buf = [0; 8];
+/// assert_eq!(fifo.read(&mut buf), Ok(4));
+/// assert_eq!(&buf[..4], b"abcd");
+/// # }
+/// ```
+#[cfg(feature = "embedded-io")]
+impl<A: Allocator> embedded_io::Write for ArrayDeque<u8, A> {
+    /// Appends as much of `buf` as fits and returns how many bytes that was,
+    /// failing if `buf` is non-empty and no byte fits.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        match self.push_bytes(buf) {
+            0 if !buf.is_empty() => Err(crate::FullError),
+            n => Ok(n),
+        }
+    }
+
+    /// Does nothing, as there is nothing to flush.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<A: Allocator> embedded_io::ReadReady for ArrayDeque<u8, A> {
+    /// Returns `true` if there is at least one byte to read.
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_empty())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<A: Allocator> embedded_io::WriteReady for ArrayDeque<u8, A> {
+    /// Returns `true` if there is room for at least one more byte.
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_full())
+    }
+}
+
+/// Reading through [`Buf`](bytes::Buf) consumes bytes from the front of the
+/// deque.
+///
+/// When the contents wrap around the end of the ring,
+/// [`chunk`](bytes::Buf::chunk) returns only the first contiguous run; the
+/// rest becomes visible once that run has been advanced past.
+///
+/// The inherent [`ArrayDeque::copy_to_slice`] takes precedence over
+/// [`Buf::copy_to_slice`](bytes::Buf::copy_to_slice) in method calls and does
+/// not consume anything; call the latter through the trait to consume.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "bytes")] {
+/// use array_deque::ArrayDeque;
+/// use bytes::Buf;
+///
+/// let mut dq = ArrayDeque::from(*b"\x00\x2Aok");
+/// assert_eq!(dq.get_u16(), 42);
+/// assert_eq!(dq.chunk(), b"ok");
+/// # }
+/// ```
+#[cfg(feature = "bytes")]
+impl<A: Allocator> bytes::Buf for ArrayDeque<u8, A> {
+    /// Returns the number of buffered bytes.
+    fn remaining(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the first contiguous run of buffered bytes.
+    fn chunk(&self) -> &[u8] {
+        self.as_slices().0
+    }
+
+    /// Removes `cnt` bytes from the front of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cnt` is greater than the number of buffered bytes.
+    fn advance(&mut self, cnt: usize) {
+        assert!(cnt <= self.len, "cannot advance past the end of the deque");
+        self.truncate_front(self.len - cnt);
+    }
+}
+
+/// Writing through [`BufMut`](bytes::BufMut) appends bytes to the back of the
+/// deque without ever overwriting buffered data; the writable space is
+/// exactly the free capacity.
+///
+/// When the free space wraps around the end of the ring,
+/// [`chunk_mut`](bytes::BufMut::chunk_mut) exposes only the first contiguous
+/// free region; the rest becomes available once that region is filled.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "bytes")] {
+/// use array_deque::ArrayDeque;
+/// use bytes::BufMut;
+///
+/// let mut dq = ArrayDeque::new(6);
+/// dq.put_u16(42);
+/// dq.put_slice(b"ok");
+/// assert_eq!(dq, *b"\x00\x2Aok");
+/// assert_eq!(dq.remaining_mut(), 2);
+/// # }
+/// ```
+#[cfg(feature = "bytes")]
+unsafe impl<A: Allocator> bytes::BufMut for ArrayDeque<u8, A> {
+    /// Returns the number of free slots.
+    fn remaining_mut(&self) -> usize {
+        self.cap - self.len
+    }
+
+    /// Marks the next `cnt` free slots as holding bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cnt` is greater than the number of free slots.
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        unsafe { self.advance_back(cnt) }
+    }
+
+    /// Returns the first contiguous run of free slots after the back byte.
+    fn chunk_mut(&mut self) -> &mut bytes::buf::UninitSlice {
+        let free = self.cap - self.len;
+        let start = self.physical_index(self.len);
+        let run = (self.cap - start).min(free);
+        unsafe { bytes::buf::UninitSlice::from_raw_parts_mut(self.ptr.as_ptr().add(start), run) }
+    }
+}
+
+impl<T, A: Allocator> IntoIterator for ArrayDeque<T, A> {
+    type Item = T;
+    type IntoIter = ArrayDequeIntoIter<T, A>;
+    /// Consumes the deque and returns an iterator over its elements.
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayDequeIntoIter { deque: self }
+    }
+}
+
+/// An owning iterator that moves elements out of an `ArrayDeque`.
+///
+/// Returned by `into_iter()`.
+pub struct ArrayDequeIntoIter<T, A: Allocator = Global> {
+    deque: ArrayDeque<T, A>,
+}
+
+impl<T, A: Allocator> Iterator for ArrayDequeIntoIter<T, A> {
+    type Item = T;
+
+    /// Advances and returns the next element.
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    /// Returns the exact number of remaining elements.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+
+    /// Drops the first `n` remaining elements and returns the next one.
+    fn nth(&mut self, n: usize) -> Option<T> {
+        self.deque.truncate_front(self.deque.len.saturating_sub(n));
+        self.deque.pop_front()
+    }
+
+    /// Returns the number of remaining elements, dropping them.
+    fn count(self) -> usize {
+        self.deque.len
+    }
+
+    /// Returns the back element, dropping the rest.
+    fn last(mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for ArrayDequeIntoIter<T, A> {
+    /// Removes and returns the next element from the back.
+    fn next_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for ArrayDequeIntoIter<T, A> {}
+
+impl<T, A: Allocator> FusedIterator for ArrayDequeIntoIter<T, A> {}
+
+impl<T: Clone, A: Allocator + Clone> Clone for ArrayDequeIntoIter<T, A> {
+    /// Clones the elements that have not been yielded yet.
+    fn clone(&self) -> Self {
+        ArrayDequeIntoIter {
+            deque: self.deque.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for ArrayDequeIntoIter<T, A> {
+    /// Formats the elements that have not been yielded yet.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayDequeIntoIter")
+            .field(&self.deque)
+            .finish()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a ArrayDeque<T, A> {
+    type Item = &'a T;
+    type IntoIter = ArrayDequeIter<'a, T, A>;
+    /// Borrows the deque and returns an iterator over `&T`.
+    fn into_iter(self) -> Self::IntoIter {
+        let (front, back) = self.as_slices();
+        ArrayDequeIter::new(front, back)
+    }
+}
+
+/// A borrowed iterator over `&T` from an `ArrayDeque`.
+///
+/// Returned by `iter()` and `&deque.into_iter()`.
+pub struct ArrayDequeIter<'a, T, A: Allocator = Global> {
+    /// Remaining elements up to the wrap point
+    front: slice::Iter<'a, T>,
+    /// Remaining elements after the wrap point
+    back: slice::Iter<'a, T>,
+    _marker: PhantomData<&'a ArrayDeque<T, A>>,
+}
+
+impl<'a, T, A: Allocator> ArrayDequeIter<'a, T, A> {
+    /// Creates an iterator over the contents `front` followed by `back`.
+    fn new(front: &'a [T], back: &'a [T]) -> Self {
+        ArrayDequeIter {
+            front: front.iter(),
+            back: back.iter(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> Iterator for ArrayDequeIter<'a, T, A> {
+    type Item = &'a T;
+
+    /// Advances and returns the next reference, front to back.
+    fn next(&mut self) -> Option<&'a T> {
+        match self.front.next() {
+            Some(value) => Some(value),
+            None => {
+                // Step onto the second run so later calls take the first arm.
+                mem::swap(&mut self.front, &mut self.back);
+                self.front.next()
+            }
+        }
+    }
+
+    /// Returns the exact number of remaining elements.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len();
+        (remaining, Some(remaining))
+    }
+
+    /// Skips `n` elements in O(1) and returns the next reference.
+    fn nth(&mut self, n: usize) -> Option<&'a T> {
+        let front_len = self.front.len();
+        if n < front_len {
+            return self.front.nth(n);
+        }
+        self.front = mem::replace(&mut self.back, [].iter());
+        self.front.nth(n - front_len)
+    }
+
+    /// Returns the number of remaining elements in O(1).
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    /// Returns a reference to the back element in O(1).
+    fn last(mut self) -> Option<&'a T> {
+        self.next_back()
+    }
+
+    /// Folds over each run in turn, so the inner loops are over plain slices.
+    fn fold<B, F: FnMut(B, &'a T) -> B>(self, init: B, mut f: F) -> B {
+        let acc = self.front.fold(init, &mut f);
+        self.back.fold(acc, f)
+    }
+}
+
+impl<'a, T, A: Allocator> DoubleEndedIterator for ArrayDequeIter<'a, T, A> {
+    /// Returns the next reference from the back.
+    fn next_back(&mut self) -> Option<&'a T> {
+        match self.back.next_back() {
+            Some(value) => Some(value),
+            None => self.front.next_back(),
+        }
+    }
+
+    /// Folds over each run in turn, back to front.
+    fn rfold<B, F: FnMut(B, &'a T) -> B>(self, init: B, mut f: F) -> B {
+        let acc = self.back.rfold(init, &mut f);
+        self.front.rfold(acc, f)
+    }
+}
+
+impl<'a, T, A: Allocator> ExactSizeIterator for ArrayDequeIter<'a, T, A> {
+    /// Returns the number of remaining elements in both runs.
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<'a, T, A: Allocator> FusedIterator for ArrayDequeIter<'a, T, A> {}
+
+impl<'a, T, A: Allocator> Clone for ArrayDequeIter<'a, T, A> {
+    /// Creates a copy of the iterator at the same position.
+    fn clone(&self) -> Self {
+        ArrayDequeIter {
+            front: self.front.clone(),
+            back: self.back.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug, A: Allocator> fmt::Debug for ArrayDequeIter<'a, T, A> {
+    /// Formats the elements that have not been yielded yet.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayDequeIter")
+            .field(&self.front.as_slice())
+            .field(&self.back.as_slice())
+            .finish()
+    }
+}
+
+/// An iterator that removes the elements matching a predicate from an
+/// `ArrayDeque`.
+///
+/// Returned by [`ArrayDeque::extract_if`].
+pub struct ExtractIf<'a, T, F, A: Allocator = Global> {
+    deque: &'a mut ArrayDeque<T, A>,
+    pred: F,
+    /// Logical index of the next element to test
+    pos: usize,
+    /// Number of elements removed so far
+    removed: usize,
+    /// Length of the deque before extraction started
+    old_len: usize,
+}
+
+impl<T, F: FnMut(&mut T) -> bool, A: Allocator> Iterator for ExtractIf<'_, T, F, A> {
+    type Item = T;
+
+    /// Tests elements until one matches, then removes and returns it. Kept
+    /// elements are shifted forward over the gap left by earlier removals.
+    fn next(&mut self) -> Option<T> {
+        while self.pos < self.old_len {
+            let i = self.pos;
+            let slot = unsafe { &mut *self.deque.ptr.as_ptr().add(self.deque.physical_index(i)) };
+            let matched = (self.pred)(slot);
+            // Only advance once `pred` has returned, so a panic leaves `i`
+            // among the elements that `drop` moves back into place.
+            self.pos += 1;
+            if matched {
+                self.removed += 1;
+                return Some(unsafe { ptr::read(slot) });
+            }
+            if self.removed > 0 {
+                unsafe { self.deque.move_slot(i, i - self.removed) };
+            }
+        }
+        None
+    }
+
+    /// Returns bounds on the number of remaining matches.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.pos))
+    }
+}
+
+impl<T, F: FnMut(&mut T) -> bool, A: Allocator> FusedIterator for ExtractIf<'_, T, F, A> {}
+
+impl<T, F, A: Allocator> Drop for ExtractIf<'_, T, F, A> {
+    /// Shifts the untested elements over the removed ones and restores the
+    /// deque's length.
+    fn drop(&mut self) {
+        if self.removed > 0 {
+            for i in self.pos..self.old_len {
+                unsafe { self.deque.move_slot(i, i - self.removed) };
+            }
+        }
+        self.deque.len = self.old_len - self.removed;
+    }
+}
+
+/// A cursor over an `ArrayDeque` that can move back and forth and edit the
+/// deque where it stands.
+///
+/// The cursor always points either at an element or at a "ghost" position
+/// between the back and the front, where [`current`](Self::current) returns
+/// `None`. Moving past either end lands on the ghost, and moving on from the
+/// ghost wraps to the other end, like the cursors of `LinkedList`.
+///
+/// Moving and peeking are O(1). Inserting and removing shift whichever side
+/// of the deque holds fewer elements, like [`ArrayDeque::insert`] and
+/// [`ArrayDeque::remove`].
+///
+/// # Full deques
+///
+/// When the deque is full, [`insert_after`](Self::insert_after) and
+/// [`insert_before`](Self::insert_before) follow the deque's
+/// [`OverflowPolicy`], just like `push_back`: by default the front element is
+/// evicted to make room, and the cursor keeps pointing at the same element.
+/// If the cursor was on the evicted front element, it moves on to the
+/// element that followed it, as after [`remove_current`](Self::remove_current).
+/// Under [`OverflowPolicy::Reject`] the new value is dropped, and under
+/// [`OverflowPolicy::Panic`] the insertion panics.
+///
+/// Returned by [`ArrayDeque::cursor_front_mut`] and
+/// [`ArrayDeque::cursor_back_mut`].
+pub struct CursorMut<'a, T, A: Allocator = Global> {
+    /// Logical index of the current element, or `len` at the ghost position
+    index: usize,
+    deque: &'a mut ArrayDeque<T, A>,
+}
+
+impl<T, A: Allocator> CursorMut<'_, T, A> {
+    /// Returns the logical index of the current element, or `None` at the
+    /// ghost position.
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.deque.len).then_some(self.index)
+    }
+
+    /// Moves to the next element, from the back to the ghost position, or
+    /// from the ghost position to the front.
+    pub fn move_next(&mut self) {
+        self.index = if self.index == self.deque.len {
+            0
+        } else {
+            self.index + 1
+        };
+    }
+
+    /// Moves to the previous element, from the front to the ghost position,
+    /// or from the ghost position to the back.
+    pub fn move_prev(&mut self) {
+        self.index = if self.index == 0 {
+            self.deque.len
+        } else {
+            self.index - 1
+        };
+    }
+
+    /// Returns the current element, or `None` at the ghost position.
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.deque.get_mut(self.index)
+    }
+
+    /// Returns the element after the current one, or `None` if the cursor
+    /// is on the back element. At the ghost position, this is the front.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = if self.index == self.deque.len {
+            0
+        } else {
+            self.index + 1
+        };
+        self.deque.get_mut(next)
+    }
+
+    /// Returns the element before the current one, or `None` if the cursor
+    /// is on the front element. At the ghost position, this is the back.
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = self.index.checked_sub(1)?;
+        self.deque.get_mut(prev)
+    }
+
+    /// Removes and returns the current element, moving the cursor to the
+    /// element that followed it, or to the ghost position if it was the back.
+    ///
+    /// Returns `None`, and removes nothing, at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.deque.remove(self.index)
+    }
+
+    /// Inserts `value` after the current element, or at the front if the
+    /// cursor is at the ghost position. The cursor does not move.
+    ///
+    /// See the [type docs](Self#full-deques) for what happens when the
+    /// deque is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is full under [`OverflowPolicy::Panic`].
+    pub fn insert_after(&mut self, value: T) {
+        let pos = if self.index == self.deque.len {
+            0
+        } else {
+            self.index + 1
+        };
+        self.insert_at(pos, value);
+    }
+
+    /// Inserts `value` before the current element, or at the back if the
+    /// cursor is at the ghost position. The cursor does not move.
+    ///
+    /// See the [type docs](Self#full-deques) for what happens when the
+    /// deque is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is full under [`OverflowPolicy::Panic`].
+    pub fn insert_before(&mut self, value: T) {
+        self.insert_at(self.index, value);
+    }
+
+    /// Inserts `value` at logical index `pos`, evicting the front element
+    /// first if the deque is full, and keeps the cursor on its element.
+    fn insert_at(&mut self, mut pos: usize, value: T) {
+        let deque = &mut *self.deque;
+        if deque.is_full() {
+            if deque.cap == 0 || !deque.evict_on_overflow() {
+                return;
+            }
+            drop(deque.pop_front());
+            #[cfg(feature = "stats")]
+            deque.stats.record(0, 1, deque.len);
+            pos = pos.saturating_sub(1);
+            self.index = self.index.saturating_sub(1);
+        }
+        deque.insert(pos, value);
+        if pos <= self.index {
+            self.index += 1;
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for CursorMut<'_, T, A> {
+    /// Formats the cursor's index and the deque's elements.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorMut")
+            .field("index", &self.index())
+            .field("deque", &self.deque)
+            .finish()
+    }
+}
+
+/// Lengthening the element lifetime must not compile: `ArrayDeque<T>` is
+/// covariant in `T`, not contravariant.
+///
+/// ```compile_fail
+/// use array_deque::ArrayDeque;
+///
+/// fn lengthen<'a>(dq: ArrayDeque<&'a str>) -> ArrayDeque<&'static str> {
+///     dq
+/// }
+/// ```
+#[cfg(doctest)]
+struct VarianceCompileFail;
+
+/// Even with the `unstable` feature, elements whose own destructor reads the
+/// data they borrow must not outlive that data.
+///
+/// ```compile_fail
+/// use array_deque::ArrayDeque;
+///
+/// struct Observer<'a>(&'a String);
+///
+/// impl Drop for Observer<'_> {
+///     fn drop(&mut self) {
+///         println!("{}", self.0);
+///     }
+/// }
+///
+/// let mut deque = ArrayDeque::new(1);
+/// let s = String::from("observed");
+/// deque.push_back(Observer(&s));
+/// ```
+#[cfg(doctest)]
+struct DropckCompileFail;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestRng;
+    use allocator_api2::alloc::AllocError;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::String, sync::Arc, vec};
+    #[cfg(feature = "std")]
+    use std::sync::Arc;
+
+    #[derive(Clone)]
+    struct DropCounter {
+        drops: Arc<AtomicUsize>,
+    }
+
+    impl DropCounter {
+        fn new(drops: Arc<AtomicUsize>) -> Self {
+            Self { drops }
+        }
+    }
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.drops.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// A value whose third clone panics, counting drops through its
+    /// [`DropCounter`].
+    #[cfg(feature = "std")]
+    struct Bomb {
+        clones: Arc<AtomicUsize>,
+        counter: DropCounter,
+    }
+
+    #[cfg(feature = "std")]
+    impl Bomb {
+        fn new(clones: &Arc<AtomicUsize>, drops: &Arc<AtomicUsize>) -> Self {
+            Bomb {
+                clones: clones.clone(),
+                counter: DropCounter::new(drops.clone()),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            if self.clones.fetch_add(1, Ordering::SeqCst) == 2 {
+                panic!("clone failed");
+            }
+            Bomb {
+                clones: self.clones.clone(),
+                counter: self.counter.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn send_and_sync() {
+        fn assert_send<T: Send>() {}
+        fn assert_sync<T: Sync>() {}
+        assert_send::<ArrayDeque<u64>>();
+        assert_sync::<ArrayDeque<u64>>();
+        assert_send::<ArrayDequeIntoIter<u64>>();
+        assert_sync::<ArrayDequeIter<'_, u64>>();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn move_to_thread() {
+        let mut deque = ArrayDeque::new(3);
+        deque.extend([1u64, 2, 3]);
+        let sum = std::thread::spawn(move || deque.iter().sum::<u64>())
+            .join()
+            .unwrap();
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn covariant_in_element_type() {
+        fn shorten<'a>(deque: ArrayDeque<&'static i32>) -> ArrayDeque<&'a i32> {
+            deque
+        }
+        fn shorten_iter<'a>(iter: ArrayDequeIntoIter<&'static i32>) -> ArrayDequeIntoIter<&'a i32> {
+            iter
+        }
+
+        static ONE: i32 = 1;
+        let local = 2;
+        let mut deque = ArrayDeque::new(2);
+        deque.push_back(&ONE);
+        let mut deque = shorten(deque);
+        deque.push_back(&local);
+        assert_eq!(*deque[0], 1);
+        assert_eq!(*deque[1], 2);
+        assert_eq!(
+            shorten_iter(ArrayDeque::from([&ONE]).into_iter()).count(),
+            1
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn elements_may_dangle_on_drop() {
+        let mut deque = ArrayDeque::new(4);
+        let s = String::from("a");
+        deque.push_back(&s);
+        assert_eq!(deque[0], "a");
+    }
+
+    #[test]
+    fn option_uses_pointer_niche() {
+        assert_eq!(
+            core::mem::size_of::<Option<ArrayDeque<u8>>>(),
+            core::mem::size_of::<ArrayDeque<u8>>()
+        );
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        let mut deque = ArrayDeque::new(8);
+        for _ in 0..10 {
+            deque.push_back(());
+        }
+        deque.push_front(());
+        assert_eq!(deque.len(), 8);
+        assert!(deque.is_full());
+        assert_eq!(deque.iter().count(), 8);
+        assert_eq!(deque.remove(3), Some(()));
+        deque.insert(1, ());
+        deque.sort();
+        assert_eq!(deque.pop_front(), Some(()));
+        assert_eq!(deque.pop_back(), Some(()));
+        assert_eq!(deque.len(), 6);
+        assert_eq!(deque.into_iter().count(), 6);
+    }
+
+    #[test]
+    fn zero_sized_elements_with_destructor() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Zst;
+        impl Drop for Zst {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        {
+            let mut deque = ArrayDeque::new(3);
+            for _ in 0..5 {
+                deque.push_back(Zst);
+            }
+            assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+            drop(deque.pop_front());
+            assert_eq!(DROPS.load(Ordering::SeqCst), 3);
+            deque.truncate(1);
+            assert_eq!(DROPS.load(Ordering::SeqCst), 4);
+        }
+
+        assert_eq!(DROPS.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_front(), None);
+    }
+
+    #[test]
+    fn push_front_back() {
+        let mut deque = ArrayDeque::new(3);
+        deque.push_front(1);
+        deque.push_front(2);
+        deque.push_back(3);
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn clear() {
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn capacity() {
+        let deque = ArrayDeque::<i32>::new(5);
+        assert_eq!(deque.capacity(), 5);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn clone() {
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back(1);
+        deque.push_back(2);
+        let cloned_deque = deque.clone();
+        assert_eq!(cloned_deque.len(), 2);
+        assert_eq!(cloned_deque[0], 1);
+        assert_eq!(cloned_deque[1], 2);
+    }
+
+    #[test]
+    fn from_iter() {
+        let vec = vec![1, 2, 3];
+        let deque: ArrayDeque<_> = vec.into_iter().collect();
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 1);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    fn from_slice() {
+        let slice = [1, 2, 3];
+        let deque: ArrayDeque<_> = ArrayDeque::from(&slice[..]);
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 1);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    fn from_array() {
+        let array = [1, 2, 3];
+        let deque: ArrayDeque<_> = ArrayDeque::from(array);
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 1);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    fn index() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert_eq!(deque[0], 1);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds_panics() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(1);
+        let _ = deque[1];
+    }
+
+    #[test]
+    fn index_mut() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        deque[0] = 10;
+        assert_eq!(deque[0], 10);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_mut_out_of_bounds_panics() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(1);
+        deque[1] = 99;
+    }
+
+    #[test]
+    fn get_and_get_mut() {
+        let mut deque = ArrayDeque::new(3);
+        assert_eq!(deque.get(0), None);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_back(4);
+        assert_eq!(deque.get(0), Some(&2));
+        assert_eq!(deque.get(2), Some(&4));
+        assert_eq!(deque.get(3), None);
+
+        *deque.get_mut(2).unwrap() = 40;
+        assert_eq!(deque[2], 40);
+        assert_eq!(deque.get_mut(3), None);
+    }
+
+    #[test]
+    fn get_unchecked_wrapped() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..7 {
+            deque.push_back(i);
+        }
+        for i in 0..deque.len() {
+            assert_eq!(unsafe { *deque.get_unchecked(i) }, deque[i]);
+        }
+        for i in 0..deque.len() {
+            unsafe { *deque.get_unchecked_mut(i) *= 10 };
+        }
+        assert_eq!(contents(&deque), vec![30, 40, 50, 60]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Index out of bounds")]
+    fn get_unchecked_out_of_bounds_debug_panics() {
+        let mut deque = ArrayDeque::new(4);
+        deque.push_back(1);
+        let _ = unsafe { deque.get_unchecked(1) };
+    }
+
+    #[test]
+    fn get_many_mut_across_wrap_point() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..7 {
+            deque.push_back(i);
+        }
+        // The front element sits in the last slot, the rest wrap to slot 0.
+        assert_eq!(deque.as_slices(), (&[3][..], &[4, 5, 6][..]));
+        let [back, front, mid] = deque.get_many_mut([3, 0, 1]).unwrap();
+        core::mem::swap(front, back);
+        *mid += 100;
+        assert_eq!(contents(&deque), vec![6, 104, 5, 3]);
+        let [] = deque.get_many_mut([]).unwrap();
+    }
+
+    #[test]
+    fn get_many_mut_rejects_bad_indices() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        assert_eq!(
+            deque.get_many_mut([2, 0, 2]),
+            Err(GetManyMutError::OverlappingIndices)
+        );
+        assert_eq!(
+            deque.get_many_mut([1, 4]),
+            Err(GetManyMutError::IndexOutOfBounds)
+        );
+        assert_eq!(
+            deque.get_many_mut([usize::MAX]),
+            Err(GetManyMutError::IndexOutOfBounds)
+        );
+        assert_eq!(contents(&deque), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn hash_ignores_physical_layout() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut wrapped = ArrayDeque::new(3);
+        for i in 0..5 {
+            wrapped.push_back(i);
+        }
+        let unwrapped: ArrayDeque<_> = [2, 3, 4].into_iter().collect();
+        assert_eq!(wrapped, unwrapped);
+        assert_eq!(hash_of(&wrapped), hash_of(&unwrapped));
+
+        let mut shorter = ArrayDeque::new(3);
+        shorter.push_back(2);
+        shorter.push_back(3);
+        assert_ne!(hash_of(&shorter), hash_of(&unwrapped));
+    }
+
+    #[test]
+    fn ord_ignores_physical_layout() {
+        use core::cmp::Ordering;
+
+        let mut wrapped = ArrayDeque::new(3);
+        for i in 0..5 {
+            wrapped.push_back(i);
+        }
+        let unwrapped: ArrayDeque<i32> = [2, 3, 4].into_iter().collect();
+        assert_eq!(wrapped.cmp(&unwrapped), Ordering::Equal);
+        assert_eq!(wrapped.partial_cmp(&unwrapped), Some(Ordering::Equal));
+
+        let prefix: ArrayDeque<i32> = [2, 3].into_iter().collect();
+        assert!(prefix < wrapped);
+        let bigger: ArrayDeque<i32> = [2, 4].into_iter().collect();
+        assert!(bigger > wrapped);
+
+        let mut sorted = vec![bigger.clone(), wrapped.clone(), prefix.clone()];
+        sorted.sort();
+        assert_eq!(sorted, vec![prefix, wrapped, bigger]);
+    }
+
+    #[test]
+    fn eq_slices_arrays_and_vecs_wrapped() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        assert_ne!(deque.as_slices().1, &[] as &[i32]);
+
+        let expected = [2, 3, 4, 5];
+        assert_eq!(deque, expected);
+        assert_eq!(deque, &expected);
+        assert_eq!(deque, expected[..]);
+        assert_eq!(deque, &expected[..]);
+        assert_eq!(deque, &mut [2, 3, 4, 5][..]);
+        assert_eq!(deque, vec![2, 3, 4, 5]);
+        assert_eq!(expected, deque);
+        assert_eq!(expected[..], deque);
+        assert_eq!(vec![2, 3, 4, 5], deque);
+
+        assert_ne!(deque, [2, 3, 4]);
+        assert_ne!(deque, [2, 3, 4, 6]);
+        assert_ne!(deque, vec![3, 4, 5, 2]);
+        assert_ne!(vec![2, 3, 4, 5, 6], deque);
+        assert_eq!(ArrayDeque::<i32>::new(0), [0; 0]);
+    }
+
+    #[test]
+    fn filled() {
+        let mut deque = ArrayDeque::filled(3, 7);
+        assert!(deque.is_full());
+        assert_eq!(deque, [7, 7, 7]);
+        deque.push_back(1);
+        assert_eq!(deque, [7, 7, 1]);
+
+        assert_eq!(ArrayDeque::filled(1, 'x'), ['x']);
+        assert!(ArrayDeque::filled(0, 'x').is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn filled_panicking_clone_drops_written_slots() {
+        use std::panic::catch_unwind;
+
+        let clones = Arc::new(AtomicUsize::new(0));
+        let drops = Arc::new(AtomicUsize::new(0));
+        let bomb = Bomb::new(&clones, &drops);
+        let result = catch_unwind(move || ArrayDeque::filled(5, bomb));
+        assert!(result.is_err());
+        // Two clones were written before the third panicked; both are dropped
+        // along with the original value.
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn from_fn() {
+        let deque = ArrayDeque::from_fn(5, |i| i * 10);
+        assert!(deque.is_full());
+        assert_eq!(deque, [0, 10, 20, 30, 40]);
+        assert!(ArrayDeque::from_fn(0, |i| i).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_fn_panic_drops_constructed_elements() {
+        use std::panic::catch_unwind;
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        let counter = drops.clone();
+        let result = catch_unwind(move || {
+            ArrayDeque::from_fn(5, |i| {
+                if i == 3 {
+                    panic!("init failed");
+                }
+                DropCounter::new(counter.clone())
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn extend_from_slice_straddles_wrap_point() {
+        let mut deque = ArrayDeque::new(5);
+        deque.extend([0, 0, 0, 1, 2]);
+        for _ in 0..3 {
+            deque.pop_front();
+        }
+        deque.extend_from_slice(&[3, 4, 5]);
+        assert!(!deque.as_slices().1.is_empty());
+        assert_eq!(deque, [1, 2, 3, 4, 5]);
+
+        deque.extend_from_slice(&[6, 7]);
+        assert_eq!(deque, [3, 4, 5, 6, 7]);
+        deque.extend_from_slice(&[]);
+        assert_eq!(deque, [3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn extend_from_copy_slice_matches_extend_from_slice() {
+        for policy in [OverflowPolicy::OverwriteOldest, OverflowPolicy::Reject] {
+            for offset in 0..5 {
+                for (len, add) in [(0, 3), (2, 2), (3, 4), (5, 7), (4, 0)] {
+                    let mut cloned = ArrayDeque::with_policy(5, policy);
+                    for _ in 0..offset {
+                        cloned.push_back(0);
+                        cloned.pop_front();
+                    }
+                    cloned.extend(0..len);
+                    let mut copied = cloned.clone();
+                    let slice: Vec<i32> = (10..10 + add).collect();
+                    cloned.extend_from_slice(&slice);
+                    copied.extend_from_copy_slice(&slice);
+                    assert_eq!(copied, cloned);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn extend_from_slice_longer_than_capacity() {
+        let mut deque = ArrayDeque::new(3);
+        for item in [1, 2, 3, 4] {
+            deque.push_back(item);
+        }
+        deque.extend_from_slice(&[5, 6, 7, 8, 9]);
+        assert_eq!(deque, [7, 8, 9]);
+        deque.extend_from_slice(&[10, 11, 12]);
+        assert_eq!(deque, [10, 11, 12]);
+
+        let mut empty = ArrayDeque::new(0);
+        empty.extend_from_slice(&[1, 2]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn overflow_policy_overwrite_oldest_is_default() {
+        let mut deque = ArrayDeque::new(3);
+        assert_eq!(deque.policy(), OverflowPolicy::OverwriteOldest);
+        assert_eq!(deque.extend_back(0..5), 5);
+        assert_eq!(deque.extend_back((0..5).filter(|_| true)), 5);
+        deque.push_back(5);
+        deque.push_front(1);
+        assert_eq!(deque, [1, 3, 4]);
+        assert_eq!(deque.extend_front([7, 8, 9, 10]), 4);
+        assert_eq!(deque, [7, 8, 9]);
+        assert_eq!(deque.extend_from_slice(&[1, 2, 3, 4]), 4);
+    }
+
+    #[test]
+    fn overflow_policy_reject() {
+        let mut deque = ArrayDeque::with_policy(4, OverflowPolicy::Reject);
+        let mut items = 1..10;
+        deque.extend(items.by_ref());
+        // Nothing is taken from the iterator once the deque is full.
+        assert_eq!(items.next(), Some(5));
+        assert_eq!(deque, [1, 2, 3, 4]);
+        assert_eq!(deque.extend_back(items.by_ref()), 0);
+        assert_eq!(items.next(), Some(6));
+
+        deque.push_back(10);
+        deque.push_front(0);
+        deque.insert(2, 7);
+        assert_eq!(deque.try_push_back(11), Err(11));
+        assert_eq!(deque.try_insert(1, 12), Err(12));
+        assert_eq!(deque, [1, 2, 3, 4]);
+
+        deque.pop_front();
+        deque.pop_back();
+        let mut items = [8, 9, 10].into_iter();
+        assert_eq!(deque.extend_front(items.by_ref()), 2);
+        assert_eq!(items.next(), Some(10));
+        assert_eq!(deque, [8, 9, 2, 3]);
+        deque.truncate(3);
+        assert_eq!(deque.extend_from_slice(&[4, 5]), 1);
+        assert_eq!(deque, [8, 9, 2, 4]);
+        assert_eq!(deque.extend_from_copy_slice(&[6]), 0);
+
+        deque.truncate(2);
+        deque.push_back(1);
+        deque.push_front(0);
+        assert_eq!(deque, [0, 8, 9, 1]);
+        deque.pop_back();
+        assert_eq!(deque.extend_back([2, 3]), 1);
+        assert_eq!(deque, [0, 8, 9, 2]);
+
+        let clone = deque.clone();
+        assert_eq!(clone.policy(), OverflowPolicy::Reject);
+        assert_eq!(deque.take().policy(), OverflowPolicy::Reject);
+        assert_eq!(deque.policy(), OverflowPolicy::Reject);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn extend_front_keeps_order_when_panic_policy_fires() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let mut deque = ArrayDeque::with_policy(4, OverflowPolicy::Panic);
+        deque.push_back(9);
+        let result = catch_unwind(AssertUnwindSafe(|| deque.extend_front([1, 2, 3, 4])));
+        assert!(result.is_err());
+        assert_eq!(deque, [1, 2, 3, 9]);
+
+        // A panicking iterator leaves the pushed prefix in order too.
+        let mut deque = ArrayDeque::new(4);
+        deque.push_back(9);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            deque.extend_front((1..).map(|i| if i == 3 { panic!("iterator failed") } else { i }))
+        }));
+        assert!(result.is_err());
+        assert_eq!(deque, [1, 2, 9]);
+    }
+
+    #[test]
+    fn evict_variants_ignore_policy() {
+        for policy in [OverflowPolicy::Reject, OverflowPolicy::Panic] {
+            let mut deque = ArrayDeque::with_policy(2, policy);
+            deque.extend([1, 2]);
+            assert_eq!(deque.push_back_evict(3), Some(1));
+            assert_eq!(deque.push_front_evict(0), Some(3));
+            assert_eq!(deque, [0, 2]);
+        }
+    }
+
+    #[test]
+    fn overflow_policy_reject_zero_capacity() {
+        let mut deque = ArrayDeque::with_policy(0, OverflowPolicy::Reject);
+        assert_eq!(deque.extend_back([1, 2]), 0);
+        assert_eq!(deque.extend_front([1, 2]), 0);
+        deque.push_front(3);
+        assert_eq!(deque.push_back_evict(4), Some(4));
+        assert!(deque.is_empty());
+
+        deque.set_policy(OverflowPolicy::OverwriteOldest);
+        deque.push_back(5);
+        deque.insert(0, 6);
+        assert_eq!(deque.extend_back([1, 2]), 0);
+        assert_eq!(deque.extend_back((1..3).filter(|_| true)), 0);
+        assert_eq!(deque.extend_front([1, 2]), 0);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn overflow_policy_reject_io_write() {
+        use std::io::{IoSlice, Write};
+
+        let mut deque = ArrayDeque::with_policy(5, OverflowPolicy::Reject);
+        let bufs = [
+            IoSlice::new(b"ab"),
+            IoSlice::new(b"cd"),
+            IoSlice::new(b"ef"),
+        ];
+        assert_eq!(deque.write_vectored(&bufs).unwrap(), 5);
+        assert_eq!(deque.write(b"g").unwrap(), 0);
+        let err = deque.write_all(b"g").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(deque, *b"abcde");
+    }
+
+    #[test]
+    fn overflow_policy_panic_allows_filling() {
+        let mut deque = ArrayDeque::with_policy(4, OverflowPolicy::Panic);
+        deque.extend([2, 3]);
+        deque.push_back(4);
+        deque.push_front(1);
+        assert_eq!(deque, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "deque is full (capacity 2)")]
+    fn overflow_policy_panic_push_back() {
+        let mut deque = ArrayDeque::with_policy(2, OverflowPolicy::Panic);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "deque is full (capacity 2)")]
+    fn overflow_policy_panic_push_front() {
+        let mut deque = ArrayDeque::with_policy(2, OverflowPolicy::Panic);
+        deque.push_front(1);
+        deque.push_front(2);
+        deque.push_front(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "deque is full (capacity 2)")]
+    fn overflow_policy_panic_extend() {
+        let mut deque = ArrayDeque::with_policy(2, OverflowPolicy::Panic);
+        deque.extend([1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "deque is full (capacity 0)")]
+    fn overflow_policy_panic_zero_capacity() {
+        ArrayDeque::with_policy(0, OverflowPolicy::Panic).push_back(1);
+    }
+
+    #[test]
+    fn extend_from_slice_drop_counts() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let item = DropCounter::new(drops.clone());
+        let mut deque = ArrayDeque::new(3);
+        deque.extend_from_slice(&[item.clone(), item.clone()]);
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+        deque.extend_from_slice(&[item.clone(), item.clone()]);
+        // Two temporaries plus the single overwritten front element.
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+        drop(deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 8);
+    }
+
+    #[test]
+    fn extend_exact_size_skips_overwritten_items() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let created = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back((100, DropCounter::new(drops.clone())));
+
+        deque.extend((0..10).map(|i| {
+            created.fetch_add(1, Ordering::SeqCst);
+            (i, DropCounter::new(drops.clone()))
+        }));
+        assert_eq!(created.load(Ordering::SeqCst), 10);
+        // The old element and the seven skipped items.
+        assert_eq!(drops.load(Ordering::SeqCst), 8);
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [7, 8, 9]);
+
+        // Without an exact length the items go through the ring one by one.
+        deque.extend(
+            (10..15)
+                .filter(|_| true)
+                .map(|i| (i, DropCounter::new(drops.clone()))),
+        );
+        assert_eq!(drops.load(Ordering::SeqCst), 13);
+        assert_eq!(
+            deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            [12, 13, 14]
+        );
+
+        deque.extend([(20, DropCounter::new(drops.clone()))]);
+        assert_eq!(
+            deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            [13, 14, 20]
+        );
+        deque.extend(core::iter::empty());
+        assert_eq!(deque.len(), 3);
+    }
+
+    #[test]
+    fn extend_from_slice_clones_only_the_tail() {
+        let clones = Arc::new(AtomicUsize::new(0));
+
+        struct Tracked(u32, Arc<AtomicUsize>);
+
+        impl Clone for Tracked {
+            fn clone(&self) -> Self {
+                self.1.fetch_add(1, Ordering::SeqCst);
+                Tracked(self.0, self.1.clone())
+            }
+        }
+
+        let source: Vec<_> = (0..100).map(|i| Tracked(i, clones.clone())).collect();
+        let mut deque = ArrayDeque::new(4);
+        deque.extend_from_slice(&source);
+        assert_eq!(clones.load(Ordering::SeqCst), 4);
+        assert_eq!(
+            deque.iter().map(|t| t.0).collect::<Vec<_>>(),
+            [96, 97, 98, 99]
+        );
+    }
+
+    #[test]
+    fn extend_front_preserves_order() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(4);
+        deque.push_back(5);
+        deque.extend_front([1, 2, 3]);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+        let mut unsized_source = ArrayDeque::new(5);
+        unsized_source.push_back(9);
+        unsized_source.extend_front((1..4).filter(|_| true));
+        assert_eq!(
+            unsized_source.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 9]
+        );
+    }
+
+    #[test]
+    fn extend_front_overflow_evicts_from_back() {
+        let mut deque = ArrayDeque::new(5);
+        for i in [7, 8, 9, 10, 11, 12] {
+            deque.push_back(i);
+        }
+        deque.extend_front([1, 2]);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 8, 9, 10]);
+
+        deque.extend_front((0..8).filter(|_| true));
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        deque.extend_front(core::iter::empty());
+        assert_eq!(deque.len(), 5);
+    }
+
+    #[test]
+    fn extend_front_drops_each_element_once() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back((100, DropCounter::new(drops.clone())));
+        deque.extend_front((0..4).map(|i| (i, DropCounter::new(drops.clone()))));
+        // The old element and the fourth new item do not fit.
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [0, 1, 2]);
+        drop(deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn extend_by_reference() {
+        let source = [1, 2, 3, 4, 5];
+        let mut deque: ArrayDeque<i32> = ArrayDeque::new(4);
+        deque.extend(source[..2].iter());
+        deque.extend(&source[2..]);
+        assert_eq!(deque, [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn extend() {
+        let mut deque = ArrayDeque::new(5);
+        deque.extend(vec![1, 2, 3]);
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 1);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn into_iter_empty() {
+        let deque: ArrayDeque<i32> = ArrayDeque::new(5);
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn into_iter_full() {
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn into_iter_partial() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(1);
+        deque.push_back(2);
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn into_iter_rev() {
+        let mut deque = ArrayDeque::new(3);
+        for item in [1, 2, 3, 4] {
+            deque.push_back(item);
+        }
+        let items: Vec<_> = deque.into_iter().rev().collect();
+        assert_eq!(items, vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn into_iter_interleaved_front_and_back() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn into_iter_interleaved_drop_counts() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque = ArrayDeque::new(5);
+            for _ in 0..5 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            let mut iter = deque.into_iter();
+            drop(iter.next_back());
+            drop(iter.next());
+            drop(iter.next_back());
+            assert_eq!(drops.load(Ordering::SeqCst), 3);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn iter_clone_fused_debug() {
+        let mut deque = ArrayDeque::new(3);
+        for item in [0, 1, 2, 3] {
+            deque.push_back(item);
+        }
+        let mut iter = (&deque).into_iter();
+        assert_eq!(iter.next(), Some(&1));
+
+        let cloned = iter.clone();
+        assert_eq!(cloned.copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(format!("{:?}", iter), "ArrayDequeIter([2], [3])");
+
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(format!("{:?}", iter), "ArrayDequeIter([], [3])");
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        fn assert_fused<I: FusedIterator + Clone>(_: &I) {}
+        assert_fused(&iter);
+    }
+
+    #[test]
+    fn into_iter_clone_fused_debug() {
+        let mut deque = ArrayDeque::new(3);
+        deque.extend([1, 2, 3]);
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next(), Some(1));
+
+        let cloned = iter.clone();
+        assert_eq!(cloned.collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(format!("{:?}", iter), "ArrayDequeIntoIter([2, 3])");
+
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_nth_count_last() {
+        let mut deque = ArrayDeque::new(5);
+        deque.extend(0..8);
+        let mut iter = (&deque).into_iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.nth(1), Some(&4));
+        assert_eq!(iter.nth(1), Some(&6));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.clone().count(), 1);
+        assert_eq!(iter.clone().last(), Some(&7));
+        assert_eq!(iter.nth(1), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.clone().last(), None);
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn into_iter_nth_drops_skipped_once() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque = ArrayDeque::new(6);
+            for _ in 0..6 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            let mut iter = deque.into_iter();
+            let third = iter.nth(2);
+            assert!(third.is_some());
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
+            assert_eq!(iter.len(), 3);
+
+            assert!(iter.nth(5).is_none());
+            assert_eq!(drops.load(Ordering::SeqCst), 5);
+            assert_eq!(iter.len(), 0);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn into_iter_count_and_last() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let make = || {
+            let mut deque = ArrayDeque::new(4);
+            for _ in 0..4 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            deque.into_iter()
+        };
+
+        assert_eq!(make().count(), 4);
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+
+        let last = make().last();
+        assert!(last.is_some());
+        assert_eq!(drops.load(Ordering::SeqCst), 7);
+        drop(last);
+        assert_eq!(drops.load(Ordering::SeqCst), 8);
+
+        let mut deque = ArrayDeque::new(3);
+        for item in [1, 2, 3, 4] {
+            deque.push_back(item);
+        }
+        assert_eq!(deque.into_iter().last(), Some(4));
+    }
+
+    #[test]
+    fn iter_empty() {
+        let deque: ArrayDeque<i32> = ArrayDeque::new(5);
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_full() {
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_partial() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(1);
+        deque.push_back(2);
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn is_empty() {
+        let deque: ArrayDeque<i32> = ArrayDeque::new(5);
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn is_full() {
+        let mut deque = ArrayDeque::new(3);
+        assert!(!deque.is_full());
+        deque.push_back(1);
+        deque.push_back(2);
+        assert!(!deque.is_full());
+        deque.push_back(3);
+        assert!(deque.is_full());
+    }
+
+    #[test]
+    fn clear_empty() {
+        let mut deque = ArrayDeque::<()>::new(3);
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn clear_non_empty() {
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn push_back_overwrite_drops_replaced_element() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque = ArrayDeque::new(2);
+            deque.push_back(DropCounter::new(drops.clone()));
+            deque.push_back(DropCounter::new(drops.clone()));
+            deque.push_back(DropCounter::new(drops.clone()));
+
+            assert_eq!(drops.load(Ordering::SeqCst), 1);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+    }
+
+    fn contents<T: Clone, A: Allocator>(deque: &ArrayDeque<T, A>) -> Vec<T> {
         deque.iter().cloned().collect()
     }
-}
 
-impl<T: Clone> From<&Vec<T>> for ArrayDeque<T> {
-    /// Clones each element from the vector.
-    fn from(vec: &Vec<T>) -> Self {
-        let mut deque = ArrayDeque::new(vec.len().max(1));
-        for item in vec {
-            deque.push_back(item.clone());
+    #[test]
+    fn insert_shifts_shorter_side() {
+        let mut deque = ArrayDeque::new(6);
+        deque.extend([1, 2, 4, 5]);
+        deque.insert(2, 3);
+        assert_eq!(contents(&deque), vec![1, 2, 3, 4, 5]);
+        deque.insert(0, 0);
+        assert_eq!(contents(&deque), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn insert_across_wrap_point() {
+        let mut deque = ArrayDeque::new(6);
+        deque.extend([0, 0, 0, 0]);
+        for _ in 0..4 {
+            deque.pop_front();
+        }
+        // Front now sits at slot 4, so the contents wrap around the end.
+        deque.extend([1, 2, 3, 5]);
+        deque.insert(3, 4);
+        assert_eq!(contents(&deque), vec![1, 2, 3, 4, 5]);
+        deque.insert(1, 9);
+        assert_eq!(contents(&deque), vec![1, 9, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn insert_on_full_evicts_back() {
+        let mut deque = ArrayDeque::new(3);
+        deque.extend([1, 2, 3]);
+        deque.insert(1, 9);
+        assert_eq!(contents(&deque), vec![1, 9, 2]);
+        deque.insert(3, 7);
+        assert_eq!(contents(&deque), vec![1, 9, 7]);
+        assert_eq!(deque.try_insert(0, 0), Err(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds_panics() {
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back(1);
+        deque.insert(2, 2);
+    }
+
+    #[test]
+    fn remove_shifts_shorter_side() {
+        let mut deque = ArrayDeque::new(5);
+        deque.extend([1, 2, 3, 4, 5]);
+        assert_eq!(deque.remove(1), Some(2));
+        assert_eq!(contents(&deque), vec![1, 3, 4, 5]);
+        assert_eq!(deque.remove(2), Some(4));
+        assert_eq!(contents(&deque), vec![1, 3, 5]);
+        assert_eq!(deque.remove(3), None);
+    }
+
+    #[test]
+    fn remove_across_wrap_point() {
+        let mut deque = ArrayDeque::new(5);
+        deque.extend([0, 1, 2, 3, 4]);
+        deque.extend([5, 6, 7]);
+        // Physical layout is [5, 6, 7, 3, 4] with the front at slot 3.
+        assert_eq!(deque.remove(1), Some(4));
+        assert_eq!(contents(&deque), vec![3, 5, 6, 7]);
+        assert_eq!(deque.remove(2), Some(6));
+        assert_eq!(contents(&deque), vec![3, 5, 7]);
+    }
+
+    /// Returns a full deque of `0..cap + 3` whose front sits at slot 3.
+    fn wrapped_range(cap: i32) -> ArrayDeque<i32> {
+        let mut deque = ArrayDeque::new(cap as usize);
+        for i in 0..cap + 3 {
+            deque.push_back(i);
+        }
+        deque
+    }
+
+    #[test]
+    fn windows_across_wrap_point() {
+        let deque = wrapped_range(5);
+        assert_eq!(deque.as_slices(), (&[3, 4][..], &[5, 6, 7][..]));
+        let windows: Vec<Vec<i32>> = deque
+            .windows(4)
+            .map(|(a, b)| a.iter().chain(b).copied().collect())
+            .collect();
+        assert_eq!(windows, [vec![3, 4, 5, 6], vec![4, 5, 6, 7]]);
+        let arrays: Vec<[i32; 2]> = deque.array_windows().collect();
+        assert_eq!(arrays, [[3, 4], [4, 5], [5, 6], [6, 7]]);
+        assert_eq!(deque.windows(6).next(), None);
+    }
+
+    #[test]
+    fn chunks_across_wrap_point() {
+        let deque = wrapped_range(5);
+        let chunks: Vec<Vec<i32>> = deque
+            .chunks(2)
+            .map(|(a, b)| a.iter().chain(b).copied().collect())
+            .collect();
+        assert_eq!(chunks, [vec![3, 4], vec![5, 6], vec![7]]);
+        let chunks: Vec<_> = deque.chunks_exact(3).collect();
+        assert_eq!(chunks, [(&[3, 4][..], &[5][..])]);
+        assert_eq!(deque.chunks_exact(3).remainder(), (&[6, 7][..], &[][..]));
+    }
+
+    #[test]
+    fn slice_views_across_wrap_point() {
+        let deque = wrapped_range(5);
+        let view = deque.slice(1..4);
+        assert_eq!(view.as_slices(), (&[4][..], &[5, 6][..]));
+        assert_eq!(view, [4, 5, 6]);
+        assert_eq!((view[0], view[2], view.get(3)), (4, 6, None));
+        assert_eq!(view.slice(1..), [5, 6]);
+        assert_eq!(view.slice(..=0).as_slices(), (&[4][..], &[][..]));
+        assert!(deque.slice(5..).is_empty());
+        assert_eq!(format!("{:?}", deque.slice(..)), "[3, 4, 5, 6, 7]");
+    }
+
+    #[test]
+    fn slice_mut_writes_through() {
+        let mut deque = wrapped_range(5);
+        let mut view = deque.slice_mut(1..=3);
+        view[0] = 40;
+        *view.get_mut(2).unwrap() = 60;
+        assert!(view.get_mut(3).is_none());
+        view.slice_mut(1..2).iter_mut().for_each(|x| *x *= 10);
+        assert_eq!(format!("{view:?}"), "[40, 50, 60]");
+        assert_eq!(deque, [3, 40, 50, 60, 7]);
+    }
+
+    #[test]
+    fn cursor_moves_through_ghost() {
+        let mut deque = wrapped_range(3);
+        let mut cursor = deque.cursor_back_mut();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 5)));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.peek_next(), Some(&mut 3));
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.peek_next(), None);
+
+        let mut empty: ArrayDeque<i32> = ArrayDeque::new(2);
+        let mut cursor = empty.cursor_back_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), None);
+    }
+
+    #[test]
+    fn cursor_removes_at_head_and_tail() {
+        let mut deque = wrapped_range(5);
+        let mut cursor = deque.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 4));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(7));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_prev();
+        *cursor.current().unwrap() *= 10;
+        assert_eq!(
+            format!("{cursor:?}"),
+            "CursorMut { index: Some(2), deque: [4, 5, 60] }"
+        );
+        assert_eq!(deque, [4, 5, 60]);
+
+        let mut deque = ArrayDeque::from([1]);
+        let mut cursor = deque.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!((cursor.index(), cursor.remove_current()), (None, None));
+        cursor.insert_before(2);
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+        assert_eq!(deque, [2]);
+    }
+
+    #[test]
+    fn cursor_inserts_at_wrap_boundary() {
+        // Physical layout is [_, _, _, 10, 11, 12] with the front at slot 3:
+        // inserting after the back element writes into slot 0.
+        let mut deque = ArrayDeque::new(6);
+        deque.extend([0, 0, 0]);
+        while deque.pop_front().is_some() {}
+        deque.extend([10, 11, 12]);
+        let mut cursor = deque.cursor_back_mut();
+        cursor.insert_after(13);
+        cursor.insert_before(115);
+        assert_eq!(cursor.current(), Some(&mut 12));
+        assert_eq!(cursor.peek_next(), Some(&mut 13));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_after(9);
+        cursor.insert_before(14);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(deque, [10, 11, 115, 12, 13, 14]);
+    }
+
+    #[test]
+    fn cursor_insert_into_full_deque_evicts_front() {
+        let mut deque = wrapped_range(4);
+        let mut cursor = deque.cursor_back_mut();
+        cursor.move_prev();
+        cursor.insert_after(55);
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.peek_next(), Some(&mut 55));
+        assert_eq!(deque, [4, 5, 55, 6]);
+
+        // The cursor was on the evicted front, so it moves to its successor.
+        let mut cursor = deque.cursor_front_mut();
+        cursor.insert_before(0);
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.peek_prev(), Some(&mut 0));
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.insert_before(7);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(deque, [5, 55, 6, 7]);
+
+        deque.set_policy(OverflowPolicy::Reject);
+        deque.cursor_front_mut().insert_after(8);
+        deque.cursor_back_mut().insert_before(9);
+        assert_eq!(deque, [5, 55, 6, 7]);
+        ArrayDeque::<i32>::new(0).cursor_front_mut().insert_after(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "deque is full (capacity 2)")]
+    fn cursor_insert_into_full_deque_panics_under_panic_policy() {
+        let mut deque = ArrayDeque::with_policy(2, OverflowPolicy::Panic);
+        deque.extend([1, 2]);
+        deque.cursor_back_mut().insert_before(3);
+    }
+
+    #[test]
+    fn pop_if_checks_predicate() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(2);
+        assert!(deque.pop_front_if(|_| unreachable!()).is_none());
+        assert!(deque.pop_back_if(|_| unreachable!()).is_none());
+        deque.push_back(DropCounter::new(drops.clone()));
+        deque.push_back(DropCounter::new(drops.clone()));
+
+        assert!(deque.pop_front_if(|_| false).is_none());
+        assert!(deque.pop_back_if(|_| false).is_none());
+        assert_eq!((deque.len(), drops.load(Ordering::SeqCst)), (2, 0));
+
+        let popped = deque.pop_back_if(|_| true);
+        assert!(popped.is_some());
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(popped);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        assert!(deque.pop_front_if(|_| true).is_some());
+        assert!(deque.is_empty());
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn pop_if_on_wrapped_deque() {
+        // Elements 3..7 with the front at slot 3 and the back at slot 2.
+        let mut deque = wrapped_range(4);
+        assert_eq!(deque.pop_back_if(|x| *x % 2 == 1), None);
+        assert_eq!(deque.pop_back_if(|x| *x == 6), Some(6));
+        assert_eq!(deque.pop_front_if(|x| *x < 4), Some(3));
+        assert_eq!(deque.pop_front_if(|x| *x < 4), None);
+        assert_eq!(deque, [4, 5]);
+    }
+
+    #[test]
+    fn pop_while_stops_at_first_rejected() {
+        // Elements 3..9, split into [3, 4, 5] and [6, 7, 8] by the wrap point.
+        let mut deque = wrapped_range(6);
+        assert_eq!(deque.pop_front_while(|_| false).count(), 0);
+        assert_eq!(deque.pop_back_while(|x| *x > 8).count(), 0);
+        assert_eq!(deque.len(), 6);
+
+        // Exactly up to the wrap boundary, from each end.
+        assert!(deque.pop_front_while(|x| *x < 6).eq([3, 4, 5]));
+        let mut calls = 0;
+        let popped = deque.pop_back_while(|x| {
+            calls += 1;
+            *x != 7
+        });
+        assert!(popped.eq([8]));
+        assert_eq!(calls, 2);
+        assert!(deque.iter().eq(&[6, 7]));
+
+        assert!(deque.pop_back_while(|_| true).eq([7, 6]));
+        assert!(deque.is_empty());
+        assert_eq!(deque.pop_front_while(|_| true).next(), None);
+    }
+
+    #[test]
+    fn pop_while_abandoned_keeps_rest() {
+        let mut deque = wrapped_range(6);
+        let mut popped = deque.pop_front_while(|_| true);
+        assert_eq!(popped.next(), Some(3));
+        assert_eq!(popped.next(), Some(4));
+        drop(popped);
+        assert!(deque.iter().eq(&[5, 6, 7, 8]));
+    }
+
+    #[test]
+    fn map_keeps_capacity_order_and_policy() {
+        let mut deque = wrapped_range(5);
+        deque.set_policy(OverflowPolicy::Reject);
+        deque.pop_back();
+        let mut mapped = deque.map(|x| u64::from(x as u8) << 40);
+        assert_eq!(mapped, [3 << 40, 4 << 40, 5 << 40, 6 << 40]);
+        assert_eq!(
+            (mapped.capacity(), mapped.policy()),
+            (5, OverflowPolicy::Reject)
+        );
+        mapped.push_front(0);
+        mapped.push_back(1);
+        assert_eq!(mapped.len(), 5);
+        assert_eq!(mapped.back(), Some(&(6 << 40)));
+
+        let empty: ArrayDeque<()> = ArrayDeque::new(0);
+        assert_eq!(empty.map(|()| 1u8).capacity(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn map_panic_drops_both_sides() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let source_drops = Arc::new(AtomicUsize::new(0));
+        let mapped_drops = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(6);
+        for i in 0..6 {
+            deque.push_back((i, DropCounter::new(source_drops.clone())));
+        }
+        deque.pop_front();
+        deque.push_back((6, DropCounter::new(source_drops.clone())));
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            deque.map(|(i, _)| {
+                if i == 4 {
+                    panic!("map failed");
+                }
+                DropCounter::new(mapped_drops.clone())
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(source_drops.load(Ordering::SeqCst), 7);
+        assert_eq!(mapped_drops.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn leak_returns_contiguous_contents() {
+        let leaked: &'static mut [String] = {
+            let mut deque = ArrayDeque::new(4);
+            for word in ["a", "b", "c", "d", "e", "f"] {
+                deque.push_back(String::from(word));
+            }
+            deque.leak()
+        };
+        leaked[0].push('!');
+        assert_eq!(leaked, ["c!", "d", "e", "f"]);
+
+        let empty: &'static mut [u8] = ArrayDeque::new(0).leak();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn into_boxed_slice_is_front_to_back() {
+        let boxed = wrapped_range(5).into_boxed_slice();
+        assert_eq!(*boxed, [3, 4, 5, 6, 7]);
+
+        let mut deque = wrapped_range(5);
+        deque.pop_front();
+        deque.pop_back();
+        let boxed = deque.into_boxed_slice();
+        assert_eq!(*boxed, [4, 5, 6]);
+
+        assert!(ArrayDeque::<String>::new(3).into_boxed_slice().is_empty());
+        assert_eq!(ArrayDeque::from([(); 3]).into_boxed_slice().len(), 3);
+    }
+
+    #[test]
+    fn from_iter_with_capacity_keeps_newest() {
+        let shorter = ArrayDeque::from_iter_with_capacity(5, 0..3);
+        assert_eq!(shorter, [0, 1, 2]);
+        assert_eq!(shorter.capacity(), 5);
+        let equal = ArrayDeque::from_iter_with_capacity(3, 0..3);
+        assert_eq!(equal, [0, 1, 2]);
+        assert_eq!(equal.capacity(), 3);
+        let longer = ArrayDeque::from_iter_with_capacity(4, 0..1_000);
+        assert_eq!(longer, [996, 997, 998, 999]);
+        assert_eq!(longer.capacity(), 4);
+        // Without an exact size hint, every item goes through the buffer.
+        let filtered = ArrayDeque::from_iter_with_capacity(3, (0..1_000).filter(|x| x % 7 == 0));
+        assert_eq!(filtered, [980, 987, 994]);
+        assert!(ArrayDeque::from_iter_with_capacity(0, 0..10).is_empty());
+    }
+
+    #[test]
+    fn from_iter_sizes_buffer_to_items() {
+        let exact: ArrayDeque<i32> = (0..5).map(|x| x * 2).collect();
+        assert_eq!(exact, [0, 2, 4, 6, 8]);
+        assert_eq!(exact.capacity(), 5);
+        let inexact: ArrayDeque<i32> = (0..100).filter(|x| x % 25 == 0).collect();
+        assert_eq!(inexact, [0, 25, 50, 75]);
+        assert_eq!(inexact.capacity(), 4);
+    }
+
+    #[test]
+    fn from_iter_tolerates_wrong_exact_hint() {
+        /// Yields `0..len` while claiming to yield exactly `hint` items.
+        struct Lying {
+            next: u32,
+            len: u32,
+            hint: usize,
+        }
+
+        impl Iterator for Lying {
+            type Item = u32;
+
+            fn next(&mut self) -> Option<u32> {
+                (self.next < self.len).then(|| {
+                    self.next += 1;
+                    self.next - 1
+                })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.hint, Some(self.hint))
+            }
+        }
+
+        let short: ArrayDeque<u32> = Lying {
+            next: 0,
+            len: 2,
+            hint: 6,
+        }
+        .collect();
+        assert_eq!((short.capacity(), short.len()), (2, 2));
+        assert_eq!(short, [0, 1]);
+        let long: ArrayDeque<u32> = Lying {
+            next: 0,
+            len: 7,
+            hint: 3,
+        }
+        .collect();
+        assert_eq!((long.capacity(), long.len()), (7, 7));
+        assert_eq!(long, [0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn from_vec_takes_over_buffer() {
+        let vec = vec![String::from("a"), String::from("b")];
+        let buffer = vec.as_ptr();
+        let deque = ArrayDeque::from(vec);
+        assert_eq!(deque.as_slices().0.as_ptr(), buffer);
+        assert_eq!(deque.capacity(), 2);
+
+        let mut vec = Vec::with_capacity(10);
+        vec.extend([1, 2, 3]);
+        let deque = ArrayDeque::from(vec);
+        assert_eq!((deque.capacity(), deque), (3, ArrayDeque::from([1, 2, 3])));
+        assert_eq!(ArrayDeque::from(vec![(); 4]).capacity(), 4);
+    }
+
+    #[test]
+    fn get_back_counts_from_back() {
+        let mut deque: ArrayDeque<i32> = ArrayDeque::new(3);
+        assert_eq!(deque.get_back(0), None);
+        assert_eq!(deque.get_back_mut(0), None);
+        assert_eq!(deque.get_back(usize::MAX), None);
+
+        let mut deque = ArrayDeque::from([7]);
+        assert_eq!(deque.get_back(0), Some(&7));
+        assert_eq!(deque.get_back(1), None);
+        *deque.get_back_mut(0).unwrap() = 8;
+        assert_eq!(deque.back(), Some(&8));
+
+        // Elements 3..8, split into [3, 4] and [5, 6, 7] by the wrap point.
+        let mut deque = wrapped_range(5);
+        assert_eq!(deque.get_back(0), deque.back());
+        assert_eq!([deque.get_back(2), deque.get_back(3)], [Some(&5), Some(&4)]);
+        assert_eq!(deque.get_back(4), Some(&3));
+        assert_eq!(deque.get_back(5), None);
+        *deque.get_back_mut(3).unwrap() = 40;
+        assert_eq!(deque[1], 40);
+    }
+
+    #[test]
+    fn spare_capacity_commits_across_wrap_point() {
+        let mut deque = ArrayDeque::new(5);
+        deque.extend(["a", "b", "c", "d"].map(String::from));
+        deque.pop_front();
+        deque.pop_front();
+        deque.pop_front();
+        // The front is `d` at slot 3; slot 4 is free, then slots 0 to 2.
+        let (first, second) = deque.spare_capacity_mut();
+        assert_eq!((first.len(), second.len()), (1, 3));
+        first[0].write(String::from("e"));
+        second[0].write(String::from("f"));
+        unsafe { deque.advance_back(2) };
+        assert!(deque.iter().eq(["d", "e", "f"]));
+
+        let (first, second) = deque.spare_capacity_mut();
+        assert_eq!((first.len(), second.len()), (2, 0));
+        first[0].write(String::from("b"));
+        first[1].write(String::from("c"));
+        unsafe { deque.advance_front(2) };
+        assert!(deque.iter().eq(["b", "c", "d", "e", "f"]));
+        assert!(deque.is_full());
+        let (first, second) = deque.spare_capacity_mut();
+        assert!(first.is_empty() && second.is_empty());
+        unsafe {
+            deque.advance_back(0);
+            deque.advance_front(0);
+        }
+        assert_eq!(deque.len(), 5);
+    }
+
+    #[test]
+    fn advance_front_wraps_below_slot_zero() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(String::from("c"));
+        let (first, second) = deque.spare_capacity_mut();
+        assert_eq!((first.len(), second.len()), (4, 0));
+        first[2].write(String::from("a"));
+        first[3].write(String::from("b"));
+        unsafe { deque.advance_front(2) };
+        assert!(deque.iter().eq(["a", "b", "c"]));
+        assert_eq!(deque.pop_front().as_deref(), Some("a"));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot advance past the end of the free space")]
+    fn advance_back_past_free_space_panics() {
+        let mut deque = ArrayDeque::new(5);
+        deque.push_back(String::new());
+        unsafe { deque.advance_back(5) };
+    }
+
+    #[test]
+    fn matches_vecdeque_under_random_operations() {
+        let mut rng = TestRng::new(0x9e37_79b9);
+        for cap in 1..=7 {
+            let mut deque = ArrayDeque::new(cap);
+            let mut model = VecDeque::new();
+            for step in 0..2_000u32 {
+                match rng.below(7) {
+                    0 => {
+                        if model.len() == cap {
+                            model.pop_front();
+                        }
+                        model.push_back(step);
+                        deque.push_back(step);
+                    }
+                    1 => {
+                        if model.len() == cap {
+                            model.pop_back();
+                        }
+                        model.push_front(step);
+                        deque.push_front(step);
+                    }
+                    2 => assert_eq!(deque.pop_back(), model.pop_back()),
+                    3 => assert_eq!(deque.pop_front(), model.pop_front()),
+                    4 if !model.is_empty() => {
+                        let i = rng.below(model.len());
+                        assert_eq!(deque.remove(i), model.remove(i));
+                    }
+                    5 if !model.is_empty() => {
+                        let i = rng.below(model.len());
+                        deque[i] = step;
+                        model[i] = step;
+                    }
+                    _ => {
+                        let clone = deque.clone();
+                        assert!(clone.into_iter().rev().eq(model.iter().rev().copied()));
+                    }
+                }
+                assert_eq!(deque.len(), model.len());
+                assert!(deque.iter().eq(model.iter()));
+                assert!((0..model.len()).all(|i| deque[i] == model[i]));
+            }
+        }
+    }
+
+    #[test]
+    fn iter_matches_index_order_across_wrap() {
+        for cap in 1..=6 {
+            for offset in 0..cap {
+                for len in 0..=cap {
+                    let mut deque = ArrayDeque::new(cap);
+                    for _ in 0..offset {
+                        deque.push_back(0);
+                        deque.pop_front();
+                    }
+                    deque.extend(0..len);
+                    let expected: Vec<&usize> = (0..len).map(|i| &deque[i]).collect();
+                    assert!(deque.iter().eq(expected.iter().copied()));
+                    assert!(
+                        (&deque)
+                            .into_iter()
+                            .rev()
+                            .eq(expected.iter().rev().copied())
+                    );
+                    assert_eq!(deque.iter().fold(0, |acc, &x| acc * 7 + x), {
+                        expected.iter().fold(0, |acc, &&x| acc * 7 + x)
+                    });
+                    for n in 0..=len {
+                        let mut iter = deque.iter();
+                        assert_eq!(iter.nth(n), expected.get(n).copied());
+                        assert_eq!(iter.len(), len.saturating_sub(n + 1));
+                        assert_eq!(
+                            iter.next_back(),
+                            expected.get(n + 1..).and_then(|rest| rest.last().copied())
+                        );
+                    }
+                    let mut iter = deque.iter();
+                    let mut mixed = Vec::new();
+                    while let Some(&x) = iter.next() {
+                        mixed.push(x);
+                        mixed.extend(iter.next_back());
+                    }
+                    mixed.sort_unstable();
+                    assert_eq!(mixed, (0..len).collect::<Vec<_>>());
+                    assert!(deque.clone().into_iter().eq(expected.iter().map(|&&x| x)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn iter_can_be_stored_by_name() {
+        struct Reader<'a> {
+            samples: ArrayDequeIter<'a, i32>,
+        }
+
+        impl Reader<'_> {
+            fn take_pair(&mut self) -> Option<(i32, i32)> {
+                Some((*self.samples.next()?, *self.samples.next_back()?))
+            }
+        }
+
+        let deque = wrapped_range(5);
+        let mut reader = Reader {
+            samples: deque.iter(),
+        };
+        assert_eq!(reader.samples.len(), 5);
+        assert_eq!(reader.take_pair(), Some((3, 7)));
+        assert_eq!(reader.take_pair(), Some((4, 6)));
+        assert_eq!(reader.take_pair(), None);
+        assert_eq!(reader.samples.len(), 0);
+    }
+
+    #[test]
+    fn reverse_in_place() {
+        let mut deque: ArrayDeque<i32> = ArrayDeque::new(4);
+        deque.reverse();
+        assert!(deque.is_empty());
+        deque.push_back(1);
+        deque.reverse();
+        assert_eq!(deque, [1]);
+
+        // Odd length, wrapped: [3, 4] then [5, 6, 7].
+        let mut deque = wrapped_range(5);
+        deque.reverse();
+        assert_eq!(deque, [7, 6, 5, 4, 3]);
+        deque.pop_front();
+        deque.reverse();
+        assert_eq!(deque, [3, 4, 5, 6]);
+
+        // Even length, full and wrapped: [3, 4, 5] then [6, 7, 8].
+        let mut deque = wrapped_range(6);
+        deque.reverse();
+        assert_eq!(deque, [8, 7, 6, 5, 4, 3]);
+        deque.push_back(9);
+        assert_eq!(deque, [7, 6, 5, 4, 3, 9]);
+    }
+
+    #[test]
+    fn range_within_bounds() {
+        let deque = wrapped_range(5);
+        assert_eq!(deque.range(3..3).next(), None);
+        assert_eq!(deque.range(5..).count(), 0);
+        assert_eq!(deque.range(..).collect::<Vec<_>>(), [&3, &4, &5, &6, &7]);
+        assert_eq!(deque.range(1..4).copied().collect::<Vec<_>>(), [4, 5, 6]);
+        assert_eq!(
+            deque.range(..=2).rev().copied().collect::<Vec<_>>(),
+            [5, 4, 3]
+        );
+    }
+
+    #[test]
+    fn range_mut_across_wrap_point() {
+        let mut deque = wrapped_range(5);
+        deque.range_mut(1..4).for_each(|x| *x = -*x);
+        assert_eq!(deque, [3, -4, -5, -6, 7]);
+        assert_eq!((deque[1], deque[3]), (-4, -6));
+        assert_eq!(deque.range_mut(4..4).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 7 out of range for slice of length 5")]
+    fn range_mut_past_end_panics() {
+        let _ = wrapped_range(5).range_mut(3..=6);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 6 out of range for slice of length 5")]
+    fn slice_past_end_panics() {
+        wrapped_range(5).slice(2..6);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice index starts at 3 but ends at 2")]
+    fn slice_inverted_panics() {
+        #[allow(clippy::reversed_empty_ranges)]
+        wrapped_range(5).slice(3..2);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start index 6 out of range for slice of length 5")]
+    fn slice_mut_start_past_end_panics() {
+        wrapped_range(5).slice_mut(6..);
+    }
+
+    #[test]
+    #[cfg(feature = "stats")]
+    fn stats_count_overwrites() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        deque.push_front(-1);
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (7, 3));
+        // Exact-size fast path: 4 old elements and the first 6 items are lost.
+        deque.extend(0..10);
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (17, 13));
+        // Without an exact size hint, items are pushed one at a time.
+        deque.extend((0..6).filter(|_| true));
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (23, 19));
+        deque.extend_from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (28, 24));
+        deque.pop_front();
+        deque.insert(0, 9);
+        deque.insert(0, 9);
+        deque.extend_front([7, 8, 9, 10, 11, 12]);
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (36, 31));
+        assert_eq!(deque.max_len_seen(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "stats")]
+    fn stats_survive_clear_and_take() {
+        let mut deque = ArrayDeque::new(3);
+        deque.extend([1, 2]);
+        deque.clear();
+        deque.push_back(3);
+        assert_eq!(deque.total_pushed(), 3);
+        assert_eq!(deque.max_len_seen(), 2);
+        let taken = deque.take();
+        assert_eq!(taken.total_pushed(), 0);
+        assert_eq!(taken.max_len_seen(), 1);
+        assert_eq!(deque.total_pushed(), 3);
+        assert_eq!(deque.clone().total_pushed(), 3);
+        deque.reset_stats();
+        assert_eq!((deque.total_pushed(), deque.max_len_seen()), (0, 0));
+        assert_eq!(ArrayDeque::from(vec![1, 2]).max_len_seen(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "stats")]
+    fn stats_ignore_rejected_pushes() {
+        let mut deque = ArrayDeque::with_policy(2, OverflowPolicy::Reject);
+        deque.extend([1, 2, 3]);
+        deque.push_front(0);
+        deque.extend_from_slice(&[4, 5]);
+        assert_eq!(deque.try_push_back(6), Err(6));
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (2, 0));
+    }
+
+    #[test]
+    fn extract_if_across_wrap_point() {
+        let mut deque = wrapped_range(8);
+        let evens: Vec<i32> = deque.extract_if(|x| *x % 2 == 0).collect();
+        assert_eq!(evens, [4, 6, 8, 10]);
+        assert_eq!(contents(&deque), vec![3, 5, 7, 9]);
+        deque.push_back(11);
+        assert_eq!(deque.pop_front(), Some(3));
+        assert_eq!(contents(&deque), vec![5, 7, 9, 11]);
+    }
+
+    #[test]
+    fn extract_if_partially_consumed() {
+        let mut deque = wrapped_range(8);
+        let mut iter = deque.extract_if(|x| {
+            let hit = *x % 3 == 0;
+            *x += 100;
+            hit
+        });
+        assert_eq!(iter.next(), Some(103));
+        assert_eq!(iter.next(), Some(106));
+        drop(iter);
+        // 3..=6 were tested (and bumped), 7..=10 were never reached.
+        assert_eq!(contents(&deque), vec![104, 105, 7, 8, 9, 10]);
+        assert_eq!(deque.len(), 6);
+        assert!(deque.extract_if(|_| false).next().is_none());
+        assert_eq!(contents(&deque), vec![104, 105, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn extract_if_drop_counts() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(4);
+        for _ in 0..6 {
+            deque.push_back(DropCounter::new(drops.clone()));
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+        let mut n = 0;
+        let extracted: Vec<_> = deque
+            .extract_if(|_| {
+                n += 1;
+                n % 2 == 1
+            })
+            .collect();
+        assert_eq!(extracted.len(), 2);
+        assert_eq!(deque.len(), 2);
+        drop(extracted);
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+        drop(deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn extract_if_predicate_panic_keeps_rest() {
+        let mut deque = wrapped_range(8);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            deque
+                .extract_if(|x| {
+                    assert_ne!(*x, 7);
+                    *x % 2 == 0
+                })
+                .for_each(drop);
+        }));
+        assert!(result.is_err());
+        assert_eq!(contents(&deque), vec![3, 5, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn insert_remove_drop_counts() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque = ArrayDeque::new(3);
+            for _ in 0..3 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            deque.insert(1, DropCounter::new(drops.clone()));
+            assert_eq!(drops.load(Ordering::SeqCst), 1);
+            drop(deque.remove(1));
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn swap_across_wrap_point() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        // Physical layout is [4, 5, 2, 3] with the front at slot 2.
+        deque.swap(0, 3);
+        assert_eq!(contents(&deque), vec![5, 3, 4, 2]);
+        deque.swap(1, 1);
+        assert_eq!(contents(&deque), vec![5, 3, 4, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_out_of_bounds_panics() {
+        let mut deque = ArrayDeque::new(4);
+        deque.extend([1, 2]);
+        deque.swap(0, 2);
+    }
+
+    #[test]
+    fn swap_remove_front_and_back() {
+        let mut deque = ArrayDeque::new(5);
+        for item in [0, 1, 2, 3, 4, 5, 6] {
+            deque.push_back(item);
+        }
+        assert_eq!(deque.swap_remove_back(1), Some(3));
+        assert_eq!(contents(&deque), vec![2, 6, 4, 5]);
+        assert_eq!(deque.swap_remove_front(2), Some(4));
+        assert_eq!(contents(&deque), vec![6, 2, 5]);
+        assert_eq!(deque.swap_remove_front(0), Some(6));
+        assert_eq!(deque.swap_remove_back(1), Some(5));
+        assert_eq!(contents(&deque), vec![2]);
+        assert_eq!(deque.swap_remove_back(1), None);
+        assert_eq!(deque.swap_remove_front(0), Some(2));
+        assert_eq!(deque.swap_remove_front(0), None);
+    }
+
+    #[test]
+    fn truncate_keeps_front() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        deque.truncate(5);
+        assert_eq!(contents(&deque), vec![2, 3, 4, 5]);
+        deque.truncate(3);
+        assert_eq!(contents(&deque), vec![2, 3, 4]);
+        deque.push_back(6);
+        assert_eq!(contents(&deque), vec![2, 3, 4, 6]);
+        deque.truncate(0);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn truncate_front_keeps_back() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        deque.truncate_front(4);
+        assert_eq!(contents(&deque), vec![2, 3, 4, 5]);
+        deque.truncate_front(3);
+        assert_eq!(contents(&deque), vec![3, 4, 5]);
+        deque.truncate_front(1);
+        assert_eq!(contents(&deque), vec![5]);
+        deque.push_front(4);
+        assert_eq!(contents(&deque), vec![4, 5]);
+        deque.truncate_front(0);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn truncate_drops_each_discarded_element_once() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque = ArrayDeque::new(4);
+            for _ in 0..6 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
+            deque.truncate(3);
+            assert_eq!(drops.load(Ordering::SeqCst), 3);
+            deque.truncate_front(1);
+            assert_eq!(drops.load(Ordering::SeqCst), 5);
+            deque.truncate(1);
+            deque.truncate_front(1);
+            assert_eq!(drops.load(Ordering::SeqCst), 5);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn as_slices_wrapped() {
+        let mut deque = ArrayDeque::new(4);
+        assert_eq!(deque.as_slices(), (&[][..], &[][..]));
+        deque.extend([1, 2, 3]);
+        assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[][..]));
+        deque.extend([4, 5, 6]);
+        assert_eq!(deque.as_slices(), (&[3, 4][..], &[5, 6][..]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn io_write_keeps_last_capacity_bytes() {
+        use std::io::{IoSlice, Write};
+
+        let mut deque = ArrayDeque::new(4);
+        assert_eq!(deque.write(b"abc").unwrap(), 3);
+        assert_eq!(deque.write(b"defghi").unwrap(), 6);
+        assert_eq!(deque, *b"fghi");
+
+        deque.write_all(b"jk").unwrap();
+        assert_eq!(deque, *b"hijk");
+
+        let bufs = [IoSlice::new(b"lm"), IoSlice::new(b""), IoSlice::new(b"nop")];
+        assert_eq!(deque.write_vectored(&bufs).unwrap(), 5);
+        assert_eq!(deque, *b"mnop");
+        deque.flush().unwrap();
+
+        let mut empty = ArrayDeque::new(0);
+        assert_eq!(empty.write(b"xyz").unwrap(), 3);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn io_read_straddles_wrap_point() {
+        use std::io::Read;
+
+        let mut deque = ArrayDeque::new(5);
+        for byte in b"abcdefg" {
+            deque.push_back(*byte);
+        }
+        assert_eq!(deque.as_slices(), (&b"cde"[..], &b"fg"[..]));
+
+        let mut buf = [0; 4];
+        assert_eq!(deque.read(&mut buf).unwrap(), 4);
+        assert_eq!(&buf, b"cdef");
+        assert_eq!(deque, *b"g");
+
+        deque.extend_from_slice(b"hij");
+        assert_eq!(deque.read(&mut buf[..2]).unwrap(), 2);
+        assert_eq!(&buf[..2], b"gh");
+        assert_eq!(deque.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], b"ij");
+        assert_eq!(deque.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn io_read_exact_unexpected_eof() {
+        use std::io::{ErrorKind, Read};
+
+        let mut deque = ArrayDeque::from(*b"abc");
+        let mut buf = [0; 4];
+        let err = deque.read_exact(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(deque, *b"abc");
+
+        deque.read_exact(&mut buf[..3]).unwrap();
+        assert_eq!(&buf[..3], b"abc");
+        let err = deque.read_exact(&mut buf[..1]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn io_buf_read_line_across_wrap() {
+        use std::io::BufRead;
+
+        let mut deque = ArrayDeque::new(8);
+        deque.extend_from_slice(b"xxxxxab");
+        deque.consume(5);
+        deque.extend_from_slice(b"c\nde\n");
+        assert_eq!(deque.fill_buf().unwrap(), b"abc");
+
+        let mut line = String::new();
+        assert_eq!(deque.read_line(&mut line).unwrap(), 4);
+        assert_eq!(line, "abc\n");
+
+        assert_eq!(deque.fill_buf().unwrap(), b"de\n");
+        line.clear();
+        assert_eq!(deque.read_line(&mut line).unwrap(), 3);
+        assert_eq!(line, "de\n");
+        assert!(deque.fill_buf().unwrap().is_empty());
+        assert_eq!(deque.read_line(&mut line).unwrap(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    struct ShortReader<'a> {
+        data: &'a [u8],
+        chunk: usize,
+        interrupts: usize,
+    }
+
+    #[cfg(feature = "std")]
+    impl std::io::Read for ShortReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.interrupts > 0 {
+                self.interrupts -= 1;
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            let n = buf.len().min(self.chunk).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_from_fills_both_free_regions() {
+        let mut deque = ArrayDeque::new(6);
+        deque.extend_from_slice(b"xxab");
+        deque.truncate_front(2);
+
+        let mut input: &[u8] = b"cdefghij";
+        assert_eq!(deque.read_from(&mut input).unwrap(), 4);
+        assert_eq!(deque, *b"abcdef");
+        assert_eq!(deque.as_slices(), (&b"abcd"[..], &b"ef"[..]));
+        assert_eq!(deque.read_from(&mut input).unwrap(), 0);
+        assert_eq!(input, b"ghij");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_from_short_and_interrupted_reads() {
+        let mut reader = ShortReader {
+            data: b"hello world",
+            chunk: 3,
+            interrupts: 2,
+        };
+        let mut deque = ArrayDeque::new(8);
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 3);
+        assert_eq!(deque, *b"hel");
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 3);
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 2);
+        assert_eq!(deque, *b"hello wo");
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 0);
+
+        deque.truncate_front(0);
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 3);
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 0);
+        assert_eq!(deque, *b"rld");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_from_propagates_errors() {
+        struct Failing;
+
+        impl std::io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+        }
+
+        let mut deque = ArrayDeque::from(*b"ab");
+        deque.pop_back();
+        let err = deque.read_from(&mut Failing).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+        assert_eq!(deque, *b"a");
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-io")]
+    fn embedded_io_round_trip() {
+        use embedded_io::{Read, ReadReady, Write, WriteReady};
+
+        let mut deque = ArrayDeque::new(5);
+        assert_eq!(deque.read_ready(), Ok(false));
+        assert_eq!(deque.write(b"abc"), Ok(3));
+        let mut buf = [0; 2];
+        assert_eq!(deque.read(&mut buf), Ok(2));
+        assert_eq!(&buf, b"ab");
+
+        assert_eq!(deque.write(b"defghi"), Ok(4));
+        assert_eq!(deque.write_ready(), Ok(false));
+        assert_eq!(deque.write(b"j"), Err(crate::FullError));
+        assert_eq!(deque.write(b""), Ok(0));
+        assert_eq!(deque.as_slices(), (&b"cde"[..], &b"fg"[..]));
+
+        let mut buf = [0; 8];
+        assert_eq!(deque.read(&mut buf), Ok(5));
+        assert_eq!(&buf[..5], b"cdefg");
+        assert_eq!(deque.read(&mut buf), Ok(0));
+        assert_eq!(deque.read_ready(), Ok(false));
+        assert_eq!(deque.write_ready(), Ok(true));
+        deque.flush().unwrap();
+
+        // `write_all` reports the full deque instead of panicking on `Ok(0)`.
+        let err = deque.write_all(b"xyz123").unwrap_err();
+        assert_eq!(
+            embedded_io::Error::kind(&err),
+            embedded_io::ErrorKind::WriteZero
+        );
+        assert_eq!(deque.len(), 5);
+        assert!(deque.write_all(b"").is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes_buf_reads_across_chunks() {
+        use bytes::Buf;
+
+        let mut deque = ArrayDeque::new(6);
+        for byte in *b"xxxxabcdef" {
+            deque.push_back(byte);
+        }
+        assert_eq!(deque.remaining(), 6);
+        assert_eq!(deque.chunk(), b"ab");
+        deque.advance(1);
+        assert_eq!(deque.chunk(), b"b");
+        assert_eq!(deque.get_u16(), u16::from_be_bytes(*b"bc"));
+        assert_eq!(deque.chunk(), b"def");
+
+        let mut rest = [0; 3];
+        Buf::copy_to_slice(&mut deque, &mut rest);
+        assert_eq!(&rest, b"def");
+        assert!(!deque.has_remaining());
+        assert!(deque.chunk().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    #[should_panic(expected = "cannot advance past the end of the deque")]
+    fn bytes_buf_advance_past_end_panics() {
+        use bytes::Buf;
+
+        let mut deque = ArrayDeque::from(*b"ab");
+        deque.advance(3);
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes_buf_mut_writes_across_chunks() {
+        use bytes::BufMut;
+
+        let mut deque = ArrayDeque::new(6);
+        deque.extend_from_slice(b"xxxxab");
+        deque.truncate_front(2);
+        assert_eq!(deque.remaining_mut(), 4);
+        assert_eq!(deque.chunk_mut().len(), 4);
+
+        deque.put_slice(b"c");
+        deque.put_u16(u16::from_be_bytes(*b"de"));
+        assert_eq!(deque.chunk_mut().len(), 1);
+        deque.put_u8(b'f');
+        assert_eq!(deque.remaining_mut(), 0);
+        assert_eq!(deque.chunk_mut().len(), 0);
+        assert_eq!(deque, *b"abcdef");
+
+        let mut deque = ArrayDeque::new(6);
+        deque.extend_from_slice(b"xxxab");
+        deque.truncate_front(2);
+        assert_eq!(deque.chunk_mut().len(), 1);
+        deque.put_slice(b"cdef");
+        assert_eq!(deque.as_slices(), (&b"abc"[..], &b"def"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary_generates_wrapped_deques() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut rng = TestRng::new(0x2545_f491);
+        let mut wrapped = 0;
+        for _ in 0..64 {
+            let data: Vec<u8> = (0..48).map(|_| rng.next_u16() as u8).collect();
+            let deque = <ArrayDeque<u16>>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            assert!(deque.capacity() <= ARBITRARY_MAX_CAPACITY);
+            assert!(deque.len() <= deque.capacity());
+            assert_eq!(deque.iter().count(), deque.len());
+            if !deque.as_slices().1.is_empty() {
+                wrapped += 1;
+            }
+        }
+        assert!(wrapped > 0);
+        assert_eq!(<ArrayDeque<u16>>::size_hint(0).0, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "quickcheck")]
+    fn quickcheck_push_pop_round_trip() {
+        fn prop(mut deque: ArrayDeque<i32>, x: i32) -> bool {
+            if deque.is_full() {
+                return true;
+            }
+            let before = deque.to_vec();
+            deque.push_back(x);
+            let back = deque.pop_back();
+            deque.push_front(x);
+            let front = deque.pop_front();
+            back == Some(x) && front == Some(x) && deque.to_vec() == before
+        }
+        quickcheck::quickcheck(prop as fn(ArrayDeque<i32>, i32) -> bool);
+    }
+
+    #[test]
+    #[cfg(feature = "quickcheck")]
+    fn quickcheck_iter_matches_index() {
+        fn prop(deque: ArrayDeque<i32>) -> bool {
+            deque.iter().enumerate().all(|(i, x)| *x == deque[i])
+        }
+        quickcheck::quickcheck(prop as fn(ArrayDeque<i32>) -> bool);
+    }
+
+    #[test]
+    #[cfg(feature = "quickcheck")]
+    fn quickcheck_shrink_unwraps_then_shrinks() {
+        use quickcheck::Arbitrary;
+
+        let mut deque = ArrayDeque::new(6);
+        for i in 0..8 {
+            deque.push_back(i);
+        }
+        deque.pop_back();
+        let mut shrunk = deque.shrink();
+        let first = shrunk.next().unwrap();
+        assert_eq!(first, deque);
+        assert!(first.as_slices().1.is_empty());
+        for candidate in shrunk {
+            assert!(candidate.capacity() <= deque.capacity());
+            assert!(candidate.len() <= deque.len());
+        }
+    }
+
+    #[test]
+    fn copy_to_slice_wrapped() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        let mut buf = [0; 4];
+        deque.copy_to_slice(&mut buf);
+        assert_eq!(buf, [2, 3, 4, 5]);
+        assert_eq!(deque, [2, 3, 4, 5]);
+
+        let mut strings = vec![String::new(); 4];
+        let mut words = ArrayDeque::new(4);
+        for item in deque.iter().chain(&[6, 7]) {
+            words.push_back(format!("{item}"));
+        }
+        words.clone_into_slice(&mut strings);
+        assert_eq!(strings, ["4", "5", "6", "7"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "destination length does not match deque length")]
+    fn copy_to_slice_larger_dst_panics() {
+        let mut deque = ArrayDeque::new(4);
+        deque.extend([1, 2, 3]);
+        deque.copy_to_slice(&mut [0; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "destination length does not match deque length")]
+    fn clone_into_slice_smaller_dst_panics() {
+        let mut deque = ArrayDeque::new(4);
+        deque.extend([1, 2, 3]);
+        deque.clone_into_slice(&mut [0; 2]);
+    }
+
+    #[test]
+    fn to_vec_wrapped() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        let vec = deque.to_vec();
+        assert_eq!(vec, [2, 3, 4, 5]);
+        assert_eq!(vec.capacity(), 4);
+        assert_eq!(deque, vec);
+        assert!(ArrayDeque::<i32>::new(0).to_vec().is_empty());
+    }
+
+    #[test]
+    fn contains_and_index_of_wrapped() {
+        let mut deque = ArrayDeque::new(4);
+        assert!(!deque.contains(&1));
+        assert_eq!(deque.index_of(&1), None);
+
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        // Physical layout is [4, 5, 2, 3]; 4 and 5 live in the second half.
+        assert!(deque.contains(&5));
+        assert!(!deque.contains(&1));
+        assert_eq!(deque.index_of(&2), Some(0));
+        assert_eq!(deque.index_of(&4), Some(2));
+        assert_eq!(deque.index_of(&5), Some(3));
+        assert_eq!(deque.index_of(&0), None);
+    }
+
+    #[test]
+    fn binary_search_wrapped() {
+        let mut deque = ArrayDeque::new(5);
+        for item in [0, 10, 20, 30, 40, 50, 60] {
+            deque.push_back(item);
+        }
+        // Physical layout is [50, 60, 20, 30, 40] with the front at slot 2.
+        assert_eq!(deque.binary_search(&20), Ok(0));
+        assert_eq!(deque.binary_search(&40), Ok(2));
+        assert_eq!(deque.binary_search(&50), Ok(3));
+        assert_eq!(deque.binary_search(&60), Ok(4));
+        assert_eq!(deque.binary_search(&5), Err(0));
+        assert_eq!(deque.binary_search(&45), Err(3));
+        assert_eq!(deque.binary_search(&55), Err(4));
+        assert_eq!(deque.binary_search(&70), Err(5));
+        assert_eq!(deque.binary_search_by_key(&6, |x| x / 10), Ok(4));
+    }
+
+    #[test]
+    fn binary_search_duplicates() {
+        let mut deque = ArrayDeque::new(6);
+        for item in [0, 0, 1, 2, 2, 2, 2, 3] {
+            deque.push_back(item);
+        }
+        let found = deque.binary_search(&2).unwrap();
+        assert!((1..5).contains(&found));
+        assert_eq!(deque[found], 2);
+        assert_eq!(deque.partition_point(|&x| x < 2), 1);
+        assert_eq!(deque.partition_point(|&x| x <= 2), 5);
+    }
+
+    #[test]
+    fn partition_point_wrapped() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
         }
-        deque
+        // Physical layout is [4, 5, 2, 3] with the front at slot 2.
+        assert_eq!(deque.partition_point(|&x| x < 0), 0);
+        assert_eq!(deque.partition_point(|&x| x < 3), 1);
+        assert_eq!(deque.partition_point(|&x| x < 5), 3);
+        assert_eq!(deque.partition_point(|&x| x < 9), 4);
+
+        let cutoff = deque.partition_point(|&x| x <= 3);
+        deque.truncate_front(deque.len() - cutoff);
+        assert_eq!(contents(&deque), vec![4, 5]);
     }
-}
 
-impl<T: Clone, const N: usize> From<&[T; N]> for ArrayDeque<T> {
-    /// Clones each element from the array reference.
-    fn from(array: &[T; N]) -> Self {
-        let mut deque = ArrayDeque::new(N.max(1));
-        for item in array {
-            deque.push_back(item.clone());
+    #[test]
+    fn sort_wrapped() {
+        let mut deque = ArrayDeque::new(5);
+        for item in [0, 0, 9, 3, 7, 1, 5] {
+            deque.push_back(item);
         }
-        deque
+        deque.pop_back();
+        // Front sits at slot 2 and the contents wrap around the end.
+        deque.sort();
+        assert_eq!(contents(&deque), vec![1, 3, 7, 9]);
+        deque.push_back(8);
+        deque.push_front(0);
+        assert_eq!(contents(&deque), vec![0, 1, 3, 7, 9]);
     }
-}
 
-#[cfg(feature = "serde")]
-impl<T: Serialize> Serialize for ArrayDeque<T> {
-    /// Serializes the deque as a sequence (front to back).
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        use serde::ser::SerializeSeq;
+    #[test]
+    fn sort_by_custom_comparator() {
+        let mut deque = ArrayDeque::new(4);
+        deque.extend([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (3, 'e')]);
+        deque.sort_by(|a, b| b.0.cmp(&a.0));
+        assert_eq!(
+            contents(&deque),
+            vec![(3, 'e'), (2, 'c'), (1, 'b'), (1, 'd')]
+        );
 
-        let mut seq = serializer.serialize_seq(Some(self.len))?;
-        for item in self.iter() {
-            seq.serialize_element(item)?;
-        }
-        seq.end()
+        deque.sort_unstable_by_key(|&(_, c)| c);
+        assert_eq!(
+            contents(&deque),
+            vec![(1, 'b'), (2, 'c'), (1, 'd'), (3, 'e')]
+        );
+
+        deque.sort_unstable_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+        assert_eq!(
+            contents(&deque),
+            vec![(1, 'd'), (1, 'b'), (2, 'c'), (3, 'e')]
+        );
     }
-}
 
-#[cfg(feature = "serde")]
-impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArrayDeque<T> {
-    /// Deserializes a sequence into a deque (capacity == item count).
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let vec: Vec<T> = Vec::deserialize(deserializer)?;
-        Ok(ArrayDeque::from(vec))
+    #[test]
+    fn push_evict_returns_overwritten_element() {
+        let mut deque = ArrayDeque::new(3);
+        assert_eq!(deque.push_back_evict(1), None);
+        assert_eq!(deque.push_back_evict(2), None);
+        assert_eq!(deque.push_back_evict(3), None);
+        assert_eq!(deque.push_back_evict(4), Some(1));
+        assert_eq!(deque.push_front_evict(0), Some(4));
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 0);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
     }
-}
 
-impl<T> IntoIterator for ArrayDeque<T> {
-    type Item = T;
-    type IntoIter = ArrayDequeIntoIter<T>;
-    /// Consumes the deque and returns an iterator over its elements.
-    fn into_iter(self) -> Self::IntoIter {
-        ArrayDequeIntoIter { deque: self }
+    #[test]
+    fn push_evict_moves_element_out() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque = ArrayDeque::new(1);
+            deque.push_back(DropCounter::new(drops.clone()));
+            let back = deque.push_back_evict(DropCounter::new(drops.clone()));
+            let front = deque.push_front_evict(DropCounter::new(drops.clone()));
+            assert!(back.is_some());
+            assert!(front.is_some());
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
     }
-}
 
-/// An owning iterator that moves elements out of an `ArrayDeque`.
-///
-/// Returned by `into_iter()`.
-pub struct ArrayDequeIntoIter<T> {
-    deque: ArrayDeque<T>,
-}
+    #[test]
+    fn try_extend_stops_at_first_rejected_element() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(3);
+        deque.push_back((0, DropCounter::new(drops.clone())));
 
-impl<T> Iterator for ArrayDequeIntoIter<T> {
-    type Item = T;
+        let items = (1..6).map(|i| (i, DropCounter::new(drops.clone())));
+        let err = deque.try_extend(items).unwrap_err();
+        assert_eq!(err.element.0, 3);
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [0, 1, 2]);
 
-    /// Advances and returns the next element.
-    fn next(&mut self) -> Option<T> {
-        self.deque.pop_front()
+        let rest: Vec<_> = err.remaining.map(|(i, _)| i).collect();
+        assert_eq!(rest, [4, 5]);
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+        assert!(deque.try_extend(core::iter::empty()).is_ok());
+        deque.pop_front();
+        assert!(
+            deque
+                .try_extend([(9, DropCounter::new(drops.clone()))])
+                .is_ok()
+        );
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [1, 2, 9]);
     }
-}
 
-impl<'a, T> IntoIterator for &'a ArrayDeque<T> {
-    type Item = &'a T;
-    type IntoIter = ArrayDequeIter<'a, T>;
-    /// Borrows the deque and returns an iterator over `&T`.
-    fn into_iter(self) -> Self::IntoIter {
-        ArrayDequeIter {
-            deque: self,
-            pos: 0,
+    #[test]
+    fn try_push_back_and_front() {
+        let mut deque = ArrayDeque::new(3);
+        assert_eq!(deque.try_push_back(2), Ok(()));
+        assert_eq!(deque.try_push_front(1), Ok(()));
+        assert_eq!(deque.try_push_back(3), Ok(()));
+        assert_eq!(deque.try_push_back(4), Err(4));
+        assert_eq!(deque.try_push_front(0), Err(0));
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 1);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    fn try_push_on_full_does_not_drop_or_overwrite() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque = ArrayDeque::new(2);
+            deque.push_back(DropCounter::new(drops.clone()));
+            deque.push_back(DropCounter::new(drops.clone()));
+
+            let back = deque.try_push_back(DropCounter::new(drops.clone()));
+            let front = deque.try_push_front(DropCounter::new(drops.clone()));
+            assert!(back.is_err());
+            assert!(front.is_err());
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
+            assert_eq!(deque.len(), 2);
+
+            drop(back);
+            drop(front);
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
         }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
     }
-}
 
-/// A borrowed iterator over `&T` from an `ArrayDeque`.
-///
-/// Returned by `iter()` and `&deque.into_iter()`.
-pub struct ArrayDequeIter<'a, T> {
-    deque: &'a ArrayDeque<T>,
-    pos: usize,
-}
+    #[test]
+    fn into_iter_partial_consumption_no_double_drop() {
+        let drops = Arc::new(AtomicUsize::new(0));
 
-impl<'a, T> Iterator for ArrayDequeIter<'a, T> {
-    type Item = &'a T;
+        {
+            let mut deque = ArrayDeque::new(2);
+            deque.push_back(DropCounter::new(drops.clone()));
+            deque.push_back(DropCounter::new(drops.clone()));
 
-    /// Advances and returns the next reference.
-    fn next(&mut self) -> Option<&'a T> {
-        if self.pos >= self.deque.len {
-            return None;
+            let mut iter = deque.into_iter();
+            let _first = iter.next();
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
         }
-        let idx = (self.deque.idx + self.pos) % self.deque.cap;
-        self.pos += 1;
-        unsafe { Some(&*self.deque.ptr.add(idx)) }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
     }
-}
 
-impl<'a, T> ExactSizeIterator for ArrayDequeIter<'a, T> {}
+    #[test]
+    fn into_iter_dropped_after_two_of_five() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque = ArrayDeque::new(5);
+            for _ in 0..5 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use core::sync::atomic::{AtomicUsize, Ordering};
+            let mut iter = deque.into_iter();
+            drop(iter.next());
+            drop(iter.next());
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
+        }
 
-    #[cfg(not(feature = "std"))]
-    use alloc::{sync::Arc, vec};
-    #[cfg(feature = "std")]
-    use std::sync::Arc;
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+    }
 
-    #[derive(Clone)]
-    struct DropCounter {
-        drops: Arc<AtomicUsize>,
+    #[test]
+    fn from_empty_slice_has_zero_capacity() {
+        let slice: &[i32] = &[];
+        let deque = ArrayDeque::from(slice);
+
+        assert!(deque.is_empty());
+        assert_eq!(deque.capacity(), 0);
+
+        let collected: ArrayDeque<i32> = core::iter::empty().collect();
+        assert_eq!(collected.capacity(), 0);
+        assert_eq!(ArrayDeque::<i32>::from(Vec::new()).capacity(), 0);
+        assert_eq!(ArrayDeque::<i32>::from([]).capacity(), 0);
     }
 
-    impl DropCounter {
-        fn new(drops: Arc<AtomicUsize>) -> Self {
-            Self { drops }
+    #[test]
+    fn try_new_reports_capacity_overflow() {
+        let deque = ArrayDeque::<u64>::try_new(4).unwrap();
+        assert_eq!(deque.capacity(), 4);
+
+        assert_eq!(
+            ArrayDeque::<u64>::try_new(usize::MAX).unwrap_err(),
+            TryReserveError::CapacityOverflow
+        );
+        assert_eq!(
+            ArrayDeque::<u8>::try_new(isize::MAX as usize + 1).unwrap_err(),
+            TryReserveError::CapacityOverflow
+        );
+        // Zero-sized types never allocate, so any capacity succeeds.
+        assert_eq!(
+            ArrayDeque::<()>::try_new(usize::MAX).unwrap().capacity(),
+            usize::MAX
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn new_panics_on_capacity_overflow() {
+        let _ = ArrayDeque::<u64>::new(usize::MAX);
+    }
+
+    #[derive(Clone, Default)]
+    struct CountingAlloc {
+        allocs: Arc<AtomicUsize>,
+        deallocs: Arc<AtomicUsize>,
+    }
+
+    unsafe impl Allocator for CountingAlloc {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.allocs.fetch_add(1, Ordering::SeqCst);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.deallocs.fetch_add(1, Ordering::SeqCst);
+            unsafe { Global.deallocate(ptr, layout) }
         }
     }
 
-    impl Drop for DropCounter {
-        fn drop(&mut self) {
-            self.drops.fetch_add(1, Ordering::SeqCst);
+    struct FailingAlloc;
+
+    unsafe impl Allocator for FailingAlloc {
+        fn allocate(&self, _: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+
+        unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {
+            unreachable!("nothing was allocated");
         }
     }
 
     #[test]
-    fn push_pop() {
-        let mut deque = ArrayDeque::new(3);
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        assert_eq!(deque.pop_front(), Some(1));
-        assert_eq!(deque.pop_back(), Some(3));
-        assert_eq!(deque.pop_front(), Some(2));
-        assert_eq!(deque.pop_front(), None);
+    fn new_in_pairs_alloc_and_dealloc() {
+        let alloc = CountingAlloc::default();
+        let mut deque = ArrayDeque::new_in(3, alloc.clone());
+        assert_eq!(alloc.allocs.load(Ordering::SeqCst), 1);
+
+        for i in 0..5 {
+            deque.push_back(i);
+        }
+        assert_eq!(contents(&deque), vec![2, 3, 4]);
+        assert_eq!(alloc.deallocs.load(Ordering::SeqCst), 0);
+
+        drop(deque);
+        assert_eq!(alloc.allocs.load(Ordering::SeqCst), 1);
+        assert_eq!(alloc.deallocs.load(Ordering::SeqCst), 1);
     }
 
     #[test]
-    fn push_front_back() {
-        let mut deque = ArrayDeque::new(3);
-        deque.push_front(1);
-        deque.push_front(2);
-        deque.push_back(3);
-        assert_eq!(deque.pop_front(), Some(2));
-        assert_eq!(deque.pop_back(), Some(3));
-        assert_eq!(deque.pop_front(), Some(1));
-        assert_eq!(deque.pop_front(), None);
+    fn clone_wrapped_is_linearized() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        let cloned = deque.clone();
+        assert_eq!(cloned, [2, 3, 4, 5]);
+        assert_eq!(cloned.as_slices(), (&[2, 3, 4, 5][..], &[][..]));
+        assert_eq!(cloned.capacity(), 4);
     }
 
     #[test]
-    fn iter() {
-        let mut deque = ArrayDeque::new(5);
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), Some(&3));
-        assert_eq!(iter.next(), None);
+    #[cfg(feature = "std")]
+    fn clone_panic_drops_cloned_elements_and_frees_buffer() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let clones = Arc::new(AtomicUsize::new(0));
+        let drops = Arc::new(AtomicUsize::new(0));
+        let alloc = CountingAlloc::default();
+        let mut deque = ArrayDeque::new_in(4, alloc.clone());
+        for _ in 0..6 {
+            deque.push_back(Bomb::new(&clones, &drops));
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+        let result = catch_unwind(AssertUnwindSafe(|| deque.clone()));
+        assert!(result.is_err());
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+        assert_eq!(alloc.allocs.load(Ordering::SeqCst), 2);
+        assert_eq!(alloc.deallocs.load(Ordering::SeqCst), 1);
+        assert_eq!(deque.len(), 4);
     }
 
     #[test]
-    fn clear() {
-        let mut deque = ArrayDeque::new(3);
+    fn clone_allocates_from_same_allocator() {
+        let alloc = CountingAlloc::default();
+        let mut deque = ArrayDeque::new_in(2, alloc.clone());
         deque.push_back(1);
-        deque.push_back(2);
-        deque.clear();
-        assert!(deque.is_empty());
-        assert_eq!(deque.len(), 0);
-    }
+        let cloned = deque.clone();
+        assert_eq!(alloc.allocs.load(Ordering::SeqCst), 2);
+        assert_eq!(cloned, deque);
 
-    #[test]
-    fn capacity() {
-        let deque = ArrayDeque::<i32>::new(5);
-        assert_eq!(deque.capacity(), 5);
-        assert!(deque.is_empty());
+        drop(deque);
+        drop(cloned);
+        assert_eq!(alloc.deallocs.load(Ordering::SeqCst), 2);
     }
 
     #[test]
-    fn clone() {
-        let mut deque = ArrayDeque::new(3);
+    fn into_iter_deallocates_through_allocator() {
+        let alloc = CountingAlloc::default();
+        let mut deque = ArrayDeque::new_in(2, alloc.clone());
         deque.push_back(1);
         deque.push_back(2);
-        let cloned_deque = deque.clone();
-        assert_eq!(cloned_deque.len(), 2);
-        assert_eq!(cloned_deque[0], 1);
-        assert_eq!(cloned_deque[1], 2);
+
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        drop(iter);
+        assert_eq!(alloc.allocs.load(Ordering::SeqCst), 1);
+        assert_eq!(alloc.deallocs.load(Ordering::SeqCst), 1);
     }
 
     #[test]
-    fn from_iter() {
-        let vec = vec![1, 2, 3];
-        let deque: ArrayDeque<_> = vec.into_iter().collect();
-        assert_eq!(deque.len(), 3);
-        assert_eq!(deque[0], 1);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+    fn zero_sized_layouts_skip_allocator() {
+        let alloc = CountingAlloc::default();
+        drop(ArrayDeque::<u32, _>::new_in(0, alloc.clone()));
+        drop(ArrayDeque::<(), _>::new_in(8, alloc.clone()));
+        assert_eq!(alloc.allocs.load(Ordering::SeqCst), 0);
+        assert_eq!(alloc.deallocs.load(Ordering::SeqCst), 0);
     }
 
     #[test]
-    fn from_slice() {
-        let slice = [1, 2, 3];
-        let deque: ArrayDeque<_> = ArrayDeque::from(&slice[..]);
-        assert_eq!(deque.len(), 3);
-        assert_eq!(deque[0], 1);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+    fn try_new_in_reports_alloc_error() {
+        let layout = Layout::array::<u32>(4).unwrap();
+        assert_eq!(
+            ArrayDeque::<u32, _>::try_new_in(4, FailingAlloc).unwrap_err(),
+            TryReserveError::AllocError { layout }
+        );
     }
 
     #[test]
-    fn from_array() {
-        let array = [1, 2, 3];
-        let deque: ArrayDeque<_> = ArrayDeque::from(array);
-        assert_eq!(deque.len(), 3);
-        assert_eq!(deque[0], 1);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+    fn take_hands_off_buffer() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(3);
+        for _ in 0..4 {
+            deque.push_back(DropCounter::new(drops.clone()));
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        let buffer = deque.ptr;
+
+        let batch = deque.take();
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.ptr, buffer);
+        assert!(deque.is_empty());
+        assert_eq!(deque.capacity(), 3);
+
+        deque.push_back(DropCounter::new(drops.clone()));
+        assert_eq!(deque.len(), 1);
+        drop(batch);
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn set_capacity_grow_wrapped() {
+        let alloc = CountingAlloc::default();
+        let mut deque = ArrayDeque::new_in(4, alloc.clone());
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        assert!(!deque.as_slices().1.is_empty());
+
+        deque.set_capacity(6).unwrap();
+        assert_eq!(deque.capacity(), 6);
+        assert_eq!(deque, [2, 3, 4, 5]);
+        deque.extend([6, 7, 8]);
+        assert_eq!(deque, [3, 4, 5, 6, 7, 8]);
+        assert_eq!(alloc.allocs.load(Ordering::SeqCst), 2);
+        assert_eq!(alloc.deallocs.load(Ordering::SeqCst), 1);
     }
 
     #[test]
-    fn index() {
-        let mut deque = ArrayDeque::new(5);
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        assert_eq!(deque[0], 1);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+    fn set_capacity_shrink_keeps_newest() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..6 {
+            deque.push_back((i, DropCounter::new(drops.clone())));
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+        deque.set_capacity(2).unwrap();
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+        assert_eq!(deque.capacity(), 2);
+        assert!(deque.is_full());
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [4, 5]);
+
+        deque.set_capacity(0).unwrap();
+        assert!(deque.is_empty());
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+        deque.set_capacity(1).unwrap();
+        deque.push_back((9, DropCounter::new(drops.clone())));
+        assert_eq!(deque.len(), 1);
     }
 
     #[test]
-    #[should_panic]
-    fn index_out_of_bounds_panics() {
-        let mut deque = ArrayDeque::new(5);
-        deque.push_back(1);
-        let _ = deque[1];
+    fn set_capacity_failure_leaves_deque_unchanged() {
+        let mut deque = ArrayDeque::<u64>::new(3);
+        for item in [1, 2, 3, 4] {
+            deque.push_back(item);
+        }
+        assert_eq!(
+            deque.set_capacity(usize::MAX).unwrap_err(),
+            TryReserveError::CapacityOverflow
+        );
+        assert_eq!(deque.capacity(), 3);
+        assert_eq!(deque, [2, 3, 4]);
+
+        let mut empty = ArrayDeque::<u32, _>::new_in(0, FailingAlloc);
+        assert!(matches!(
+            empty.set_capacity(4),
+            Err(TryReserveError::AllocError { .. })
+        ));
+        assert_eq!(empty.capacity(), 0);
     }
 
     #[test]
-    fn index_mut() {
-        let mut deque = ArrayDeque::new(5);
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        deque[0] = 10;
-        assert_eq!(deque[0], 10);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+    fn try_reserve_error_display() {
+        assert_eq!(
+            format!("{}", TryReserveError::CapacityOverflow),
+            "requested capacity exceeds the maximum allocation size"
+        );
+        let layout = Layout::array::<u32>(4).unwrap();
+        assert_eq!(
+            format!("{}", TryReserveError::AllocError { layout }),
+            "memory allocation of 16 bytes failed"
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn index_mut_out_of_bounds_panics() {
-        let mut deque = ArrayDeque::new(5);
-        deque.push_back(1);
-        deque[1] = 99;
+    fn zero_capacity() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: ArrayDeque<DropCounter> = ArrayDeque::new(0);
+        assert_eq!(deque.capacity(), 0);
+        assert_eq!(deque.len(), 0);
+        assert!(deque.is_empty());
+        assert!(deque.is_full());
+
+        deque.push_back(DropCounter::new(drops.clone()));
+        deque.push_front(DropCounter::new(drops.clone()));
+        deque.insert(0, DropCounter::new(drops.clone()));
+        deque.extend([DropCounter::new(drops.clone())]);
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+        assert!(
+            deque
+                .push_back_evict(DropCounter::new(drops.clone()))
+                .is_some()
+        );
+        assert!(
+            deque
+                .push_front_evict(DropCounter::new(drops.clone()))
+                .is_some()
+        );
+        assert!(
+            deque
+                .try_push_back(DropCounter::new(drops.clone()))
+                .is_err()
+        );
+        assert!(
+            deque
+                .try_push_front(DropCounter::new(drops.clone()))
+                .is_err()
+        );
+        assert!(
+            deque
+                .try_insert(0, DropCounter::new(drops.clone()))
+                .is_err()
+        );
+        assert_eq!(drops.load(Ordering::SeqCst), 9);
+        assert!(deque.is_empty());
+
+        assert!(deque.pop_back().is_none());
+        assert!(deque.pop_front().is_none());
+        assert!(deque.remove(0).is_none());
+        assert!(deque.swap_remove_back(0).is_none());
+        assert!(deque.swap_remove_front(0).is_none());
+        assert!(deque.front().is_none());
+        assert!(deque.back().is_none());
+        assert!(deque.get(0).is_none());
+        assert!(deque.get_mut(0).is_none());
+        assert_eq!(deque.iter().count(), 0);
+        assert_eq!((&deque).into_iter().count(), 0);
+        assert!(deque.as_slices().0.is_empty() && deque.as_slices().1.is_empty());
+        assert!(deque.as_mut_slices().0.is_empty());
+        assert_eq!(deque.partition_point(|_| true), 0);
+        assert_eq!(
+            deque.binary_search_by(|_| core::cmp::Ordering::Less),
+            Err(0)
+        );
+        deque.sort_by(|_, _| core::cmp::Ordering::Equal);
+        deque.sort_unstable_by(|_, _| core::cmp::Ordering::Equal);
+        deque.truncate(0);
+        deque.truncate_front(0);
+        deque.clear();
+        assert_eq!(format!("{:?}", ArrayDeque::<i32>::new(0)), "[]");
+
+        let mut ints: ArrayDeque<i32> = ArrayDeque::new(0);
+        ints.push_back(1);
+        assert!(!ints.contains(&1));
+        assert_eq!(ints.index_of(&1), None);
+        assert_eq!(ints.binary_search(&1), Err(0));
+        ints.sort();
+        assert_eq!(ints.clone(), ints);
+        assert_eq!(ints.clone().capacity(), 0);
+        assert_eq!(ints.into_iter().next(), None);
     }
 
     #[test]
-    fn extend() {
-        let mut deque = ArrayDeque::new(5);
-        deque.extend(vec![1, 2, 3]);
+    fn from_vecdeque_preserves_order_and_len() {
+        let vec_deque: VecDeque<_> = [1, 2, 3].into_iter().collect();
+        let deque = ArrayDeque::from(vec_deque);
+
         assert_eq!(deque.len(), 3);
         assert_eq!(deque[0], 1);
         assert_eq!(deque[1], 2);
@@ -841,187 +7583,373 @@ mod tests {
     }
 
     #[test]
-    fn into_iter() {
-        let mut deque = ArrayDeque::new(5);
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        let mut iter = deque.into_iter();
-        assert_eq!(iter.next(), Some(1));
-        assert_eq!(iter.next(), Some(2));
-        assert_eq!(iter.next(), Some(3));
-        assert_eq!(iter.next(), None);
+    fn from_wrapped_vecdeque_round_trip() {
+        let mut vec_deque = VecDeque::with_capacity(4);
+        vec_deque.extend([0, 0, 0, 1]);
+        for _ in 0..3 {
+            vec_deque.pop_front();
+        }
+        vec_deque.extend([2, 3, 4]);
+        assert!(!vec_deque.as_slices().1.is_empty());
+
+        let deque = ArrayDeque::from(vec_deque);
+        assert_eq!(deque.capacity(), 4);
+        assert_eq!(contents(&deque), vec![1, 2, 3, 4]);
+
+        let back = VecDeque::from(deque);
+        assert_eq!(back, [1, 2, 3, 4]);
     }
 
     #[test]
-    fn into_iter_empty() {
-        let deque: ArrayDeque<i32> = ArrayDeque::new(5);
-        let mut iter = deque.into_iter();
-        assert_eq!(iter.next(), None);
+    fn into_vec_wrapped_reuses_buffer() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        assert!(!deque.as_slices().1.is_empty());
+        let buffer = deque.ptr.as_ptr();
+
+        let vec = deque.into_vec();
+        assert_eq!(vec, [2, 3, 4, 5]);
+        assert_eq!(vec.capacity(), 4);
+        assert_eq!(vec.as_ptr(), buffer as *const i32);
     }
 
     #[test]
-    fn into_iter_full() {
+    fn into_vec_partial_keeps_capacity() {
+        let mut deque = ArrayDeque::new(5);
+        deque.extend([1, 2, 3, 4]);
+        deque.pop_front();
+        deque.pop_front();
+
+        let vec: Vec<_> = deque.into();
+        assert_eq!(vec, [3, 4]);
+        assert_eq!(vec.capacity(), 5);
+
+        let mut wrapped = ArrayDeque::new(5);
+        wrapped.extend([1, 2, 3, 4, 5]);
+        wrapped.pop_front();
+        wrapped.pop_front();
+        wrapped.push_back(6);
+        assert_eq!(wrapped.into_vec(), [3, 4, 5, 6]);
+
+        assert!(ArrayDeque::<i32>::new(0).into_vec().is_empty());
+        let mut unit = ArrayDeque::new(3);
+        unit.extend([(), (), (), ()]);
+        assert_eq!(unit.into_vec().len(), 3);
+    }
+
+    #[test]
+    fn into_vec_drops_each_element_once() {
+        let drops = Arc::new(AtomicUsize::new(0));
         let mut deque = ArrayDeque::new(3);
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        let mut iter = deque.into_iter();
-        assert_eq!(iter.next(), Some(1));
-        assert_eq!(iter.next(), Some(2));
-        assert_eq!(iter.next(), Some(3));
-        assert_eq!(iter.next(), None);
+        for _ in 0..5 {
+            deque.push_back(DropCounter::new(drops.clone()));
+        }
+        let vec = deque.into_vec();
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+        drop(vec);
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
     }
 
     #[test]
-    fn into_iter_partial() {
-        let mut deque = ArrayDeque::new(5);
-        deque.push_back(1);
-        deque.push_back(2);
-        let mut iter = deque.into_iter();
-        assert_eq!(iter.next(), Some(1));
-        assert_eq!(iter.next(), Some(2));
-        assert_eq!(iter.next(), None);
+    fn into_vecdeque_from_wrapped_deque() {
+        let mut deque = ArrayDeque::new(4);
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        assert!(!deque.as_slices().1.is_empty());
+
+        let vec_deque = VecDeque::from(deque);
+        assert_eq!(vec_deque, [2, 3, 4, 5]);
+        assert_eq!(ArrayDeque::from(vec_deque), [2, 3, 4, 5]);
     }
 
     #[test]
-    fn iter_empty() {
-        let deque: ArrayDeque<i32> = ArrayDeque::new(5);
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), None);
+    fn vecdeque_conversions_drop_each_element_once() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque = ArrayDeque::new(3);
+        for _ in 0..5 {
+            deque.push_back(DropCounter::new(drops.clone()));
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+        let vec_deque = VecDeque::from(deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+        let deque = ArrayDeque::from(vec_deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+        drop(deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
     }
 
     #[test]
-    fn iter_full() {
+    fn into_vecdeque_preserves_order() {
         let mut deque = ArrayDeque::new(3);
         deque.push_back(1);
         deque.push_back(2);
         deque.push_back(3);
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), Some(&3));
-        assert_eq!(iter.next(), None);
+
+        let vec_deque: VecDeque<_> = VecDeque::from(deque);
+        assert_eq!(vec_deque.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
     }
 
     #[test]
-    fn iter_partial() {
-        let mut deque = ArrayDeque::new(5);
+    #[cfg(feature = "serde")]
+    fn serde_serialize() {
+        let mut deque = ArrayDeque::new(3);
         deque.push_back(1);
         deque.push_back(2);
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), None);
+        let serialized = serde_json::to_string(&deque).unwrap();
+        assert_eq!(serialized, "[1,2]");
     }
 
     #[test]
-    fn is_empty() {
-        let deque: ArrayDeque<i32> = ArrayDeque::new(5);
+    #[cfg(feature = "serde")]
+    fn serde_deserialize_length_prefixed() {
+        let bytes = postcard::to_allocvec(&vec![1u32, 2, 3, 4]).unwrap();
+        let deque: ArrayDeque<u32> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(deque, [1, 2, 3, 4]);
+        assert_eq!(deque.capacity(), 4);
+
+        let deque: ArrayDeque<u32> = postcard::from_bytes(&[0]).unwrap();
         assert!(deque.is_empty());
-        assert_eq!(deque.len(), 0);
+        assert_eq!(deque.capacity(), 0);
     }
 
     #[test]
-    fn is_full() {
-        let mut deque = ArrayDeque::new(3);
-        assert!(!deque.is_full());
-        deque.push_back(1);
-        deque.push_back(2);
-        assert!(!deque.is_full());
-        deque.push_back(3);
-        assert!(deque.is_full());
+    #[cfg(feature = "serde")]
+    fn serde_deserialize_caps_preallocation() {
+        use serde::de::value::{Error, SeqDeserializer};
+
+        /// Yields one element while claiming there are 2^40 of them.
+        struct Lying(Option<u64>);
+
+        impl Iterator for Lying {
+            type Item = u64;
+
+            fn next(&mut self) -> Option<u64> {
+                self.0.take()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (1 << 40, Some(1 << 40))
+            }
+        }
+
+        let deque =
+            ArrayDeque::<u64>::deserialize(SeqDeserializer::<_, Error>::new(Lying(Some(7))))
+                .unwrap();
+        assert_eq!(deque, [7]);
+        assert_eq!(deque.capacity(), 1);
+
+        // A 2^30 length prefix followed by a single element.
+        let bytes = [0x80, 0x80, 0x80, 0x80, 0x04, 0x01];
+        let err = postcard::from_bytes::<ArrayDeque<u64>>(&bytes).unwrap_err();
+        assert_eq!(err, postcard::Error::DeserializeUnexpectedEnd);
+
+        // Sequences longer than the capped preallocation still round-trip.
+        let items: Vec<u64> = (0..200_000).collect();
+        let bytes = postcard::to_allocvec(&items).unwrap();
+        let deque: ArrayDeque<u64> = postcard::from_bytes(&bytes).unwrap();
+        assert!(deque.iter().eq(&items));
+        assert_eq!(deque.capacity(), items.len());
     }
 
     #[test]
-    fn clear_empty() {
-        let mut deque = ArrayDeque::<()>::new(3);
-        deque.clear();
-        assert!(deque.is_empty());
-        assert_eq!(deque.len(), 0);
+    #[cfg(feature = "serde")]
+    fn serde_seed_rejecting_longer_sequences() {
+        for (items, expected) in [
+            (vec![1u32, 2], Some(vec![1, 2])),
+            (vec![1, 2, 3], Some(vec![1, 2, 3])),
+            (vec![1, 2, 3, 4], None),
+        ] {
+            let json = serde_json::to_string(&items).unwrap();
+            let mut de = serde_json::Deserializer::from_str(&json);
+            let from_json = ArrayDequeSeed::<u32>::with_capacity(3).deserialize(&mut de);
+
+            let bytes = postcard::to_allocvec(&items).unwrap();
+            let mut de = postcard::Deserializer::from_bytes(&bytes);
+            let from_postcard = ArrayDequeSeed::<u32>::with_capacity(3).deserialize(&mut de);
+
+            match expected {
+                Some(expected) => {
+                    for deque in [from_json.unwrap(), from_postcard.unwrap()] {
+                        assert_eq!(deque.capacity(), 3);
+                        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), expected);
+                    }
+                }
+                None => {
+                    let err = from_json.unwrap_err();
+                    assert!(format!("{err}").contains("at most 3 elements"), "{err}");
+                    assert!(from_postcard.is_err());
+                }
+            }
+        }
     }
 
     #[test]
-    fn clear_non_empty() {
-        let mut deque = ArrayDeque::new(3);
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.clear();
-        assert!(deque.is_empty());
-        assert_eq!(deque.len(), 0);
+    #[cfg(feature = "serde")]
+    fn serde_seed_overwriting_keeps_last() {
+        for (items, expected) in [
+            (vec![1u32, 2], vec![1, 2]),
+            (vec![1, 2, 3], vec![1, 2, 3]),
+            (vec![1, 2, 3, 4, 5], vec![3, 4, 5]),
+        ] {
+            let json = serde_json::to_string(&items).unwrap();
+            let mut de = serde_json::Deserializer::from_str(&json);
+            let from_json = ArrayDequeSeed::<u32>::overwriting(3)
+                .deserialize(&mut de)
+                .unwrap();
+
+            let bytes = postcard::to_allocvec(&items).unwrap();
+            let mut de = postcard::Deserializer::from_bytes(&bytes);
+            let from_postcard = ArrayDequeSeed::<u32>::overwriting(3)
+                .deserialize(&mut de)
+                .unwrap();
+
+            for deque in [from_json, from_postcard] {
+                assert_eq!(deque.capacity(), 3);
+                assert_eq!(deque.iter().copied().collect::<Vec<_>>(), expected);
+            }
+        }
     }
 
     #[test]
-    fn push_back_overwrite_drops_replaced_element() {
-        let drops = Arc::new(AtomicUsize::new(0));
-        {
-            let mut deque = ArrayDeque::new(2);
-            deque.push_back(DropCounter::new(drops.clone()));
-            deque.push_back(DropCounter::new(drops.clone()));
-            deque.push_back(DropCounter::new(drops.clone()));
+    #[cfg(feature = "bincode")]
+    fn bincode_round_trip_rotated() {
+        let config = bincode::config::standard();
+        let mut deque = ArrayDeque::new(4);
+        deque.extend([1u32, 2, 3, 4, 5, 6]);
+        deque.pop_back();
+        let bytes = bincode::encode_to_vec(&deque, config).unwrap();
+        assert_eq!(
+            bytes,
+            bincode::encode_to_vec([3u32, 4, 5].as_slice(), config).unwrap()
+        );
 
-            assert_eq!(drops.load(Ordering::SeqCst), 1);
-        }
+        let (decoded, read): (ArrayDeque<u32>, _) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(read, bytes.len());
+        assert_eq!(decoded, [3, 4, 5]);
+        assert_eq!(decoded.capacity(), 3);
+    }
 
-        assert_eq!(drops.load(Ordering::SeqCst), 3);
+    #[test]
+    #[cfg(feature = "bincode")]
+    fn bincode_borrow_decode() {
+        let config = bincode::config::standard();
+        let deque = ArrayDeque::from(["ab", "cd"]);
+        let bytes = bincode::encode_to_vec(&deque, config).unwrap();
+        let (decoded, _): (ArrayDeque<&str>, _) =
+            bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, ["ab", "cd"]);
     }
 
     #[test]
-    fn into_iter_partial_consumption_no_double_drop() {
-        let drops = Arc::new(AtomicUsize::new(0));
+    #[cfg(feature = "bincode")]
+    fn bincode_limit_rejects_huge_length() {
+        let config = bincode::config::standard().with_limit::<64>();
+        let bytes = bincode::encode_to_vec(u64::MAX >> 8, config).unwrap();
+        let result = bincode::decode_from_slice::<ArrayDeque<u64>, _>(&bytes, config);
+        assert!(matches!(
+            result,
+            Err(bincode::error::DecodeError::LimitExceeded)
+        ));
+    }
 
-        {
-            let mut deque = ArrayDeque::new(2);
-            deque.push_back(DropCounter::new(drops.clone()));
-            deque.push_back(DropCounter::new(drops.clone()));
+    /// Reads every slot of the buffer, whether or not it holds an element.
+    ///
+    /// Only meaningful once every slot has been written at least once.
+    #[cfg(feature = "zeroize")]
+    fn raw_slots<T: Copy>(deque: &ArrayDeque<T>) -> Vec<T> {
+        (0..deque.cap)
+            .map(|i| unsafe { deque.ptr.as_ptr().add(i).read() })
+            .collect()
+    }
 
-            let mut iter = deque.into_iter();
-            let _first = iter.next();
-            assert_eq!(drops.load(Ordering::SeqCst), 0);
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize_spare_capacity_clears_popped_slots() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 1..=6u64 {
+            deque.push_back(i);
         }
+        assert_eq!(deque.pop_front(), Some(3));
+        assert_eq!(deque.pop_back(), Some(6));
+        assert_eq!(raw_slots(&deque), [5, 6, 3, 4]);
 
-        assert_eq!(drops.load(Ordering::SeqCst), 2);
+        deque.zeroize_spare_capacity();
+        assert_eq!(raw_slots(&deque), [5, 0, 0, 4]);
+        assert_eq!(deque, [4, 5]);
     }
 
     #[test]
-    fn from_empty_slice_is_empty_with_min_capacity() {
-        let slice: &[i32] = &[];
-        let deque = ArrayDeque::from(slice);
-
+    #[cfg(feature = "zeroize")]
+    fn zeroize_wipes_elements_and_stale_slots() {
+        let mut deque = ArrayDeque::new(3);
+        deque.extend([[1u8; 4], [2; 4], [3; 4], [4; 4]]);
+        deque.pop_front();
+        deque.zeroize();
         assert!(deque.is_empty());
-        assert_eq!(deque.capacity(), 1);
+        assert_eq!(raw_slots(&deque), [[0; 4]; 3]);
+
+        deque.push_back([5; 4]);
+        assert_eq!(deque, [[5; 4]]);
     }
 
     #[test]
-    fn from_vecdeque_preserves_order_and_len() {
-        let vec_deque: VecDeque<_> = [1, 2, 3].into_iter().collect();
-        let deque = ArrayDeque::from(vec_deque);
+    #[cfg(feature = "defmt")]
+    fn defmt_format_writes_elements_front_to_back() {
+        let mut deque = ArrayDeque::new(3);
+        for value in [0xdead_0001u32, 0xdead_0002, 0xdead_0003, 0xdead_0004] {
+            deque.push_back(value);
+        }
+        defmt::export::fetch_bytes();
+        defmt::write!(defmt::export::make_formatter(), "{}", deque);
+        let bytes = defmt::export::fetch_bytes();
 
-        assert_eq!(deque.len(), 3);
-        assert_eq!(deque[0], 1);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+        let mut rest = &bytes[..];
+        for value in [0xdead_0002u32, 0xdead_0003, 0xdead_0004] {
+            let needle = value.to_le_bytes();
+            let at = rest.windows(4).position(|w| w == needle).unwrap();
+            rest = &rest[at + 4..];
+        }
+        assert!(!rest.windows(4).any(|w| w == 0xdead_0001u32.to_le_bytes()));
     }
 
-    #[test]
-    fn into_vecdeque_preserves_order() {
-        let mut deque = ArrayDeque::new(3);
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
+    #[cfg(feature = "serde")]
+    static FAILING_DROPS: AtomicUsize = AtomicUsize::new(0);
 
-        let vec_deque: VecDeque<_> = VecDeque::from(deque);
-        assert_eq!(vec_deque.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// Deserializes from a `u32`, failing on 99 and counting drops.
+    #[cfg(feature = "serde")]
+    struct FailsOn99;
+
+    #[cfg(feature = "serde")]
+    impl<'de> Deserialize<'de> for FailsOn99 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            match u32::deserialize(deserializer)? {
+                99 => Err(de::Error::custom("got 99")),
+                _ => Ok(FailsOn99),
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl Drop for FailsOn99 {
+        fn drop(&mut self) {
+            FAILING_DROPS.fetch_add(1, Ordering::SeqCst);
+        }
     }
 
     #[test]
     #[cfg(feature = "serde")]
-    fn serde_serialize() {
-        let mut deque = ArrayDeque::new(3);
-        deque.push_back(1);
-        deque.push_back(2);
-        let serialized = serde_json::to_string(&deque).unwrap();
-        assert_eq!(serialized, "[1,2]");
+    fn serde_deserialize_error_drops_partial_deque() {
+        let bytes = postcard::to_allocvec(&vec![1u32, 2, 99, 4]).unwrap();
+        assert!(postcard::from_bytes::<ArrayDeque<FailsOn99>>(&bytes).is_err());
+        assert_eq!(FAILING_DROPS.load(Ordering::SeqCst), 2);
+
+        assert!(serde_json::from_str::<ArrayDeque<FailsOn99>>("[1,2,3,99]").is_err());
+        assert_eq!(FAILING_DROPS.load(Ordering::SeqCst), 5);
     }
 
     #[test]
@@ -0,0 +1,183 @@
+use crate::ArrayDeque;
+use allocator_api2::alloc::{Allocator, Global};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::io;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// An in-memory, bounded byte pipe implementing tokio's [`AsyncRead`] and
+/// [`AsyncWrite`] on top of a borrowed `ArrayDeque<u8>`.
+///
+/// Writes append to the back of the deque and reads pop from the front. The
+/// adapter never returns [`Poll::Pending`], since nothing would wake the task
+/// once data or space became available. Instead:
+///
+/// - reading from an empty deque returns `Ok(0)`, which readers treat as end
+///   of stream;
+/// - writing to a full deque fails with
+///   [`WouldBlock`](io::ErrorKind::WouldBlock), and a partial write reports
+///   how many bytes fit. Buffered bytes are never overwritten.
+///
+/// This makes it a convenient stand-in for a socket when testing codecs:
+/// write everything first, then read it back.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::{ArrayDeque, AsyncRing};
+/// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+///
+/// # futures::executor::block_on(async {
+/// let mut deque = ArrayDeque::new(16);
+/// let mut pipe = AsyncRing::new(&mut deque);
+/// pipe.write_all(b"ping").await.unwrap();
+///
+/// let mut received = Vec::new();
+/// pipe.read_to_end(&mut received).await.unwrap();
+/// assert_eq!(received, b"ping");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncRing<'a, A: Allocator = Global> {
+    /// The deque backing the pipe
+    deque: &'a mut ArrayDeque<u8, A>,
+}
+
+impl<'a, A: Allocator> AsyncRing<'a, A> {
+    /// Wraps `deque` so it can be driven through tokio's async I/O traits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{ArrayDeque, AsyncRing};
+    ///
+    /// let mut deque = ArrayDeque::new(8);
+    /// let pipe = AsyncRing::new(&mut deque);
+    /// assert!(pipe.get_ref().is_empty());
+    /// ```
+    pub fn new(deque: &'a mut ArrayDeque<u8, A>) -> Self {
+        Self { deque }
+    }
+
+    /// Returns a shared reference to the underlying deque.
+    pub fn get_ref(&self) -> &ArrayDeque<u8, A> {
+        self.deque
+    }
+
+    /// Returns a mutable reference to the underlying deque.
+    pub fn get_mut(&mut self) -> &mut ArrayDeque<u8, A> {
+        self.deque
+    }
+}
+
+impl<A: Allocator> AsyncRead for AsyncRing<'_, A> {
+    /// Moves as many bytes as fit into `buf` from the front of the deque.
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let deque = &mut *self.get_mut().deque;
+        let n = buf.remaining().min(deque.len());
+        let n = deque.pop_bytes(buf.initialize_unfilled_to(n));
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<A: Allocator> AsyncWrite for AsyncRing<'_, A> {
+    /// Appends as much of `buf` as fits, failing with `WouldBlock` if the
+    /// deque is already full.
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let deque = &mut *self.get_mut().deque;
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let n = buf.len().min(deque.capacity() - deque.len());
+        if n == 0 {
+            return Poll::Ready(Err(io::ErrorKind::WouldBlock.into()));
+        }
+        deque.extend_from_copy_slice(&buf[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    /// Does nothing, as there is nothing to flush.
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// Does nothing; the deque stays readable after shutdown.
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::{SinkExt, StreamExt};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_util::codec::{FramedRead, FramedWrite, LinesCodec};
+
+    #[test]
+    fn read_empty_is_eof() {
+        let mut deque = ArrayDeque::new(4);
+        let mut pipe = AsyncRing::new(&mut deque);
+        let mut buf = [0; 4];
+        assert_eq!(block_on(pipe.read(&mut buf)).unwrap(), 0);
+    }
+
+    #[test]
+    fn read_straddles_wrap_point() {
+        let mut deque = ArrayDeque::new(5);
+        for byte in *b"xxxabcd" {
+            deque.push_back(byte);
+        }
+        deque.truncate_front(4);
+        let mut pipe = AsyncRing::new(&mut deque);
+        let mut buf = [0; 3];
+        assert_eq!(block_on(pipe.read(&mut buf)).unwrap(), 3);
+        assert_eq!(&buf, b"abc");
+        assert_eq!(*pipe.get_ref(), *b"d");
+    }
+
+    #[test]
+    fn write_full_would_block() {
+        let mut deque = ArrayDeque::new(4);
+        let mut pipe = AsyncRing::new(&mut deque);
+        assert_eq!(block_on(pipe.write(b"abcdef")).unwrap(), 4);
+        let err = block_on(pipe.write(b"g")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(block_on(pipe.write(b"")).unwrap(), 0);
+        block_on(pipe.shutdown()).unwrap();
+        assert_eq!(deque, *b"abcd");
+    }
+
+    #[test]
+    fn codec_round_trip() {
+        let mut deque = ArrayDeque::new(64);
+        deque.extend_from_slice(&[b'x'; 50]);
+        deque.truncate_front(0);
+
+        block_on(async {
+            let mut sink = FramedWrite::new(AsyncRing::new(&mut deque), LinesCodec::new());
+            sink.send("hello").await.unwrap();
+            sink.send("wrapped world").await.unwrap();
+        });
+        assert_eq!(deque.len(), 20);
+        assert!(!deque.as_slices().1.is_empty());
+
+        let lines: Vec<String> = block_on(
+            FramedRead::new(AsyncRing::new(&mut deque), LinesCodec::new())
+                .map(Result::unwrap)
+                .collect(),
+        );
+        assert_eq!(lines, ["hello", "wrapped world"]);
+        assert!(deque.is_empty());
+    }
+}
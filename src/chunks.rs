@@ -0,0 +1,306 @@
+use crate::sub_slices;
+use core::fmt;
+use core::iter::FusedIterator;
+
+/// An iterator over non-overlapping chunks of a deque, front to back.
+///
+/// Each chunk holds `size` elements, except the last one, which may be
+/// shorter. Like a window from [`Windows`](crate::Windows), a chunk is a pair
+/// of slices: the first slice followed by the second. The second slice is
+/// empty unless the chunk straddles the point where the deque wraps around
+/// its buffer.
+///
+/// Returned by [`ArrayDeque::chunks`](crate::ArrayDeque::chunks) and
+/// [`StackArrayDeque::chunks`](crate::StackArrayDeque::chunks).
+pub struct Chunks<'a, T> {
+    /// Contents of the deque, as returned by `as_slices`
+    slices: (&'a [T], &'a [T]),
+    /// Maximum number of elements in each chunk
+    size: usize,
+    /// Start of the next chunk from the front
+    start: usize,
+    /// End of the next chunk from the back
+    end: usize,
+}
+
+impl<'a, T> Chunks<'a, T> {
+    /// Creates an iterator over the chunks of `size` elements of the deque
+    /// whose contents are `front` followed by `back`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub(crate) fn new(front: &'a [T], back: &'a [T], size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks {
+            slices: (front, back),
+            size,
+            start: 0,
+            end: front.len() + back.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = (&'a [T], &'a [T]);
+
+    /// Returns the next chunk from the front.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+        let start = self.start;
+        self.start += self.size.min(self.end - start);
+        Some(sub_slices(self.slices, start, self.start))
+    }
+
+    /// Returns the exact number of remaining chunks.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end - self.start).div_ceil(self.size);
+        (remaining, Some(remaining))
+    }
+
+    /// Skips `n` chunks in O(1) and returns the next one.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.start = n
+            .checked_mul(self.size)
+            .and_then(|skip| skip.checked_add(self.start))
+            .map_or(self.end, |start| start.min(self.end));
+        self.next()
+    }
+
+    /// Returns the number of remaining chunks in O(1).
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    /// Returns the last chunk in O(1).
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<T> DoubleEndedIterator for Chunks<'_, T> {
+    /// Returns the next chunk from the back, which is the shorter one if the
+    /// length is not a multiple of the chunk size.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+        let end = self.end;
+        let last = match (end - self.start) % self.size {
+            0 => self.size,
+            rem => rem,
+        };
+        self.end -= last;
+        Some(sub_slices(self.slices, self.end, end))
+    }
+}
+
+impl<T> ExactSizeIterator for Chunks<'_, T> {}
+
+impl<T> FusedIterator for Chunks<'_, T> {}
+
+impl<T> Clone for Chunks<'_, T> {
+    /// Creates a copy of the iterator at the same position.
+    fn clone(&self) -> Self {
+        Chunks {
+            slices: self.slices,
+            size: self.size,
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Chunks<'_, T> {
+    /// Formats the chunk size and the underlying pair of slices.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chunks")
+            .field("slices", &self.slices)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+/// An iterator over non-overlapping chunks of exactly `size` elements of a
+/// deque, front to back.
+///
+/// Elements left over at the back, fewer than `size`, are not yielded but can
+/// be retrieved with [`remainder`](Self::remainder). Chunks are pairs of
+/// slices, as with [`Chunks`].
+///
+/// Returned by [`ArrayDeque::chunks_exact`](crate::ArrayDeque::chunks_exact)
+/// and [`StackArrayDeque::chunks_exact`](crate::StackArrayDeque::chunks_exact).
+pub struct ChunksExact<'a, T> {
+    /// The underlying chunks, covering only the whole ones
+    chunks: Chunks<'a, T>,
+    /// Elements left over after the last whole chunk
+    remainder: (&'a [T], &'a [T]),
+}
+
+impl<'a, T> ChunksExact<'a, T> {
+    /// Creates an iterator over the whole chunks of `size` elements of the
+    /// deque whose contents are `front` followed by `back`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub(crate) fn new(front: &'a [T], back: &'a [T], size: usize) -> Self {
+        let mut chunks = Chunks::new(front, back, size);
+        let len = chunks.end;
+        chunks.end = len - len % size;
+        ChunksExact {
+            remainder: sub_slices(chunks.slices, chunks.end, len),
+            chunks,
+        }
+    }
+
+    /// Returns the elements at the back that do not fill a whole chunk, as a
+    /// pair of slices.
+    pub fn remainder(&self) -> (&'a [T], &'a [T]) {
+        self.remainder
+    }
+}
+
+impl<'a, T> Iterator for ChunksExact<'a, T> {
+    type Item = (&'a [T], &'a [T]);
+
+    /// Returns the next chunk from the front.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next()
+    }
+
+    /// Returns the exact number of remaining chunks.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    /// Skips `n` chunks in O(1) and returns the next one.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.chunks.nth(n)
+    }
+
+    /// Returns the number of remaining chunks in O(1).
+    fn count(self) -> usize {
+        self.chunks.count()
+    }
+
+    /// Returns the last whole chunk in O(1).
+    fn last(self) -> Option<Self::Item> {
+        self.chunks.last()
+    }
+}
+
+impl<T> DoubleEndedIterator for ChunksExact<'_, T> {
+    /// Returns the next chunk from the back.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for ChunksExact<'_, T> {}
+
+impl<T> FusedIterator for ChunksExact<'_, T> {}
+
+impl<T> Clone for ChunksExact<'_, T> {
+    /// Creates a copy of the iterator at the same position.
+    fn clone(&self) -> Self {
+        ChunksExact {
+            chunks: self.chunks.clone(),
+            remainder: self.remainder,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ChunksExact<'_, T> {
+    /// Formats the chunk size, the underlying pair of slices and the remainder.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunksExact")
+            .field("slices", &self.chunks.slices)
+            .field("size", &self.chunks.size)
+            .field("remainder", &self.remainder)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::StackArrayDeque;
+
+    /// Returns a full deque of `4..11` whose front sits at slot 4, so its
+    /// contents are split into `[4, 5, 6]` and `[7, 8, 9, 10]`.
+    fn wrapped() -> StackArrayDeque<i32, 7> {
+        let mut deque = StackArrayDeque::new();
+        for i in 0..11 {
+            deque.push_back(i);
+        }
+        deque
+    }
+
+    #[test]
+    fn chunks_straddle_wrap_point() {
+        let deque = wrapped();
+        assert_eq!(deque.as_slices(), (&[4, 5, 6][..], &[7, 8, 9, 10][..]));
+        let mut chunks = deque.chunks(2);
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks.next(), Some((&[4, 5][..], &[][..])));
+        assert_eq!(chunks.next(), Some((&[6][..], &[7][..])));
+        assert_eq!(chunks.next(), Some((&[8, 9][..], &[][..])));
+        assert_eq!(chunks.next(), Some((&[10][..], &[][..])));
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn chunks_from_both_ends() {
+        let deque = wrapped();
+        let mut chunks = deque.chunks(3);
+        assert_eq!(chunks.next_back(), Some((&[10][..], &[][..])));
+        assert_eq!(chunks.next_back(), Some((&[7, 8, 9][..], &[][..])));
+        assert_eq!(chunks.clone().last(), Some((&[4, 5, 6][..], &[][..])));
+        assert_eq!(chunks.nth(1), None);
+        assert_eq!(chunks.next_back(), None);
+
+        let mut chunks = deque.chunks(4);
+        assert_eq!(chunks.nth(1), Some((&[8, 9, 10][..], &[][..])));
+        assert_eq!(chunks.len(), 0);
+    }
+
+    #[test]
+    fn chunks_larger_than_deque() {
+        let deque = wrapped();
+        let mut chunks = deque.chunks(10);
+        assert_eq!(chunks.next(), Some((&[4, 5, 6][..], &[7, 8, 9, 10][..])));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(StackArrayDeque::<i32, 3>::new().chunks(2).count(), 0);
+    }
+
+    #[test]
+    fn chunks_exact_skip_remainder() {
+        let deque = wrapped();
+        let mut chunks = deque.chunks_exact(2);
+        assert_eq!(chunks.remainder(), (&[10][..], &[][..]));
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.next_back(), Some((&[8, 9][..], &[][..])));
+        assert_eq!(chunks.next(), Some((&[4, 5][..], &[][..])));
+        assert_eq!(chunks.next(), Some((&[6][..], &[7][..])));
+        assert_eq!(chunks.next(), None);
+
+        let chunks = deque.chunks_exact(5);
+        assert_eq!(chunks.remainder(), (&[9, 10][..], &[][..]));
+        assert!(chunks.eq([(&[4, 5, 6][..], &[7, 8][..])]));
+        assert_eq!(deque.chunks_exact(8).remainder(), deque.as_slices());
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_of_zero_panics() {
+        wrapped().chunks(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_exact_of_zero_panics() {
+        wrapped().chunks_exact(0);
+    }
+}
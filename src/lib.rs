@@ -1,4 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "unstable", feature(allocator_api, dropck_eyepatch))]
 #![deny(missing_docs)]
 //! A fixed-capacity circular buffer (ring buffer) implementation.
 //!
@@ -10,6 +11,7 @@
 //! # Examples
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! use array_deque::ArrayDeque;
 //!
 //! let mut deque = ArrayDeque::new(3);
@@ -19,19 +21,107 @@
 //!
 //! assert_eq!(deque.pop_front(), Some(1));
 //! assert_eq!(deque.pop_back(), Some(3));
+//! # }
 //! ```
 //!
 //! # Features
 //!
+//! - **std** (default): Implement `std::error::Error` for the error types and
+//!   the `std::io` traits for `ArrayDeque<u8>`. Implies `alloc`.
+//! - **alloc**: Provide the heap-allocated [`ArrayDeque`]. Without it the crate
+//!   only offers [`StackArrayDeque`] and needs no heap allocator at all, so
+//!   `default-features = false` builds on bare-metal targets.
+//! - **arbitrary**: Implement `arbitrary::Arbitrary` for both deque types, for
+//!   structure-aware fuzzing. Generated deques are often wrapped.
+//! - **quickcheck**: Implement `quickcheck::Arbitrary` for both deque types,
+//!   generating wrapped layouts and shrinking toward smaller deques. Implies
+//!   `std`.
 //! - **serde**: Enable serialization and deserialization support with serde.
+//!   See [`serde_with_capacity`] for a representation that keeps the capacity,
+//!   and [`ArrayDequeSeed`] for choosing the capacity when deserializing.
+//! - **bincode**: Implement bincode 2's `Encode`, `Decode` and `BorrowDecode`
+//!   for both deque types, as a length followed by the elements front to back.
+//! - **bytes**: Implement `bytes::Buf` and `bytes::BufMut` for `ArrayDeque<u8>`.
+//!   Implies `alloc`.
+//! - **tokio**: Provide [`AsyncRing`], an in-memory `AsyncRead`/`AsyncWrite`
+//!   pipe over an `ArrayDeque<u8>`. Implies `std`.
+//! - **futures**: Provide [`DequeStream`] and [`DequeSink`], which let an
+//!   `ArrayDeque` act as a bounded in-memory channel within a single task.
+//!   Implies `alloc`.
+//! - **spsc**: Add `ArrayDeque::split`, which turns a deque into a lock-free
+//!   single-producer single-consumer pair of [`Producer`] and [`Consumer`]
+//!   halves for passing elements between two threads. Implies `alloc`.
+//! - **defmt**: Implement `defmt::Format` for both deque types, printing the
+//!   elements front to back like `Debug`.
+//! - **critical-section**: Provide [`StaticRingBuffer`], a `StackArrayDeque`
+//!   with interior mutability guarded by the `critical-section` crate, so it
+//!   can live in a `static` shared between interrupt handlers and the main
+//!   loop.
+//! - **embedded-io**: Implement the `embedded-io` `Read`, `Write`, `ReadReady`
+//!   and `WriteReady` traits for byte deques, so they can act as software FIFOs
+//!   in driver pipelines.
+//! - **stats**: Count the elements pushed and evicted by each deque and the
+//!   largest length it reached, for tuning capacities. Adds 24 bytes (on
+//!   64-bit targets) to both deque types.
+//! - **zeroize**: Implement `zeroize::Zeroize` for both deque types, and add
+//!   `zeroize_spare_capacity` to scrub slots vacated by pops and moves.
+//! - **unstable**: Nightly-only. Applies the dropck eyepatch (`#[may_dangle]`) to
+//!   `ArrayDeque`, so deques of borrowed data can be dropped after the data
+//!   they borrow, as with `Vec`. Also switches [`Allocator`] to the standard
+//!   library's `core::alloc::Allocator` trait. Implies `alloc`.
+//!
+//! # Custom allocators
+//!
+//! `ArrayDeque<T, A>` takes an allocator parameter defaulting to [`Global`].
+//! The [`Allocator`] trait comes from the `allocator-api2` crate, which mirrors
+//! the unstable standard library API on stable Rust.
 
+#[cfg(feature = "alloc")]
+use core::alloc::Layout;
 use core::fmt;
+use core::ops::{Bound, Range, RangeBounds};
 
+#[cfg(feature = "alloc")]
 mod array_deque;
+#[cfg(feature = "tokio")]
+mod async_ring;
+mod chunks;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod serde_with_capacity;
+#[cfg(feature = "alloc")]
+mod sliding;
+#[cfg(feature = "spsc")]
+mod spsc;
 mod stack_array_deque;
+#[cfg(feature = "critical-section")]
+mod static_ring;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod view;
+mod windows;
 
-pub use array_deque::ArrayDeque;
-pub use stack_array_deque::StackArrayDeque;
+#[cfg(feature = "alloc")]
+pub use allocator_api2::alloc::{Allocator, Global};
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub use array_deque::ArrayDequeSeed;
+#[cfg(feature = "alloc")]
+pub use array_deque::{ArrayDeque, ArrayDequeIntoIter, ArrayDequeIter, CursorMut, ExtractIf};
+#[cfg(feature = "tokio")]
+pub use async_ring::AsyncRing;
+pub use chunks::{Chunks, ChunksExact};
+#[cfg(feature = "alloc")]
+pub use sliding::SlidingMinMax;
+#[cfg(feature = "spsc")]
+pub use spsc::{Consumer, Producer};
+pub use stack_array_deque::{StackArrayDeque, StackArrayDequeIntoIter, StackArrayDequeIter};
+#[cfg(feature = "critical-section")]
+pub use static_ring::StaticRingBuffer;
+#[cfg(feature = "futures")]
+pub use stream::{DequeSink, DequeStream};
+#[cfg(feature = "alloc")]
+pub use view::{ArrayDequeView, ArrayDequeViewMut};
+pub use windows::{ArrayWindows, Windows};
 
 /// Error returned when converting into a fixed-capacity deque would exceed capacity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,3 +144,281 @@ impl fmt::Display for CapacityError {
 
 #[cfg(feature = "std")]
 impl std::error::Error for CapacityError {}
+
+/// Error returned by `try_extend` when the deque fills up before the iterator
+/// is exhausted.
+///
+/// Nothing is lost: the element that did not fit and the unconsumed rest of
+/// the iterator are handed back.
+pub struct TryExtendError<I: Iterator> {
+    /// The first element that did not fit.
+    pub element: I::Item,
+    /// The rest of the iterator, positioned after `element`.
+    pub remaining: I,
+}
+
+impl<I: Iterator> fmt::Debug for TryExtendError<I>
+where
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryExtendError")
+            .field("element", &self.element)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I: Iterator> fmt::Display for TryExtendError<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deque is full")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Iterator> std::error::Error for TryExtendError<I> where I::Item: fmt::Debug {}
+
+/// Error returned by `get_many_mut` when the requested indices cannot be
+/// borrowed mutably at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GetManyMutError {
+    /// An index was greater than or equal to the length of the deque.
+    IndexOutOfBounds,
+    /// The same index was requested more than once.
+    OverlappingIndices,
+}
+
+impl fmt::Display for GetManyMutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GetManyMutError::IndexOutOfBounds => write!(f, "an index is out of bounds"),
+            GetManyMutError::OverlappingIndices => write!(f, "there were overlapping indices"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GetManyMutError {}
+
+/// Error returned by the `embedded_io::Write` impls when a non-empty buffer
+/// is written to a full deque.
+///
+/// Its [`kind`](embedded_io::Error::kind) is
+/// [`WriteZero`](embedded_io::ErrorKind::WriteZero), so `write_all` reports
+/// it instead of panicking.
+#[cfg(feature = "embedded-io")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FullError;
+
+#[cfg(feature = "embedded-io")]
+impl fmt::Display for FullError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deque is full")
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl embedded_io::Error for FullError {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::WriteZero
+    }
+}
+
+#[cfg(all(feature = "embedded-io", feature = "std"))]
+impl std::error::Error for FullError {}
+
+/// Error returned when allocating storage for an [`ArrayDeque`] fails.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds the maximum size of an allocation.
+    CapacityOverflow,
+    /// The allocator returned an error for the given layout.
+    AllocError {
+        /// Layout of the allocation that failed.
+        layout: Layout,
+    },
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => {
+                write!(f, "requested capacity exceeds the maximum allocation size")
+            }
+            TryReserveError::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryReserveError {}
+
+/// What an [`ArrayDeque`] does when an element is pushed while it is full.
+///
+/// The policy is chosen with [`ArrayDeque::with_policy`] or
+/// [`ArrayDeque::set_policy`] and applies to every operation that would
+/// otherwise overwrite an element: `push_back`, `push_front`, `insert`,
+/// `extend`, `extend_front`, `extend_from_slice` and the `std::io::Write` impl.
+/// The `try_*` methods never overwrite and the `_evict` methods always do,
+/// whatever the policy.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OverflowPolicy {
+    /// Evict the element at the opposite end to make room. This is the
+    /// default.
+    #[default]
+    OverwriteOldest,
+    /// Leave the deque untouched and drop the new element. The extend methods
+    /// stop taking items from the iterator once the deque is full and report
+    /// how many they pushed; the `try_*` methods hand the element back.
+    Reject,
+    /// Panic, to catch undersized buffers during development.
+    Panic,
+}
+
+/// Resolves `range` against a sequence of `len` elements.
+///
+/// # Panics
+///
+/// Panics with the same messages as slice indexing if the range is inverted
+/// or extends past `len`.
+fn resolve_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .expect("attempted to index slice from after maximum usize"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .expect("attempted to index slice up to maximum usize"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        if let Bound::Unbounded = range.end_bound() {
+            panic!("range start index {start} out of range for slice of length {len}");
+        }
+        panic!("slice index starts at {start} but ends at {end}");
+    }
+    if end > len {
+        panic!("range end index {end} out of range for slice of length {len}");
+    }
+    start..end
+}
+
+/// Returns the logical range `start..end` of the contents `front` followed by
+/// `back`, as a pair of slices whose second one is empty unless the range
+/// crosses from `front` into `back`.
+fn sub_slices<'a, T>(
+    (front, back): (&'a [T], &'a [T]),
+    start: usize,
+    end: usize,
+) -> (&'a [T], &'a [T]) {
+    if end <= front.len() {
+        (&front[start..end], &[])
+    } else if start >= front.len() {
+        (&back[start - front.len()..end - front.len()], &[])
+    } else {
+        (&front[start..], &back[..end - front.len()])
+    }
+}
+
+/// Mutable counterpart of [`sub_slices`].
+fn sub_slices_mut<'a, T>(
+    (front, back): (&'a mut [T], &'a mut [T]),
+    start: usize,
+    end: usize,
+) -> (&'a mut [T], &'a mut [T]) {
+    let front_len = front.len();
+    if end <= front_len {
+        (&mut front[start..end], &mut [])
+    } else if start >= front_len {
+        (&mut back[start - front_len..end - front_len], &mut [])
+    } else {
+        (&mut front[start..], &mut back[..end - front_len])
+    }
+}
+
+/// Checks that `indices` are all below `len` and pairwise distinct, so the
+/// elements they name can be borrowed mutably at the same time.
+fn check_disjoint<const K: usize>(indices: &[usize; K], len: usize) -> Result<(), GetManyMutError> {
+    for (i, &index) in indices.iter().enumerate() {
+        if index >= len {
+            return Err(GetManyMutError::IndexOutOfBounds);
+        }
+        if indices[..i].contains(&index) {
+            return Err(GetManyMutError::OverlappingIndices);
+        }
+    }
+    Ok(())
+}
+
+/// A linear congruential generator for randomized tests, so they are
+/// reproducible and need no extra dependency.
+#[cfg(test)]
+pub(crate) struct TestRng(u32);
+
+#[cfg(test)]
+impl TestRng {
+    /// Creates a generator starting from `seed`.
+    pub(crate) fn new(seed: u32) -> Self {
+        TestRng(seed)
+    }
+
+    /// Returns the next 16 pseudo-random bits.
+    pub(crate) fn next_u16(&mut self) -> u16 {
+        self.0 = self.0.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (self.0 >> 16) as u16
+    }
+
+    /// Returns a pseudo-random number below `bound`.
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        usize::from(self.next_u16()) % bound
+    }
+}
+
+/// Upper bound on the input bytes consumed by the header of an arbitrary
+/// deque: at most three `usize` draws, for the capacity (`ArrayDeque` only),
+/// the front position and the length.
+#[cfg(feature = "arbitrary")]
+const ARBITRARY_HEADER_BYTES: usize = 3 * size_of::<usize>();
+
+/// Running totals kept by both deque types when the `stats` feature is
+/// enabled.
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy)]
+struct Stats {
+    /// Number of elements stored by pushes, inserts, extends and writes
+    pushed: u64,
+    /// Number of elements pushed out to make room for new ones
+    evicted: u64,
+    /// Largest length reached since the last reset
+    max_len: usize,
+}
+
+#[cfg(feature = "stats")]
+impl Stats {
+    /// Returns zeroed counters.
+    const fn new() -> Self {
+        Stats {
+            pushed: 0,
+            evicted: 0,
+            max_len: 0,
+        }
+    }
+
+    /// Records that `pushed` elements were stored, pushing out `evicted` older
+    /// ones and leaving `len` elements in the deque.
+    #[inline]
+    fn record(&mut self, pushed: usize, evicted: usize, len: usize) {
+        self.pushed = self.pushed.wrapping_add(pushed as u64);
+        self.evicted = self.evicted.wrapping_add(evicted as u64);
+        self.max_len = self.max_len.max(len);
+    }
+}
@@ -0,0 +1,391 @@
+//! A serde representation of [`ArrayDeque`] that keeps its capacity.
+//!
+//! The default `Serialize`/`Deserialize` impls treat a deque as a plain
+//! sequence, so a half-full deque comes back with `capacity() == len()`. This
+//! module instead writes a struct with the capacity and the elements (front to
+//! back), e.g. `{"cap":4,"items":[1,2]}` in JSON, and restores both. Use it
+//! through `#[serde(with = "array_deque::serde_with_capacity")]`.
+//!
+//! Deserialization fails if there are more items than `cap`, or if the buffer
+//! cannot be allocated.
+//!
+//! # Untrusted input
+//!
+//! The buffer is allocated at the serialized `cap` before any item is read,
+//! so a few bytes of input can request an allocation of any size. When the
+//! input is untrusted, deserialize through a [`MaxCapacitySeed`] instead,
+//! which rejects a `cap` above a limit of your choosing before allocating.
+//!
+//! # Examples
+//!
+//! ```
+//! use array_deque::ArrayDeque;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Window {
+//!     #[serde(with = "array_deque::serde_with_capacity")]
+//!     samples: ArrayDeque<u32>,
+//! }
+//!
+//! let mut samples = ArrayDeque::new(4);
+//! samples.extend([1, 2]);
+//! let json = serde_json::to_string(&Window { samples }).unwrap();
+//! assert_eq!(json, r#"{"samples":{"cap":4,"items":[1,2]}}"#);
+//!
+//! let window: Window = serde_json::from_str(&json).unwrap();
+//! assert_eq!(window.samples.capacity(), 4);
+//! assert_eq!(window.samples, [1, 2]);
+//! ```
+
+use crate::ArrayDeque;
+use allocator_api2::alloc::Allocator;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Field names of the serialized struct, in order.
+const FIELDS: &[&str] = &["cap", "items"];
+
+/// Serializes `deque` as a `cap` field followed by an `items` sequence.
+pub fn serialize<T, A, S>(deque: &ArrayDeque<T, A>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    A: Allocator,
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct("ArrayDeque", 2)?;
+    state.serialize_field("cap", &deque.capacity())?;
+    state.serialize_field("items", deque)?;
+    state.end()
+}
+
+/// Deserializes a deque written by [`serialize`], restoring its capacity.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<ArrayDeque<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    MaxCapacitySeed::new(usize::MAX).deserialize(deserializer)
+}
+
+/// A [`DeserializeSeed`] that reads the representation written by
+/// [`serialize`], but fails instead of allocating when the serialized `cap`
+/// exceeds a maximum.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::{ArrayDeque, serde_with_capacity::MaxCapacitySeed};
+/// use serde::de::DeserializeSeed;
+///
+/// let mut json = serde_json::Deserializer::from_str(r#"{"cap":4,"items":[1,2]}"#);
+/// let deque: ArrayDeque<u32> = MaxCapacitySeed::new(16).deserialize(&mut json).unwrap();
+/// assert_eq!(deque.capacity(), 4);
+///
+/// let mut json = serde_json::Deserializer::from_str(r#"{"cap":4000000000,"items":[]}"#);
+/// assert!(MaxCapacitySeed::<u32>::new(16).deserialize(&mut json).is_err());
+/// ```
+pub struct MaxCapacitySeed<T> {
+    max_cap: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> MaxCapacitySeed<T> {
+    /// Creates a seed that accepts capacities up to `max_cap`.
+    pub fn new(max_cap: usize) -> Self {
+        MaxCapacitySeed {
+            max_cap,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for MaxCapacitySeed<T> {
+    type Value = ArrayDeque<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct(
+            "ArrayDeque",
+            FIELDS,
+            DequeVisitor {
+                max_cap: self.max_cap,
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+/// Fails with an `invalid_value` error if `cap` exceeds `max_cap`.
+fn check_cap<E: de::Error>(cap: usize, max_cap: usize) -> Result<usize, E> {
+    if cap > max_cap {
+        return Err(E::invalid_value(
+            de::Unexpected::Unsigned(cap as u64),
+            &CapacityLimit(max_cap),
+        ));
+    }
+    Ok(cap)
+}
+
+/// Describes the largest accepted capacity in error messages.
+struct CapacityLimit(usize);
+
+impl de::Expected for CapacityLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a capacity of at most {}", self.0)
+    }
+}
+
+/// Allocates an empty deque, reporting allocation failure as a serde error.
+fn allocate<T, E: de::Error>(cap: usize) -> Result<ArrayDeque<T>, E> {
+    ArrayDeque::try_new(cap).map_err(E::custom)
+}
+
+/// Fails with an `invalid_length` error if `len` items do not fit in `cap`.
+fn check_len<E: de::Error>(len: usize, cap: usize) -> Result<(), E> {
+    if len > cap {
+        return Err(E::invalid_length(len, &CapacityBound(cap)));
+    }
+    Ok(())
+}
+
+/// Describes the expected number of items in error messages.
+struct CapacityBound(usize);
+
+impl de::Expected for CapacityBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at most {} items", self.0)
+    }
+}
+
+/// Field identifiers of the serialized struct.
+enum Field {
+    Cap,
+    Items,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl Visitor<'_> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("`cap` or `items`")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                match value {
+                    "cap" => Ok(Field::Cap),
+                    "items" => Ok(Field::Items),
+                    _ => Err(E::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+/// Deserializes the `items` sequence straight into a deque of capacity `cap`.
+struct ItemsSeed<T> {
+    cap: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for ItemsSeed<T> {
+    type Value = ArrayDeque<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ItemsSeed<T> {
+    type Value = ArrayDeque<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of at most {} items", self.cap)
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        if let Some(len) = seq.size_hint() {
+            check_len(len, self.cap)?;
+        }
+        let mut deque = allocate(self.cap)?;
+        while let Some(item) = seq.next_element()? {
+            if deque.is_full() {
+                return Err(de::Error::invalid_length(
+                    deque.len() + 1,
+                    &CapacityBound(self.cap),
+                ));
+            }
+            deque.push_back(item);
+        }
+        Ok(deque)
+    }
+}
+
+/// Visits the serialized struct in either its sequence or map form.
+struct DequeVisitor<T> {
+    /// Largest `cap` accepted before allocating
+    max_cap: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for DequeVisitor<T> {
+    type Value = ArrayDeque<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a deque with `cap` and `items` fields")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let cap = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let cap = check_cap(cap, self.max_cap)?;
+        seq.next_element_seed(ItemsSeed {
+            cap,
+            _marker: PhantomData,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(1, &self))
+    }
+
+    fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+        let mut cap = None;
+        let mut deque = None;
+        // Items seen before `cap` have to be buffered until the capacity is known.
+        let mut early_items: Option<ArrayDeque<T>> = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Cap => {
+                    if cap.is_some() {
+                        return Err(de::Error::duplicate_field("cap"));
+                    }
+                    cap = Some(check_cap(map.next_value()?, self.max_cap)?);
+                }
+                Field::Items => {
+                    if deque.is_some() || early_items.is_some() {
+                        return Err(de::Error::duplicate_field("items"));
+                    }
+                    match cap {
+                        Some(cap) => {
+                            deque = Some(map.next_value_seed(ItemsSeed {
+                                cap,
+                                _marker: PhantomData,
+                            })?)
+                        }
+                        None => early_items = Some(map.next_value()?),
+                    }
+                }
+            }
+        }
+        let cap = cap.ok_or_else(|| de::Error::missing_field("cap"))?;
+        if let Some(deque) = deque {
+            return Ok(deque);
+        }
+        let items = early_items.ok_or_else(|| de::Error::missing_field("items"))?;
+        check_len(items.len(), cap)?;
+        let mut deque = allocate(cap)?;
+        deque.extend(items);
+        Ok(deque)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[cfg(not(feature = "std"))]
+    extern crate alloc;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Window {
+        #[serde(with = "crate::serde_with_capacity")]
+        samples: ArrayDeque<i32>,
+    }
+
+    fn wrapped_window() -> Window {
+        let mut samples = ArrayDeque::new(5);
+        for i in 0..7 {
+            samples.push_back(i);
+        }
+        samples.pop_back();
+        Window { samples }
+    }
+
+    #[test]
+    fn json_round_trip_keeps_capacity() {
+        let json = serde_json::to_string(&wrapped_window()).unwrap();
+        assert_eq!(json, r#"{"samples":{"cap":5,"items":[2,3,4,5]}}"#);
+        let window: Window = serde_json::from_str(&json).unwrap();
+        assert_eq!(window.samples.capacity(), 5);
+        assert_eq!(window.samples, [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn json_items_before_cap() {
+        let window: Window =
+            serde_json::from_str(r#"{"samples":{"items":[1,2],"cap":3}}"#).unwrap();
+        assert_eq!(window.samples.capacity(), 3);
+        assert_eq!(window.samples, [1, 2]);
+    }
+
+    #[test]
+    fn json_rejects_more_items_than_cap() {
+        for json in [
+            r#"{"samples":{"cap":2,"items":[1,2,3]}}"#,
+            r#"{"samples":{"items":[1,2,3],"cap":2}}"#,
+        ] {
+            let err = serde_json::from_str::<Window>(json).unwrap_err();
+            assert!(err.to_string().contains("at most 2 items"), "{err}");
+        }
+        let err = serde_json::from_str::<Window>(r#"{"samples":{"items":[]}}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `cap`"), "{err}");
+    }
+
+    #[test]
+    fn postcard_round_trip_keeps_capacity() {
+        let bytes = postcard::to_allocvec(&wrapped_window()).unwrap();
+        let window: Window = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(window.samples.capacity(), 5);
+        assert_eq!(window.samples, [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn max_capacity_seed_rejects_large_cap_before_allocating() {
+        for json in [
+            r#"{"cap":9000000000000000000,"items":[1]}"#,
+            r#"{"items":[1],"cap":9000000000000000000}"#,
+        ] {
+            let mut de = serde_json::Deserializer::from_str(json);
+            let err = MaxCapacitySeed::<i32>::new(8)
+                .deserialize(&mut de)
+                .unwrap_err();
+            assert!(err.to_string().contains("a capacity of at most 8"), "{err}");
+        }
+        // cap = 2^40, followed by an empty sequence.
+        let bytes = [0x80, 0x80, 0x80, 0x80, 0x80, 0x20, 0];
+        let mut de = postcard::Deserializer::from_bytes(&bytes);
+        assert!(MaxCapacitySeed::<i32>::new(8).deserialize(&mut de).is_err());
+
+        let mut de = serde_json::Deserializer::from_str(r#"{"cap":8,"items":[1]}"#);
+        let deque = MaxCapacitySeed::<i32>::new(8).deserialize(&mut de).unwrap();
+        assert_eq!(deque.capacity(), 8);
+    }
+
+    #[test]
+    fn postcard_rejects_more_items_than_cap() {
+        // cap = 1, followed by a two-element sequence.
+        let bytes = [1, 2, 2, 4];
+        let err = postcard::from_bytes::<Window>(&bytes).unwrap_err();
+        assert!(matches!(err, postcard::Error::SerdeDeCustom), "{err:?}");
+    }
+}
@@ -0,0 +1,221 @@
+use crate::ArrayDeque;
+use core::fmt;
+
+/// The minimum and maximum of the last `size` values pushed, kept up to date
+/// in amortized O(1) per push.
+///
+/// The window itself is stored in an [`ArrayDeque`], so pushing into a full
+/// window evicts its oldest value. Alongside it, two monotonic deques hold the
+/// ages of the values that can still become the maximum or the minimum: each
+/// push drops the candidates it outranks from the back, and the candidate at
+/// the front is dropped once its value leaves the window. Ages count pushes,
+/// so no value has to be cloned.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::SlidingMinMax;
+///
+/// let mut window = SlidingMinMax::new(3);
+/// for reading in [4, 2, 12, 3, 1] {
+///     window.push(reading);
+/// }
+/// // The window holds [12, 3, 1].
+/// assert_eq!(window.max(), Some(&12));
+/// assert_eq!(window.min(), Some(&1));
+/// window.push(5);
+/// // The window holds [3, 1, 5].
+/// assert_eq!(window.max(), Some(&5));
+/// ```
+pub struct SlidingMinMax<T> {
+    /// The last `size` values, oldest at the front
+    window: ArrayDeque<T>,
+    /// Ages of the maximum candidates, with strictly decreasing values
+    maxima: ArrayDeque<usize>,
+    /// Ages of the minimum candidates, with strictly increasing values
+    minima: ArrayDeque<usize>,
+    /// Age the next pushed value will get
+    next_age: usize,
+}
+
+impl<T: Ord> SlidingMinMax<T> {
+    /// Creates an empty window of the last `size` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(size: usize) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        SlidingMinMax {
+            window: ArrayDeque::new(size),
+            maxima: ArrayDeque::new(size),
+            minima: ArrayDeque::new(size),
+            next_age: 0,
+        }
+    }
+
+    /// Returns the number of values the window holds once full.
+    pub fn window_size(&self) -> usize {
+        self.window.capacity()
+    }
+
+    /// Returns the number of values currently in the window.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Returns `true` if no value has been pushed since creation or the last
+    /// [`clear`](Self::clear).
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Returns the values in the window, oldest first.
+    pub fn window(&self) -> &ArrayDeque<T> {
+        &self.window
+    }
+
+    /// Returns the value of age `age`, which must still be in the window.
+    fn value(&self, age: usize) -> &T {
+        let oldest = self.next_age.wrapping_sub(self.window.len());
+        &self.window[age.wrapping_sub(oldest)]
+    }
+
+    /// Pushes `value` into the window, evicting the oldest value if the window
+    /// is full.
+    ///
+    /// Amortized O(1): each value enters and leaves each candidate deque at
+    /// most once.
+    pub fn push(&mut self, value: T) {
+        if self.window.is_full() {
+            let expired = self.next_age.wrapping_sub(self.window.len());
+            if self.maxima.front() == Some(&expired) {
+                self.maxima.pop_front();
+            }
+            if self.minima.front() == Some(&expired) {
+                self.minima.pop_front();
+            }
+            self.window.pop_front();
+        }
+        while let Some(&age) = self.maxima.back() {
+            if *self.value(age) > value {
+                break;
+            }
+            self.maxima.pop_back();
+        }
+        while let Some(&age) = self.minima.back() {
+            if *self.value(age) < value {
+                break;
+            }
+            self.minima.pop_back();
+        }
+        let age = self.next_age;
+        self.next_age = age.wrapping_add(1);
+        self.window.push_back(value);
+        self.maxima.push_back(age);
+        self.minima.push_back(age);
+    }
+
+    /// Returns the largest value in the window, or `None` if it is empty.
+    ///
+    /// If several values are equal to the maximum, returns the newest.
+    pub fn max(&self) -> Option<&T> {
+        self.maxima.front().map(|&age| self.value(age))
+    }
+
+    /// Returns the smallest value in the window, or `None` if it is empty.
+    ///
+    /// If several values are equal to the minimum, returns the newest.
+    pub fn min(&self) -> Option<&T> {
+        self.minima.front().map(|&age| self.value(age))
+    }
+
+    /// Removes all values from the window, keeping its size.
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.maxima.clear();
+        self.minima.clear();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SlidingMinMax<T> {
+    /// Formats the values in the window, oldest first.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlidingMinMax")
+            .field("window", &self.window)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SlidingMinMax;
+    use crate::TestRng;
+    use core::ptr;
+
+    #[cfg(not(feature = "std"))]
+    extern crate alloc;
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec::Vec};
+
+    #[test]
+    fn matches_brute_force_over_random_pushes() {
+        let mut rng = TestRng::new(0x2545_f491);
+        for size in 1..=8 {
+            // Small value ranges make ties common, larger ones make long runs.
+            for range in [2, 5, 1_000] {
+                let mut window = SlidingMinMax::new(size);
+                let mut pushed = Vec::new();
+                for _ in 0..500 {
+                    let value = rng.below(range);
+                    window.push(value);
+                    pushed.push(value);
+                    let recent = &pushed[pushed.len().saturating_sub(size)..];
+                    assert_eq!(window.len(), recent.len());
+                    assert!(window.window().iter().eq(recent));
+                    assert_eq!(window.max(), recent.iter().max());
+                    assert_eq!(window.min(), recent.iter().min());
+                    if rng.below(50) == 0 {
+                        window.clear();
+                        pushed.clear();
+                        assert_eq!((window.max(), window.min()), (None, None));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sorted_runs() {
+        let mut window = SlidingMinMax::new(4);
+        for i in 0..20 {
+            window.push(i);
+            assert_eq!(window.max(), Some(&i));
+            assert_eq!(window.min(), Some(&(i - 3).max(0)));
+        }
+        for i in (-20..0).rev() {
+            window.push(i);
+            assert_eq!(window.min(), Some(&i));
+        }
+        assert_eq!(window.max(), Some(&-17));
+        assert_eq!(window.window_size(), 4);
+    }
+
+    #[test]
+    fn ties_resolve_to_newest() {
+        let mut window = SlidingMinMax::new(2);
+        for value in [7, 7, 7] {
+            window.push(value);
+        }
+        let newest = window.window().back().unwrap();
+        assert!(ptr::eq(window.max().unwrap(), newest));
+        assert!(ptr::eq(window.min().unwrap(), newest));
+        assert_eq!(format!("{window:?}"), "SlidingMinMax { window: [7, 7] }");
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn zero_size_panics() {
+        SlidingMinMax::<i32>::new(0);
+    }
+}
@@ -0,0 +1,422 @@
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+use alloc::{alloc::Layout, sync::Arc};
+
+#[cfg(feature = "std")]
+use std::{alloc::Layout, sync::Arc};
+
+use crate::ArrayDeque;
+use allocator_api2::alloc::{Allocator, Global};
+use core::fmt;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The buffer shared by a [`Producer`] and its [`Consumer`].
+///
+/// `head` and `tail` count the elements popped and pushed so far, wrapping on
+/// overflow, so `tail - head` is the current length. Each counter has a single
+/// writer: the consumer stores `head` and the producer stores `tail`.
+struct Shared<T, A: Allocator> {
+    /// Pointer to the allocated memory
+    ptr: NonNull<T>,
+    /// Maximum capacity of the ring
+    cap: usize,
+    /// Number of elements popped so far
+    head: AtomicUsize,
+    /// Number of elements pushed so far
+    tail: AtomicUsize,
+    /// Slot of the front element, recorded when the consumer is dropped
+    front: AtomicUsize,
+    /// Allocator that owns the buffer
+    alloc: A,
+}
+
+// SAFETY: the producer only writes slots the consumer has released, and the
+// consumer only reads slots the producer has published, so each `T` is owned
+// by one thread at a time and merely moves between them. The allocator is only
+// used by `Drop`, which runs once both halves are gone.
+unsafe impl<T: Send, A: Allocator + Send> Send for Shared<T, A> {}
+unsafe impl<T: Send, A: Allocator + Send> Sync for Shared<T, A> {}
+
+impl<T, A: Allocator> Shared<T, A> {
+    /// Returns the slot after `pos`, wrapping to the start of the buffer.
+    #[inline]
+    fn next_slot(&self, pos: usize) -> usize {
+        if pos + 1 == self.cap { 0 } else { pos + 1 }
+    }
+}
+
+impl<T, A: Allocator> Drop for Shared<T, A> {
+    /// Drops the elements that were never popped and deallocates the buffer.
+    fn drop(&mut self) {
+        let len = self.tail.get_mut().wrapping_sub(*self.head.get_mut());
+        let front = *self.front.get_mut();
+        let first = len.min(self.cap - front);
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.ptr.as_ptr().add(front),
+                first,
+            ));
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.ptr.as_ptr(),
+                len - first,
+            ));
+        }
+        let layout = Layout::array::<T>(self.cap).expect("Invalid layout");
+        if layout.size() != 0 {
+            unsafe {
+                self.alloc.deallocate(self.ptr.cast(), layout);
+            }
+        }
+    }
+}
+
+/// The pushing half of a deque split with [`ArrayDeque::split`].
+///
+/// A producer never overwrites: [`try_push`](Self::try_push) hands the value
+/// back while the ring is full.
+pub struct Producer<T, A: Allocator = Global> {
+    /// The ring shared with the consumer
+    shared: Arc<Shared<T, A>>,
+    /// Slot the next element is written to
+    pos: usize,
+    /// Local copy of `shared.tail`, which only this half writes
+    tail: usize,
+}
+
+/// The popping half of a deque split with [`ArrayDeque::split`].
+pub struct Consumer<T, A: Allocator = Global> {
+    /// The ring shared with the producer
+    shared: Arc<Shared<T, A>>,
+    /// Slot of the front element
+    pos: usize,
+    /// Local copy of `shared.head`, which only this half writes
+    head: usize,
+}
+
+impl<T, A: Allocator> ArrayDeque<T, A> {
+    /// Splits the deque into a [`Producer`] that pushes onto the back and a
+    /// [`Consumer`] that pops from the front, which can be moved to two
+    /// different threads.
+    ///
+    /// The halves share the deque's buffer and coordinate through atomic
+    /// counters, without locks. Elements already in the deque stay in place
+    /// and are popped first. The [`OverflowPolicy`](crate::OverflowPolicy) is
+    /// not carried over: the producer always refuses to push into a full ring.
+    /// The buffer, and any elements left in it, are freed once both halves have
+    /// been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    /// use std::thread;
+    ///
+    /// let (mut tx, mut rx) = ArrayDeque::new(4).split();
+    /// let sender = thread::spawn(move || {
+    ///     for i in 0..100 {
+    ///         let mut item = i;
+    ///         while let Err(back) = tx.try_push(item) {
+    ///             item = back;
+    ///             thread::yield_now();
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// let mut received = Vec::new();
+    /// while received.len() < 100 {
+    ///     match rx.pop() {
+    ///         Some(item) => received.push(item),
+    ///         None => thread::yield_now(),
+    ///     }
+    /// }
+    /// sender.join().unwrap();
+    /// assert!(received.into_iter().eq(0..100));
+    /// ```
+    pub fn split(self) -> (Producer<T, A>, Consumer<T, A>) {
+        let (ptr, cap, len, idx, alloc) = self.into_raw_parts();
+        let shared = Arc::new(Shared {
+            ptr,
+            cap,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(len),
+            front: AtomicUsize::new(idx),
+            alloc,
+        });
+        let back = idx + len;
+        let producer = Producer {
+            shared: Arc::clone(&shared),
+            pos: if back >= cap { back - cap } else { back },
+            tail: len,
+        };
+        let consumer = Consumer {
+            shared,
+            pos: idx,
+            head: 0,
+        };
+        (producer, consumer)
+    }
+}
+
+impl<T, A: Allocator> Producer<T, A> {
+    /// Pushes `value` onto the back of the ring, or returns it if the ring is
+    /// full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let (mut tx, mut rx) = ArrayDeque::new(1).split();
+    /// assert_eq!(tx.try_push('a'), Ok(()));
+    /// assert_eq!(tx.try_push('b'), Err('b'));
+    /// assert_eq!(rx.pop(), Some('a'));
+    /// assert_eq!(tx.try_push('b'), Ok(()));
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        // Acquire pairs with the consumer's release of `head`, so its read of a
+        // slot happens before we overwrite that slot.
+        let head = self.shared.head.load(Ordering::Acquire);
+        if self.tail.wrapping_sub(head) == self.shared.cap {
+            return Err(value);
+        }
+        unsafe {
+            self.shared.ptr.as_ptr().add(self.pos).write(value);
+        }
+        self.pos = self.shared.next_slot(self.pos);
+        self.tail = self.tail.wrapping_add(1);
+        self.shared.tail.store(self.tail, Ordering::Release);
+        Ok(())
+    }
+
+    /// Returns the number of elements in the ring. The consumer may pop
+    /// concurrently, so this is an upper bound by the time it returns.
+    pub fn len(&self) -> usize {
+        self.tail
+            .wrapping_sub(self.shared.head.load(Ordering::Acquire))
+    }
+
+    /// Returns `true` if the ring is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the ring is full, in which case
+    /// [`try_push`](Self::try_push) would fail.
+    pub fn is_full(&self) -> bool {
+        self.len() == self.shared.cap
+    }
+
+    /// Returns the maximum number of elements the ring can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.cap
+    }
+}
+
+impl<T, A: Allocator> Consumer<T, A> {
+    /// Pops the front element of the ring, or returns `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::ArrayDeque;
+    ///
+    /// let (mut tx, mut rx) = ArrayDeque::from([1, 2]).split();
+    /// tx.try_push(3).unwrap_err();
+    /// assert_eq!(rx.pop(), Some(1));
+    /// assert_eq!(rx.pop(), Some(2));
+    /// assert_eq!(rx.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        // Acquire pairs with the producer's release of `tail`, so the write of
+        // the slot happens before we read it.
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        if tail == self.head {
+            return None;
+        }
+        let value = unsafe { self.shared.ptr.as_ptr().add(self.pos).read() };
+        self.pos = self.shared.next_slot(self.pos);
+        self.head = self.head.wrapping_add(1);
+        self.shared.head.store(self.head, Ordering::Release);
+        Some(value)
+    }
+
+    /// Returns the number of elements in the ring. The producer may push
+    /// concurrently, so this is a lower bound by the time it returns.
+    pub fn len(&self) -> usize {
+        self.shared
+            .tail
+            .load(Ordering::Acquire)
+            .wrapping_sub(self.head)
+    }
+
+    /// Returns `true` if the ring is empty, in which case [`pop`](Self::pop)
+    /// would return `None`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of elements the ring can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.cap
+    }
+}
+
+impl<T, A: Allocator> Drop for Consumer<T, A> {
+    /// Records where the remaining elements start, so the shared buffer can
+    /// drop them.
+    fn drop(&mut self) {
+        // The reference count decrement that follows publishes this store to
+        // whichever half drops the buffer.
+        self.shared.front.store(self.pos, Ordering::Relaxed);
+    }
+}
+
+impl<T, A: Allocator> fmt::Debug for Producer<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T, A: Allocator> fmt::Debug for Consumer<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::sync::Arc;
+    use std::thread;
+    use std::vec::Vec;
+
+    struct DropCounter(Arc<AtomicUsize>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn push_blocking<T>(tx: &mut Producer<T>, mut value: T) {
+        while let Err(back) = tx.try_push(value) {
+            value = back;
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn stress_two_threads_keep_order() {
+        const ITEMS: u64 = 3_000_000;
+        let (mut tx, mut rx) = ArrayDeque::new(16).split();
+        let producer = thread::spawn(move || {
+            for i in 0..ITEMS {
+                push_blocking(&mut tx, i);
+            }
+        });
+        let mut expected = 0;
+        while expected < ITEMS {
+            match rx.pop() {
+                Some(item) => {
+                    assert_eq!(item, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn stress_items_drop_exactly_once() {
+        const ITEMS: usize = 1_000_000;
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (mut tx, mut rx) = ArrayDeque::new(7).split();
+        let producer = {
+            let drops = Arc::clone(&drops);
+            thread::spawn(move || {
+                for _ in 0..ITEMS {
+                    push_blocking(&mut tx, DropCounter(Arc::clone(&drops)));
+                }
+            })
+        };
+        let mut popped = 0;
+        while popped < ITEMS - 5 {
+            match rx.pop() {
+                Some(item) => {
+                    drop(item);
+                    popped += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(rx.len(), 5);
+        drop(rx);
+        assert_eq!(drops.load(Ordering::Relaxed), ITEMS);
+    }
+
+    #[test]
+    fn split_wrapped_deque_keeps_contents() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        let (mut tx, mut rx) = deque.split();
+        assert_eq!(tx.len(), 4);
+        assert!(tx.is_full());
+        assert_eq!(tx.try_push(6), Err(6));
+        assert_eq!(rx.pop(), Some(2));
+        tx.try_push(6).unwrap();
+        let rest: Vec<i32> = core::iter::from_fn(|| rx.pop()).collect();
+        assert_eq!(rest, [3, 4, 5, 6]);
+        assert!(rx.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity_rejects_everything() {
+        let (mut tx, mut rx) = ArrayDeque::new(0).split();
+        assert_eq!(tx.capacity(), 0);
+        assert!(tx.is_full() && tx.is_empty());
+        assert_eq!(tx.try_push(1), Err(1));
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn remaining_elements_dropped_with_last_half() {
+        for consumer_first in [false, true] {
+            let drops = Arc::new(AtomicUsize::new(0));
+            let mut deque = ArrayDeque::new(3);
+            for _ in 0..5 {
+                deque.push_back(DropCounter(Arc::clone(&drops)));
+            }
+            assert_eq!(drops.load(Ordering::Relaxed), 2);
+            let (mut tx, mut rx) = deque.split();
+            drop(rx.pop());
+            tx.try_push(DropCounter(Arc::clone(&drops))).ok().unwrap();
+            assert_eq!(drops.load(Ordering::Relaxed), 3);
+            if consumer_first {
+                drop(rx);
+                assert_eq!(drops.load(Ordering::Relaxed), 3);
+                drop(tx);
+            } else {
+                drop(tx);
+                assert_eq!(drops.load(Ordering::Relaxed), 3);
+                drop(rx);
+            }
+            assert_eq!(drops.load(Ordering::Relaxed), 6);
+        }
+    }
+}
@@ -1,18 +1,43 @@
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
-use crate::CapacityError;
+#[cfg(feature = "stats")]
+use crate::Stats;
+use crate::{
+    ArrayWindows, CapacityError, Chunks, ChunksExact, GetManyMutError, TryExtendError, Windows,
+    check_disjoint, resolve_range, sub_slices, sub_slices_mut,
+};
+use core::cmp::Ordering;
 use core::fmt;
-use core::mem::MaybeUninit;
-use core::ops::{Index, IndexMut};
+use core::hash::{Hash, Hasher};
+use core::iter::{self, Chain, FusedIterator};
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Index, IndexMut, RangeBounds};
+use core::{ptr, slice};
 
-#[cfg(not(feature = "std"))]
-use alloc::collections::VecDeque;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::VecDeque, vec::Vec};
 #[cfg(feature = "std")]
-use std::collections::VecDeque;
+use std::{collections::VecDeque, vec::Vec};
 
+#[cfg(feature = "arbitrary")]
+use crate::ARBITRARY_HEADER_BYTES;
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, MaxRecursionReached, Unstructured, size_hint};
+#[cfg(feature = "bincode")]
+use bincode::{
+    BorrowDecode, Decode, Encode,
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+};
+#[cfg(feature = "serde")]
+use serde::de::{self, SeqAccess, Visitor};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// A fixed-capacity, stack-allocated double-ended queue backed by a circular buffer.
 ///
@@ -42,6 +67,8 @@ pub struct StackArrayDeque<T, const N: usize> {
     data: [MaybeUninit<T>; N],
     len: usize,
     idx: usize,
+    #[cfg(feature = "stats")]
+    stats: Stats,
 }
 
 impl<T, const N: usize> StackArrayDeque<T, N> {
@@ -57,12 +84,61 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
     /// assert!(deque.is_empty());
     /// ```
     pub const fn new() -> Self {
-        assert!(N > 0, "StackArrayDeque capacity must be greater than 0");
         Self {
             data: unsafe { MaybeUninit::uninit().assume_init() },
             len: 0,
             idx: 0,
+            #[cfg(feature = "stats")]
+            stats: Stats::new(),
+        }
+    }
+
+    /// Creates a full `StackArrayDeque` with every slot set to a clone of `value`.
+    ///
+    /// If cloning `value` panics, the elements written so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let dq: StackArrayDeque<f32, 3> = StackArrayDeque::filled(0.0);
+    /// assert!(dq.is_full());
+    /// assert_eq!(dq[2], 0.0);
+    /// ```
+    pub fn filled(value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut deque = Self::new();
+        deque.fill(value);
+        deque
+    }
+
+    /// Creates a full `StackArrayDeque` whose element at each logical index `i`
+    /// is `f(i)`, mirroring [`core::array::from_fn`].
+    ///
+    /// If `f` panics, the elements created so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let dq: StackArrayDeque<usize, 4> = StackArrayDeque::from_fn(|i| i * i);
+    /// assert!(dq.is_full());
+    /// assert_eq!(dq[3], 9);
+    /// ```
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        let mut deque = Self::new();
+        for i in 0..N {
+            deque.data[i].write(f(i));
+            deque.len += 1;
         }
+        deque
     }
 
     /// Appends an element to the back of the deque.
@@ -85,7 +161,11 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
     /// assert_eq!(deque.len(), 2);
     /// ```
     pub fn push_back(&mut self, value: T) {
-        let write_idx = (self.idx + self.len) % N;
+        if N == 0 {
+            return;
+        }
+        let old_len = self.len;
+        let write_idx = self.physical_index(self.len);
         if self.len == N {
             unsafe {
                 self.data[write_idx].assume_init_drop();
@@ -94,10 +174,11 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
         self.data[write_idx].write(value);
 
         if self.len == N {
-            self.idx = (self.idx + 1) % N;
+            self.idx = self.wrap_add(self.idx, 1);
         } else {
             self.len += 1;
         }
+        self.record_pushes(1, old_len);
     }
 
     /// Prepends an element to the front of the deque.
@@ -120,10 +201,14 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
     /// assert_eq!(deque[1], 1);
     /// ```
     pub fn push_front(&mut self, value: T) {
-        self.idx = (self.idx + N - 1) % N;
+        if N == 0 {
+            return;
+        }
+        let old_len = self.len;
+        self.idx = self.wrap_sub(self.idx, 1);
 
         if self.len == N {
-            let drop_idx = (self.idx + self.len) % N;
+            let drop_idx = self.physical_index(self.len);
             unsafe {
                 self.data[drop_idx].assume_init_drop();
             }
@@ -132,6 +217,91 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
         }
 
         self.data[self.idx].write(value);
+        self.record_pushes(1, old_len);
+    }
+
+    /// Appends an element to the back of the deque without overwriting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the deque is full, leaving its contents untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 1> = StackArrayDeque::new();
+    /// assert_eq!(deque.try_push_back(1), Ok(()));
+    /// assert_eq!(deque.try_push_back(2), Err(2));
+    /// assert_eq!(deque[0], 1);
+    /// ```
+    pub fn try_push_back(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.push_back(value);
+        Ok(())
+    }
+
+    /// Prepends an element to the front of the deque without overwriting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the deque is full, leaving its contents untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 1> = StackArrayDeque::new();
+    /// assert_eq!(deque.try_push_front(1), Ok(()));
+    /// assert_eq!(deque.try_push_front(2), Err(2));
+    /// assert_eq!(deque[0], 1);
+    /// ```
+    pub fn try_push_front(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.push_front(value);
+        Ok(())
+    }
+
+    /// Appends every element of `iter` to the back without overwriting,
+    /// stopping at the first element that does not fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryExtendError`] holding the rejected element and the rest of
+    /// the iterator. Elements already in the deque are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// dq.push_back(1);
+    /// let err = dq.try_extend([2, 3, 4, 5]).unwrap_err();
+    /// assert_eq!(err.element, 4);
+    /// assert_eq!(err.remaining.collect::<Vec<_>>(), [5]);
+    /// assert_eq!(dq.len(), 3);
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryExtendError<I::IntoIter>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        while let Some(item) = iter.next() {
+            if let Err(element) = self.try_push_back(item) {
+                return Err(TryExtendError {
+                    element,
+                    remaining: iter,
+                });
+            }
+        }
+        Ok(())
     }
 
     /// Removes and returns the last element from the deque.
@@ -156,7 +326,7 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
         if self.len == 0 {
             return None;
         }
-        let tail_idx = (self.idx + self.len - 1) % N;
+        let tail_idx = self.physical_index(self.len - 1);
         self.len -= 1;
         Some(unsafe { self.data[tail_idx].assume_init_read() })
     }
@@ -184,55 +354,117 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
             return None;
         }
         let front_idx = self.idx;
-        self.idx = (self.idx + 1) % N;
+        self.idx = self.wrap_add(self.idx, 1);
         self.len -= 1;
         Some(unsafe { self.data[front_idx].assume_init_read() })
     }
 
-    /// Returns a reference to the front element without removing it.
+    /// Removes and returns the front element if `predicate` returns `true`
+    /// for it, or leaves the deque untouched and returns `None` otherwise.
+    ///
+    /// Returns `None` without calling `predicate` if the deque is empty.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::StackArrayDeque;
     ///
-    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-    /// assert_eq!(dq.front(), None);
-    /// dq.push_back(42);
-    /// assert_eq!(dq.front(), Some(&42));
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.pop_front_if(|x| *x > 1), None);
+    /// assert_eq!(deque.pop_front_if(|x| *x == 1), Some(1));
+    /// assert_eq!(deque.len(), 2);
     /// ```
-    pub fn front(&self) -> Option<&T> {
-        if self.is_empty() {
-            None
+    pub fn pop_front_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        let first = self.front_mut()?;
+        if predicate(first) {
+            self.pop_front()
         } else {
-            Some(unsafe { self.data[self.idx].assume_init_ref() })
+            None
         }
     }
 
-    /// Returns a reference to the back element without removing it.
+    /// Removes and returns the back element if `predicate` returns `true`
+    /// for it, or leaves the deque untouched and returns `None` otherwise.
+    ///
+    /// Returns `None` without calling `predicate` if the deque is empty.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::StackArrayDeque;
     ///
-    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-    /// dq.push_back(1);
-    /// dq.push_back(2);
-    /// assert_eq!(dq.back(), Some(&2));
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.pop_back_if(|x| *x < 3), None);
+    /// assert_eq!(deque.pop_back_if(|x| *x == 3), Some(3));
+    /// assert_eq!(deque.len(), 2);
     /// ```
-    pub fn back(&self) -> Option<&T> {
-        if self.is_empty() {
-            None
+    pub fn pop_back_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        let last = self.back_mut()?;
+        if predicate(last) {
+            self.pop_back()
         } else {
-            let back_idx = (self.idx + self.len - 1) % N;
-            Some(unsafe { self.data[back_idx].assume_init_ref() })
+            None
         }
     }
 
-    /// Returns an iterator over the elements of the deque.
+    /// Returns an iterator that pops front elements for as long as
+    /// `predicate` returns `true`, stopping at the first one it rejects.
     ///
-    /// The iterator yields elements from front to back.
+    /// Elements are popped one at a time as the iterator advances, so
+    /// dropping it early leaves the remaining elements in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 5, 3]);
+    /// assert!(deque.pop_front_while(|x| *x < 4).eq([1, 2]));
+    /// assert_eq!(deque.front(), Some(&5));
+    /// ```
+    pub fn pop_front_while<F>(&mut self, mut predicate: F) -> impl FusedIterator<Item = T>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        iter::from_fn(move || self.pop_front_if(&mut predicate)).fuse()
+    }
+
+    /// Returns an iterator that pops back elements for as long as
+    /// `predicate` returns `true`, stopping at the first one it rejects.
+    ///
+    /// Elements are popped one at a time as the iterator advances, so
+    /// dropping it early leaves the remaining elements in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 5, 3, 2]);
+    /// assert!(deque.pop_back_while(|x| *x < 4).eq([2, 3]));
+    /// assert_eq!(deque.back(), Some(&5));
+    /// ```
+    pub fn pop_back_while<F>(&mut self, mut predicate: F) -> impl FusedIterator<Item = T>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        iter::from_fn(move || self.pop_back_if(&mut predicate)).fuse()
+    }
+
+    /// Inserts an element at `index` (0 is front), shifting whichever side of
+    /// the deque holds fewer elements.
+    ///
+    /// If the deque is full, the back element is evicted first to make room,
+    /// so inserting at `len()` replaces the back element. A zero-capacity deque
+    /// drops `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()`.
     ///
     /// # Examples
     ///
@@ -241,37 +473,79 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
     ///
     /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
     /// deque.push_back(1);
-    /// deque.push_back(2);
     /// deque.push_back(3);
+    /// deque.insert(1, 2);
+    /// assert_eq!(deque[1], 2);
     ///
-    /// let mut iter = deque.iter();
-    /// assert_eq!(iter.next(), Some(&1));
-    /// assert_eq!(iter.next(), Some(&2));
-    /// assert_eq!(iter.next(), Some(&3));
-    /// assert_eq!(iter.next(), None);
+    /// // Full: the back element (3) is evicted.
+    /// deque.insert(0, 0);
+    /// assert_eq!(deque[0], 0);
+    /// assert_eq!(deque.back(), Some(&2));
     /// ```
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
-        (0..self.len).map(move |i| {
-            let idx = (self.idx + i) % N;
-            unsafe { self.data[idx].assume_init_ref() }
-        })
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len, "Index out of bounds");
+        if N == 0 {
+            return;
+        }
+        let old_len = self.len;
+        if self.is_full() {
+            self.pop_back();
+        }
+        let index = index.min(self.len);
+
+        if index < self.len - index {
+            self.idx = self.wrap_sub(self.idx, 1);
+            for i in 0..index {
+                unsafe { self.move_slot(i + 1, i) };
+            }
+        } else {
+            for i in (index..self.len).rev() {
+                unsafe { self.move_slot(i, i + 1) };
+            }
+        }
+        self.len += 1;
+        let idx = self.physical_index(index);
+        self.data[idx].write(value);
+        self.record_pushes(1, old_len);
     }
 
-    /// Returns the maximum capacity of the deque.
+    /// Inserts an element at `index` (0 is front) without evicting anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the deque is full, leaving its contents untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()`.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::StackArrayDeque;
     ///
-    /// let deque: StackArrayDeque<i32, 10> = StackArrayDeque::new();
-    /// assert_eq!(deque.capacity(), 10);
+    /// let mut deque: StackArrayDeque<i32, 2> = StackArrayDeque::new();
+    /// deque.push_back(1);
+    /// assert_eq!(deque.try_insert(0, 0), Ok(()));
+    /// assert_eq!(deque.try_insert(1, 5), Err(5));
+    /// assert_eq!(deque[0], 0);
+    /// assert_eq!(deque[1], 1);
     /// ```
-    pub const fn capacity(&self) -> usize {
-        N
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), T> {
+        assert!(index <= self.len, "Index out of bounds");
+        if self.is_full() {
+            return Err(value);
+        }
+        self.insert(index, value);
+        Ok(())
     }
 
-    /// Returns the number of elements currently in the deque.
+    /// Removes and returns the element at `index` (0 is front), shifting
+    /// whichever side of the deque holds fewer elements.
+    ///
+    /// # Returns
+    ///
+    /// `Some(T)` if `index < len()`, `None` otherwise.
     ///
     /// # Examples
     ///
@@ -279,15 +553,37 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
     /// use array_deque::StackArrayDeque;
     ///
     /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-    /// assert_eq!(deque.len(), 0);
-    /// deque.push_back(1);
-    /// assert_eq!(deque.len(), 1);
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.remove(1), Some(2));
+    /// assert_eq!(deque.remove(5), None);
+    /// assert_eq!(deque[0], 1);
+    /// assert_eq!(deque[1], 3);
     /// ```
-    pub const fn len(&self) -> usize {
-        self.len
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let value = unsafe { self.data[self.physical_index(index)].assume_init_read() };
+
+        if index < self.len - 1 - index {
+            for i in (0..index).rev() {
+                unsafe { self.move_slot(i, i + 1) };
+            }
+            self.idx = self.wrap_add(self.idx, 1);
+        } else {
+            for i in index + 1..self.len {
+                unsafe { self.move_slot(i, i - 1) };
+            }
+        }
+        self.len -= 1;
+        Some(value)
     }
 
-    /// Returns `true` if the deque contains no elements.
+    /// Swaps the elements at indices `i` and `j` (0 is front).
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
     ///
     /// # Examples
     ///
@@ -295,35 +591,55 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
     /// use array_deque::StackArrayDeque;
     ///
     /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-    /// assert!(deque.is_empty());
-    /// deque.push_back(1);
-    /// assert!(!deque.is_empty());
+    /// deque.extend([1, 2, 3]);
+    /// deque.swap(0, 2);
+    /// assert_eq!(deque[0], 3);
+    /// assert_eq!(deque[2], 1);
     /// ```
-    pub const fn is_empty(&self) -> bool {
-        self.len == 0
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len && j < self.len, "Index out of bounds");
+        let i = self.physical_index(i);
+        let j = self.physical_index(j);
+        self.data.swap(i, j);
     }
 
-    /// Returns `true` if the deque has reached its maximum capacity.
+    /// Removes the element at `index` and returns it, replacing it with the
+    /// front element. This does not preserve ordering, but is O(1).
+    ///
+    /// # Returns
+    ///
+    /// `Some(T)` if `index < len()`, `None` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::StackArrayDeque;
     ///
-    /// let mut deque: StackArrayDeque<i32, 2> = StackArrayDeque::new();
-    /// assert!(!deque.is_full());
-    /// deque.push_back(1);
-    /// deque.push_back(2);
-    /// assert!(deque.is_full());
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.swap_remove_front(2), Some(3));
+    /// assert_eq!(deque[0], 2);
+    /// assert_eq!(deque[1], 1);
     /// ```
-    pub const fn is_full(&self) -> bool {
-        self.len == N
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let value = unsafe { self.data[self.physical_index(index)].assume_init_read() };
+        if index != 0 {
+            unsafe { self.move_slot(0, index) };
+        }
+        self.idx = self.wrap_add(self.idx, 1);
+        self.len -= 1;
+        Some(value)
     }
 
-    /// Removes all elements from the deque.
+    /// Removes the element at `index` and returns it, replacing it with the
+    /// back element. This does not preserve ordering, but is O(1).
     ///
-    /// This operation properly drops all contained elements and resets
-    /// the deque to an empty state.
+    /// # Returns
+    ///
+    /// `Some(T)` if `index < len()`, `None` otherwise.
     ///
     /// # Examples
     ///
@@ -331,171 +647,2456 @@ impl<T, const N: usize> StackArrayDeque<T, N> {
     /// use array_deque::StackArrayDeque;
     ///
     /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-    /// deque.push_back(1);
-    /// deque.push_back(2);
-    /// deque.clear();
-    /// assert!(deque.is_empty());
-    /// assert_eq!(deque.len(), 0);
+    /// deque.extend([1, 2, 3]);
+    /// assert_eq!(deque.swap_remove_back(0), Some(1));
+    /// assert_eq!(deque[0], 3);
+    /// assert_eq!(deque[1], 2);
     /// ```
-    pub fn clear(&mut self) {
-        for i in 0..self.len {
-            let idx = (self.idx + i) % N;
-            unsafe {
-                self.data[idx].assume_init_drop();
-            }
+    pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
         }
-        self.len = 0;
-        self.idx = 0;
+        let value = unsafe { self.data[self.physical_index(index)].assume_init_read() };
+        let last = self.len - 1;
+        if index != last {
+            unsafe { self.move_slot(last, index) };
+        }
+        self.len -= 1;
+        Some(value)
     }
-}
 
-impl<T, const N: usize> Drop for StackArrayDeque<T, N> {
-    /// Properly drops all contained elements.
-    fn drop(&mut self) {
-        self.clear();
+    /// Rotates the deque `n` places to the left, so that the element at
+    /// index `n` becomes the front.
+    ///
+    /// A full deque is rotated by adjusting the head index only; otherwise
+    /// `min(n, len() - n)` elements are moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.rotate_left(1);
+    /// assert_eq!(deque[0], 2);
+    /// assert_eq!(deque[3], 1);
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "Rotation amount out of bounds");
+        let k = self.len - n;
+        if n <= k {
+            self.rotate_left_inner(n);
+        } else {
+            self.rotate_right_inner(k);
+        }
     }
-}
 
-impl<T, const N: usize> Default for StackArrayDeque<T, N> {
-    /// Creates an empty deque.
-    fn default() -> Self {
-        Self::new()
+    /// Rotates the deque `n` places to the right, so that the element at
+    /// index `len() - n` becomes the front.
+    ///
+    /// A full deque is rotated by adjusting the head index only; otherwise
+    /// `min(n, len() - n)` elements are moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.rotate_right(1);
+    /// assert_eq!(deque[0], 4);
+    /// assert_eq!(deque[1], 1);
+    /// ```
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "Rotation amount out of bounds");
+        let k = self.len - n;
+        if n <= k {
+            self.rotate_right_inner(n);
+        } else {
+            self.rotate_left_inner(k);
+        }
     }
-}
 
-impl<T: fmt::Debug, const N: usize> fmt::Debug for StackArrayDeque<T, N> {
-    /// Formats the deque as a debug list showing all elements from front to back.
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
+    /// Returns a reference to the front element without removing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// assert_eq!(dq.front(), None);
+    /// dq.push_back(42);
+    /// assert_eq!(dq.front(), Some(&42));
+    /// ```
+    pub fn front(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { self.data[self.idx].assume_init_ref() })
+        }
     }
-}
 
-impl<T: Clone, const N: usize> Clone for StackArrayDeque<T, N> {
-    /// Creates a deep copy of the deque with the same capacity and elements.
-    fn clone(&self) -> Self {
-        let mut new = StackArrayDeque::new();
-        for item in self.iter() {
-            new.push_back(item.clone());
+    /// Returns a reference to the back element without removing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// dq.push_back(1);
+    /// dq.push_back(2);
+    /// assert_eq!(dq.back(), Some(&2));
+    /// ```
+    pub fn back(&self) -> Option<&T> {
+        self.get_back(0)
+    }
+
+    /// Returns a mutable reference to the front element, or `None` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// assert_eq!(dq.front_mut(), None);
+    /// dq.push_back(1);
+    /// if let Some(x) = dq.front_mut() {
+    ///     *x = 10;
+    /// }
+    /// assert_eq!(dq.front(), Some(&10));
+    /// ```
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { self.data[self.idx].assume_init_mut() })
         }
-        new
     }
-}
 
-impl<T: PartialEq, const N: usize> PartialEq for StackArrayDeque<T, N> {
-    /// Compares two deques for equality based on their elements and order.
-    fn eq(&self, other: &Self) -> bool {
-        self.len == other.len && self.iter().eq(other.iter())
+    /// Returns a mutable reference to the back element, or `None` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// assert_eq!(dq.back_mut(), None);
+    /// dq.push_back(1);
+    /// dq.push_back(2);
+    /// if let Some(x) = dq.back_mut() {
+    ///     *x += 1;
+    /// }
+    /// assert_eq!(dq.back(), Some(&3));
+    /// ```
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_back_mut(0)
     }
-}
 
-impl<T: Eq, const N: usize> Eq for StackArrayDeque<T, N> {}
+    /// Returns a reference to the element `index` places from the back (0 is
+    /// back), or `None` if `index >= len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// assert_eq!(deque.get_back(0), Some(&4));
+    /// assert_eq!(deque.get_back(2), Some(&2));
+    /// assert_eq!(deque.get_back(3), None);
+    /// ```
+    pub fn get_back(&self, index: usize) -> Option<&T> {
+        let slot = self.back_slot(index)?;
+        Some(unsafe { self.data[slot].assume_init_ref() })
+    }
 
-impl<T, const N: usize> Index<usize> for StackArrayDeque<T, N> {
-    type Output = T;
+    /// Returns a mutable reference to the element `index` places from the
+    /// back (0 is back), or `None` if `index >= len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3]);
+    /// *deque.get_back_mut(1).unwrap() = 20;
+    /// assert_eq!(deque[1], 20);
+    /// ```
+    pub fn get_back_mut(&mut self, index: usize) -> Option<&mut T> {
+        let slot = self.back_slot(index)?;
+        Some(unsafe { self.data[slot].assume_init_mut() })
+    }
 
-    /// Provides indexed access to elements in the deque.
+    /// Returns a reference to the element at `index` (0 is front) without
+    /// bounds checking.
     ///
-    /// Index 0 corresponds to the front element, index 1 to the second element, etc.
+    /// # Safety
     ///
-    /// # Panics
+    /// `index` must be less than `len()`. Debug builds assert this.
     ///
-    /// Panics if the index is out of bounds (>= len()).
-    fn index(&self, i: usize) -> &Self::Output {
-        assert!(i < self.len);
-        let idx = (self.idx + i) % N;
-        unsafe { self.data[idx].assume_init_ref() }
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 2> = StackArrayDeque::new();
+    /// dq.push_back(1);
+    /// dq.push_back(2);
+    /// dq.push_back(3);
+    /// assert_eq!(unsafe { *dq.get_unchecked(0) }, 2);
+    /// ```
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        debug_assert!(index < self.len, "Index out of bounds");
+        unsafe {
+            self.data
+                .get_unchecked(self.physical_index(index))
+                .assume_init_ref()
+        }
     }
-}
 
-impl<T, const N: usize> IndexMut<usize> for StackArrayDeque<T, N> {
-    /// Provides mutable indexed access to elements in the deque.
+    /// Returns a mutable reference to the element at `index` (0 is front)
+    /// without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `len()`. Debug builds assert this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 2> = StackArrayDeque::new();
+    /// dq.push_back(1);
+    /// unsafe { *dq.get_unchecked_mut(0) = 10 };
+    /// assert_eq!(dq[0], 10);
+    /// ```
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        debug_assert!(index < self.len, "Index out of bounds");
+        let idx = self.physical_index(index);
+        unsafe { self.data.get_unchecked_mut(idx).assume_init_mut() }
+    }
+
+    /// Returns mutable references to the elements at each of `indices` (0 is
+    /// front) at once.
+    ///
+    /// Takes O(K²) time to check the indices, like the slice method of the
+    /// same shape.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GetManyMutError::IndexOutOfBounds`] if any index is not less
+    /// than `len()`, and [`GetManyMutError::OverlappingIndices`] if any index
+    /// appears twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{GetManyMutError, StackArrayDeque};
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// dq.extend([1, 2, 3, 4]);
+    /// let [prev, cur, next] = dq.get_many_mut([0, 1, 2]).unwrap();
+    /// *cur = (*prev + *cur + *next) / 3;
+    /// assert_eq!(dq[1], 3);
+    /// assert_eq!(dq.get_many_mut([1, 1]), Err(GetManyMutError::OverlappingIndices));
+    /// assert_eq!(dq.get_many_mut([0, 3]), Err(GetManyMutError::IndexOutOfBounds));
+    /// ```
+    pub fn get_many_mut<const K: usize>(
+        &mut self,
+        indices: [usize; K],
+    ) -> Result<[&mut T; K], GetManyMutError> {
+        check_disjoint(&indices, self.len)?;
+        let ptr = self.data.as_mut_ptr();
+        // Distinct logical indices below `len` map to distinct initialized
+        // slots, even when they fall on both sides of the wrap point.
+        Ok(
+            indices
+                .map(|index| unsafe { (*ptr.add(self.physical_index(index))).assume_init_mut() }),
+        )
+    }
+
+    /// Returns an iterator over the elements of the deque.
+    ///
+    /// The iterator yields elements from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    /// deque.push_back(3);
+    ///
+    /// let mut iter = deque.iter();
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> StackArrayDequeIter<'_, T, N> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` elements,
+    /// front to back.
+    ///
+    /// Since the contents may wrap around the end of the buffer, each window
+    /// is a pair of slices, like [`as_slices`](Self::as_slices): the first
+    /// slice followed by the second. If the deque holds fewer than `size`
+    /// elements, the iterator is empty.
     ///
     /// # Panics
     ///
-    /// Panics if the index is out of bounds (>= len()).
-    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
-        assert!(i < self.len);
-        let idx = (self.idx + i) % N;
-        unsafe { self.data[idx].assume_init_mut() }
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// let sums: Vec<i32> = deque
+    ///     .windows(2)
+    ///     .map(|(a, b)| a.iter().chain(b).sum())
+    ///     .collect();
+    /// assert_eq!(sums, [5, 7]);
+    /// ```
+    pub fn windows(&self, size: usize) -> Windows<'_, T> {
+        let (front, back) = self.as_slices();
+        Windows::new(front, back, size)
     }
-}
 
-#[cfg(feature = "serde")]
-impl<T: Serialize, const N: usize> Serialize for StackArrayDeque<T, N> {
-    /// Serializes the deque as a sequence of its elements from front to back.
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    /// Returns an iterator over all overlapping windows of `K` elements,
+    /// front to back, each copied into an array.
+    ///
+    /// If the deque holds fewer than `K` elements, the iterator is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// let mut windows = deque.array_windows();
+    /// assert_eq!(windows.next(), Some([2, 3, 4]));
+    /// assert_eq!(windows.next(), None);
+    /// ```
+    pub fn array_windows<const K: usize>(&self) -> ArrayWindows<'_, T, K>
     where
-        S: Serializer,
+        T: Copy,
     {
-        use serde::ser::SerializeSeq;
-        let mut seq = serializer.serialize_seq(Some(self.len))?;
-        for item in self.iter() {
-            seq.serialize_element(item)?;
-        }
-        seq.end()
+        let (front, back) = self.as_slices();
+        ArrayWindows::new(front, back)
     }
-}
 
-#[cfg(feature = "serde")]
-impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for StackArrayDeque<T, N> {
-    /// Deserializes a sequence into a `StackArrayDeque`, erroring if it exceeds capacity.
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let vec: Vec<T> = Vec::deserialize(deserializer)?;
-        if vec.len() > N {
-            return Err(serde::de::Error::custom(
-                "Too many elements for StackArrayDeque capacity",
-            ));
-        }
-        let mut deque = StackArrayDeque::new();
-        for item in vec {
-            deque.push_back(item);
-        }
-        Ok(deque)
+    /// Returns an iterator over non-overlapping chunks of `size` elements,
+    /// front to back. The last chunk is shorter if `size` does not divide the
+    /// length.
+    ///
+    /// As with [`windows`](Self::windows), each chunk is a pair of slices: the
+    /// first slice followed by the second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4, 5, 6]);
+    /// let batches: Vec<Vec<i32>> = deque
+    ///     .chunks(2)
+    ///     .map(|(a, b)| a.iter().chain(b).copied().collect())
+    ///     .collect();
+    /// assert_eq!(batches, [vec![2, 3], vec![4, 5], vec![6]]);
+    /// ```
+    pub fn chunks(&self, size: usize) -> Chunks<'_, T> {
+        let (front, back) = self.as_slices();
+        Chunks::new(front, back, size)
     }
-}
 
-impl<T, const N: usize> Extend<T> for StackArrayDeque<T, N> {
-    /// Extends the deque with items from an iterator, pushing to the back.
+    /// Returns an iterator over non-overlapping chunks of exactly `size`
+    /// elements, front to back. Elements left over at the back are skipped and
+    /// available through [`ChunksExact::remainder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::StackArrayDeque;
     ///
-    /// let mut dq: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-    /// dq.extend([1,2,3]);
-    /// assert_eq!(dq.len(), 3);
+    /// let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4, 5, 6]);
+    /// let chunks = deque.chunks_exact(2);
+    /// assert_eq!(chunks.remainder(), (&[6][..], &[][..]));
+    /// assert_eq!(chunks.len(), 2);
     /// ```
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for item in iter {
-            self.push_back(item);
-        }
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact<'_, T> {
+        let (front, back) = self.as_slices();
+        ChunksExact::new(front, back, size)
     }
-}
 
-impl<T, const N: usize> FromIterator<T> for StackArrayDeque<T, N> {
-    /// Creates a deque by collecting an iterator into its back, up to capacity.
+    /// Returns the contents of the deque as a pair of slices (front to back).
+    ///
+    /// The second slice is non-empty only when the contents wrap around the
+    /// end of the buffer.
     ///
     /// # Examples
     ///
     /// ```
     /// use array_deque::StackArrayDeque;
     ///
-    /// let dq: StackArrayDeque<_, 4> = (0..3).collect();
-    /// assert_eq!(dq.len(), 3);
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3]);
+    /// deque.push_back(4);
+    /// assert_eq!(deque.as_slices(), (&[2, 3][..], &[4][..]));
     /// ```
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut dq = StackArrayDeque::new();
-        dq.extend(iter);
-        dq
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let head_len = (N - self.idx).min(self.len);
+        let ptr = self.data.as_ptr() as *const T;
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(self.idx), head_len),
+                slice::from_raw_parts(ptr, self.len - head_len),
+            )
+        }
     }
-}
 
-impl<T, const N: usize> TryFrom<VecDeque<T>> for StackArrayDeque<T, N> {
-    type Error = CapacityError;
+    /// Returns the contents of the deque as a pair of mutable slices (front to back).
+    ///
+    /// The second slice is non-empty only when the contents wrap around the
+    /// end of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3]);
+    /// deque.push_back(4);
+    /// let (front, back) = deque.as_mut_slices();
+    /// front[0] = 20;
+    /// back[0] = 40;
+    /// assert_eq!(deque[0], 20);
+    /// assert_eq!(deque[2], 40);
+    /// ```
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let head_len = (N - self.idx).min(self.len);
+        let ptr = self.data.as_mut_ptr() as *mut T;
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(self.idx), head_len),
+                slice::from_raw_parts_mut(ptr, self.len - head_len),
+            )
+        }
+    }
+
+    /// Returns an iterator over the elements in `range`, front to back.
+    ///
+    /// The range is resolved against the deque once, so iterating does no
+    /// further bounds checks or wrap-around arithmetic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is inverted or extends past the end of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4, 5, 6]);
+    /// assert!(deque.range(1..3).eq(&[4, 5]));
+    /// assert!(deque.range(2..).rev().eq(&[6, 5]));
+    /// ```
+    pub fn range<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let range = resolve_range(range, self.len);
+        let (front, back) = sub_slices(self.as_slices(), range.start, range.end);
+        front.iter().chain(back)
+    }
+
+    /// Returns an iterator that allows modifying the elements in `range`,
+    /// front to back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is inverted or extends past the end of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4, 5, 6]);
+    /// for value in deque.range_mut(..2) {
+    ///     *value = 0;
+    /// }
+    /// assert!(deque.iter().eq(&[0, 0, 5, 6]));
+    /// ```
+    pub fn range_mut<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+    ) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        let range = resolve_range(range, self.len);
+        let (front, back) = sub_slices_mut(self.as_mut_slices(), range.start, range.end);
+        front.iter_mut().chain(back)
+    }
+
+    /// Returns the free slots behind the back element as a pair of slices,
+    /// in the order that [`push_back`](Self::push_back) would fill them.
+    ///
+    /// The second slice is non-empty only when the free region wraps around
+    /// the end of the buffer. Together they hold `capacity() - len()` slots.
+    /// Write elements into them, then call [`advance_back`](Self::advance_back)
+    /// or [`advance_front`](Self::advance_front) to make them part of the
+    /// deque. Until then, writes are not visible and values written are
+    /// never dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.pop_front();
+    /// deque.pop_front();
+    /// // One slot is free at the end of the buffer and two at its start.
+    /// let (first, second) = deque.spare_capacity_mut();
+    /// assert_eq!((first.len(), second.len()), (1, 2));
+    /// first[0].write(5);
+    /// second[0].write(6);
+    /// unsafe { deque.advance_back(2) };
+    /// assert!(deque.iter().eq(&[3, 4, 5, 6]));
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let free = N - self.len;
+        let start = self.physical_index(self.len);
+        let head_len = (N - start).min(free);
+        let ptr = self.data.as_mut_ptr();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(start), head_len),
+                slice::from_raw_parts_mut(ptr, free - head_len),
+            )
+        }
+    }
+
+    /// Appends the first `n` free slots returned by
+    /// [`spare_capacity_mut`](Self::spare_capacity_mut) to the back of the
+    /// deque, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `capacity() - len()`.
+    ///
+    /// # Safety
+    ///
+    /// The first `n` slots of the spare capacity, counting through the first
+    /// slice and then the second, must hold initialized values of `T`. The
+    /// deque takes ownership of them and will drop them.
+    pub unsafe fn advance_back(&mut self, n: usize) {
+        assert!(
+            n <= N - self.len,
+            "cannot advance past the end of the free space"
+        );
+        self.len += n;
+        self.record_pushes(n, self.len - n);
+    }
+
+    /// Prepends the last `n` free slots returned by
+    /// [`spare_capacity_mut`](Self::spare_capacity_mut) to the front of the
+    /// deque, in order.
+    ///
+    /// The free region runs from just behind the back around to just before
+    /// the front, so its last `n` slots are the ones
+    /// [`push_front`](Self::push_front) would fill, and they become the first
+    /// `n` elements in the same order as they appear in the spare slices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `capacity() - len()`.
+    ///
+    /// # Safety
+    ///
+    /// The last `n` slots of the spare capacity, counting through the first
+    /// slice and then the second, must hold initialized values of `T`. The
+    /// deque takes ownership of them and will drop them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+    /// deque.extend([3, 4]);
+    /// let (first, second) = deque.spare_capacity_mut();
+    /// assert_eq!((first.len(), second.len()), (3, 0));
+    /// first[1].write(1);
+    /// first[2].write(2);
+    /// unsafe { deque.advance_front(2) };
+    /// assert!(deque.iter().eq(&[1, 2, 3, 4]));
+    /// ```
+    pub unsafe fn advance_front(&mut self, n: usize) {
+        assert!(
+            n <= N - self.len,
+            "cannot advance past the end of the free space"
+        );
+        // The new front lies `n` slots before the old one, which is at most
+        // `capacity()` away, so a single wrap brings it back into range.
+        self.idx = self.physical_index(N - n);
+        self.len += n;
+        self.record_pushes(n, self.len - n);
+    }
+
+    /// Clones the contents into a new `Vec` (front to back).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// dq.extend([1, 2, 3, 4]);
+    /// assert_eq!(dq.to_vec(), vec![2, 3, 4]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let (front, back) = self.as_slices();
+        let mut vec = Vec::with_capacity(self.len);
+        vec.extend_from_slice(front);
+        vec.extend_from_slice(back);
+        vec
+    }
+
+    /// Copies the contents into `dst` (front to back) without modifying the
+    /// deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst.len()` differs from the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// dq.extend([1, 2, 3, 4]);
+    /// let mut buf = [0; 3];
+    /// dq.copy_to_slice(&mut buf);
+    /// assert_eq!(buf, [2, 3, 4]);
+    /// ```
+    pub fn copy_to_slice(&self, dst: &mut [T])
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            dst.len(),
+            self.len,
+            "destination length does not match deque length"
+        );
+        let (front, back) = self.as_slices();
+        let (dst_front, dst_back) = dst.split_at_mut(front.len());
+        dst_front.copy_from_slice(front);
+        dst_back.copy_from_slice(back);
+    }
+
+    /// Clones the contents into `dst` (front to back) without modifying the
+    /// deque. The previous values in `dst` are dropped as they are overwritten.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst.len()` differs from the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<String, 3> = StackArrayDeque::new();
+    /// dq.extend(["a", "b", "c", "d"].map(String::from));
+    /// let mut buf = vec![String::new(); 3];
+    /// dq.clone_into_slice(&mut buf);
+    /// assert_eq!(buf, ["b", "c", "d"]);
+    /// ```
+    pub fn clone_into_slice(&self, dst: &mut [T])
+    where
+        T: Clone,
+    {
+        assert_eq!(
+            dst.len(),
+            self.len,
+            "destination length does not match deque length"
+        );
+        let (front, back) = self.as_slices();
+        let (dst_front, dst_back) = dst.split_at_mut(front.len());
+        dst_front.clone_from_slice(front);
+        dst_back.clone_from_slice(back);
+    }
+
+    /// Moves the contents of a full deque into an array (front to back).
+    ///
+    /// # Errors
+    ///
+    /// Returns the deque unchanged if it is not full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// dq.extend([1, 2]);
+    /// let mut dq = dq.into_array().unwrap_err();
+    /// dq.extend([3, 4]);
+    /// assert_eq!(dq.into_array().ok(), Some([2, 3, 4]));
+    /// ```
+    pub fn into_array(mut self) -> Result<[T; N], Self> {
+        if !self.is_full() {
+            return Err(self);
+        }
+        self.make_contiguous();
+        let deque = ManuallyDrop::new(self);
+        // SAFETY: the deque is full and contiguous from slot 0, so every slot
+        // holds an initialized element in logical order. The deque is never
+        // dropped, so each element is moved out exactly once.
+        Ok(unsafe { ptr::read(&deque.data as *const [MaybeUninit<T>; N] as *const [T; N]) })
+    }
+
+    /// Moves the contents into an array of `Option`s (front to back), with
+    /// `None` filling the slots past `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// dq.extend([1, 2]);
+    /// assert_eq!(dq.into_partial_array(), [Some(1), Some(2), None]);
+    /// ```
+    pub fn into_partial_array(mut self) -> [Option<T>; N] {
+        core::array::from_fn(|_| self.pop_front())
+    }
+
+    /// Consumes the deque and returns a deque of the same capacity holding
+    /// `f` applied to each element, in the same order.
+    ///
+    /// If `f` panics, the elements mapped so far and those not yet mapped are
+    /// all dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut raw: StackArrayDeque<u16, 4> = StackArrayDeque::new();
+    /// raw.extend([512, 1024, 2048]);
+    /// let millivolts: StackArrayDeque<u32, 4> = raw.map(|x| u32::from(x) * 3300 / 4096);
+    /// assert!(millivolts.iter().eq(&[412, 825, 1650]));
+    /// ```
+    pub fn map<U, F>(mut self, mut f: F) -> StackArrayDeque<U, N>
+    where
+        F: FnMut(T) -> U,
+    {
+        let mut mapped = StackArrayDeque::new();
+        while let Some(value) = self.pop_front() {
+            mapped.push_back(f(value));
+        }
+        #[cfg(feature = "stats")]
+        {
+            mapped.stats = self.stats;
+        }
+        mapped
+    }
+
+    /// Returns `true` if the deque contains an element equal to `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3]);
+    /// assert!(deque.contains(&2));
+    /// assert!(!deque.contains(&4));
+    /// ```
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+        front.contains(x) || back.contains(x)
+    }
+
+    /// Returns the index (0 is front) of the first element equal to `x`, or
+    /// `None` if there is no such element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 2]);
+    /// assert_eq!(deque.index_of(&2), Some(0));
+    /// assert_eq!(deque.index_of(&3), Some(1));
+    /// assert_eq!(deque.index_of(&1), None);
+    /// ```
+    pub fn index_of(&self, x: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+        front
+            .iter()
+            .position(|e| e == x)
+            .or_else(|| back.iter().position(|e| e == x).map(|i| i + front.len()))
+    }
+
+    /// Binary searches a sorted deque for `x`.
+    ///
+    /// Returns `Ok(i)` with the front-relative index of a matching element, or
+    /// `Err(i)` with the index where `x` could be inserted to keep the deque
+    /// sorted. If several elements match, any one of them may be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 3, 5, 7]);
+    /// assert_eq!(deque.binary_search(&5), Ok(2));
+    /// assert_eq!(deque.binary_search(&4), Err(2));
+    /// ```
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|e| e.cmp(x))
+    }
+
+    /// Binary searches a sorted deque with a comparator function.
+    ///
+    /// The comparator returns the ordering of each element relative to the
+    /// target. Results follow the same convention as [`binary_search`].
+    ///
+    /// [`binary_search`]: StackArrayDeque::binary_search
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 3, 5, 7]);
+    /// assert_eq!(deque.binary_search_by(|e| e.cmp(&7)), Ok(3));
+    /// assert_eq!(deque.binary_search_by(|e| e.cmp(&8)), Err(4));
+    /// ```
+    pub fn binary_search_by<'a, F>(&'a self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a T) -> Ordering,
+    {
+        let (front, back) = self.as_slices();
+        match back.first().map(&mut f) {
+            Some(Ordering::Equal) => Ok(front.len()),
+            Some(Ordering::Less) => back
+                .binary_search_by(f)
+                .map(|i| i + front.len())
+                .map_err(|i| i + front.len()),
+            _ => front.binary_search_by(f),
+        }
+    }
+
+    /// Binary searches a deque sorted by the key extracted with `f`.
+    ///
+    /// Results follow the same convention as [`binary_search`].
+    ///
+    /// [`binary_search`]: StackArrayDeque::binary_search
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<(i32, char), 3> = StackArrayDeque::new();
+    /// deque.extend([(1, 'a'), (2, 'b'), (4, 'c')]);
+    /// assert_eq!(deque.binary_search_by_key(&2, |&(k, _)| k), Ok(1));
+    /// assert_eq!(deque.binary_search_by_key(&3, |&(k, _)| k), Err(2));
+    /// ```
+    pub fn binary_search_by_key<'a, B, F>(&'a self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a T) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|e| f(e).cmp(b))
+    }
+
+    /// Returns the index of the partition point of a deque partitioned by
+    /// `pred`: the front-relative index of the first element for which `pred`
+    /// returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 8, 9]);
+    /// assert_eq!(deque.partition_point(|&x| x <= 3), 3);
+    /// ```
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (front, back) = self.as_slices();
+        if let Some(true) = back.first().map(&mut pred) {
+            back.partition_point(pred) + front.len()
+        } else {
+            front.partition_point(pred)
+        }
+    }
+
+    /// Inserts `value` into a sorted deque at its sorted position, after any
+    /// elements that compare equal to it.
+    ///
+    /// If the deque is full, the back (greatest) element is evicted to make
+    /// room. If `value` would itself be placed last in a full deque, it is
+    /// dropped instead and the deque is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.insert_sorted(5);
+    /// deque.insert_sorted(1);
+    /// deque.insert_sorted(3);
+    /// deque.insert_sorted(2); // evicts 5
+    /// deque.insert_sorted(9); // discarded
+    /// assert_eq!(deque[0], 1);
+    /// assert_eq!(deque[1], 2);
+    /// assert_eq!(deque[2], 3);
+    /// ```
+    pub fn insert_sorted(&mut self, value: T)
+    where
+        T: Ord,
+    {
+        let index = self.partition_point(|e| e <= &value);
+        if self.is_full() && index == self.len {
+            return;
+        }
+        self.insert(index, value);
+    }
+
+    /// Sorts the deque front to back in place, without preserving the order
+    /// of equal elements. Does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([3, 1, 4, 1, 5]);
+    /// deque.sort_unstable();
+    /// assert_eq!(deque.as_slices(), (&[1, 1, 4, 5][..], &[][..]));
+    /// ```
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.make_contiguous().sort_unstable();
+    }
+
+    /// Sorts the deque front to back in place with a comparator function,
+    /// without preserving the order of equal elements. Does not allocate.
+    ///
+    /// If `compare` panics, the deque is left holding all of its elements in
+    /// an unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 3, 2]);
+    /// deque.sort_unstable_by(|a, b| b.cmp(a));
+    /// assert_eq!(deque.as_slices(), (&[3, 2, 1][..], &[][..]));
+    /// ```
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.make_contiguous().sort_unstable_by(compare);
+    }
+
+    /// Returns the maximum capacity of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let deque: StackArrayDeque<i32, 10> = StackArrayDeque::new();
+    /// assert_eq!(deque.capacity(), 10);
+    /// ```
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements currently in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// assert_eq!(deque.len(), 0);
+    /// deque.push_back(1);
+    /// assert_eq!(deque.len(), 1);
+    /// ```
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// assert!(deque.is_empty());
+    /// deque.push_back(1);
+    /// assert!(!deque.is_empty());
+    /// ```
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the deque has reached its maximum capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 2> = StackArrayDeque::new();
+    /// assert!(!deque.is_full());
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    /// assert!(deque.is_full());
+    /// ```
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the number of elements stored by pushes, inserts, extends and
+    /// writes since the deque was created or the statistics were last reset,
+    /// including elements that have since been overwritten or removed.
+    ///
+    /// Pushes that store nothing, such as a failed `try_push_back`, are not
+    /// counted. The counter wraps around on overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4, 5]);
+    /// assert_eq!(deque.total_pushed(), 5);
+    /// assert_eq!(deque.total_evicted(), 2);
+    /// assert_eq!(deque.max_len_seen(), 3);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn total_pushed(&self) -> u64 {
+        self.stats.pushed
+    }
+
+    /// Returns the number of elements that were overwritten to make room for
+    /// new ones since the deque was created or the statistics were last reset.
+    ///
+    /// Elements removed by pops, [`clear`](Self::clear) or truncation are not
+    /// evictions. The counter wraps around on overflow.
+    #[cfg(feature = "stats")]
+    pub fn total_evicted(&self) -> u64 {
+        self.stats.evicted
+    }
+
+    /// Returns the largest number of elements the deque has held since it was
+    /// created or the statistics were last reset.
+    #[cfg(feature = "stats")]
+    pub fn max_len_seen(&self) -> usize {
+        self.stats.max_len.max(self.len)
+    }
+
+    /// Resets [`total_pushed`](Self::total_pushed) and
+    /// [`total_evicted`](Self::total_evicted) to zero, and
+    /// [`max_len_seen`](Self::max_len_seen) to the current length.
+    ///
+    /// The statistics are only ever reset by this method; in particular,
+    /// [`clear`](Self::clear) leaves them untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.clear();
+    /// assert_eq!(deque.total_pushed(), 4);
+    /// deque.reset_stats();
+    /// deque.push_back(5);
+    /// assert_eq!(deque.total_pushed(), 1);
+    /// assert_eq!(deque.total_evicted(), 0);
+    /// assert_eq!(deque.max_len_seen(), 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.stats = Stats::new();
+    }
+
+    /// Removes all elements from the deque.
+    ///
+    /// This operation properly drops all contained elements and resets
+    /// the deque to an empty state.
+    ///
+    /// With the `stats` feature, the counters are not reset; use
+    /// `reset_stats` for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    /// deque.clear();
+    /// assert!(deque.is_empty());
+    /// assert_eq!(deque.len(), 0);
+    /// ```
+    pub fn clear(&mut self) {
+        for i in 0..self.len {
+            let idx = self.physical_index(i);
+            unsafe {
+                self.data[idx].assume_init_drop();
+            }
+        }
+        self.len = 0;
+        self.idx = 0;
+    }
+
+    /// Shortens the deque, keeping the first `len` elements and dropping the
+    /// rest from the back.
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.truncate(2);
+    /// assert_eq!(deque.len(), 2);
+    /// assert_eq!(deque.back(), Some(&2));
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let old_len = self.len;
+        self.len = len;
+        for i in len..old_len {
+            let idx = self.physical_index(i);
+            unsafe {
+                self.data[idx].assume_init_drop();
+            }
+        }
+    }
+
+    /// Shortens the deque, keeping the last `len` elements and dropping the
+    /// rest from the front.
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// deque.truncate_front(2);
+    /// assert_eq!(deque.len(), 2);
+    /// assert_eq!(deque.front(), Some(&3));
+    /// ```
+    pub fn truncate_front(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let removed = self.len - len;
+        let old_idx = self.idx;
+        self.idx = self.physical_index(removed);
+        self.len = len;
+        for i in 0..removed {
+            unsafe {
+                self.data[self.wrap_add(old_idx, i)].assume_init_drop();
+            }
+        }
+    }
+
+    /// Drops the current contents and fills every slot with a clone of `value`,
+    /// leaving the deque full.
+    ///
+    /// If cloning `value` panics, the elements written so far are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// deque.push_back(1);
+    /// deque.fill(0);
+    /// assert!(deque.is_full());
+    /// assert_eq!(deque[2], 0);
+    /// ```
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.clear();
+        if N == 0 {
+            return;
+        }
+        for i in 0..N - 1 {
+            self.data[i].write(value.clone());
+            // Counting each slot as it is written keeps the deque droppable
+            // if the next clone panics.
+            self.len += 1;
+        }
+        self.data[N - 1].write(value);
+        self.len = N;
+        self.record_pushes(N, 0);
+    }
+
+    /// Drops the current contents and fills every slot with the result of
+    /// calling `f`, front to back, leaving the deque full.
+    ///
+    /// If `f` panics, the elements written so far are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// let mut next = 0;
+    /// deque.fill_with(|| {
+    ///     next += 1;
+    ///     next
+    /// });
+    /// assert_eq!(deque.back(), Some(&3));
+    /// ```
+    pub fn fill_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        self.clear();
+        for i in 0..N {
+            self.data[i].write(f());
+            self.len += 1;
+        }
+        self.record_pushes(N, 0);
+    }
+
+    /// Resizes the deque to `new_len` elements, dropping elements from the back
+    /// or appending clones of `value` as needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` exceeds the capacity `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.push_back(1);
+    /// deque.resize(3, 0);
+    /// assert_eq!(deque.len(), 3);
+    /// deque.resize(1, 0);
+    /// assert_eq!(deque.back(), Some(&1));
+    /// ```
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        assert!(new_len <= N, "new_len exceeds capacity");
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
+        while self.len + 1 < new_len {
+            self.push_back(value.clone());
+        }
+        self.push_back(value);
+    }
+
+    /// Prepends every item of `iter`, so that the first item ends up at index 0
+    /// and the existing elements follow the new ones in their original order.
+    ///
+    /// If the result would exceed `N`, elements are evicted from the back as
+    /// with [`push_front`](Self::push_front): existing elements go first, then
+    /// any items beyond the first `N`, which are consumed and dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// dq.extend([4, 5]);
+    /// dq.extend_front([1, 2, 3]);
+    /// assert_eq!(dq.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    /// ```
+    pub fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        let mut count = 0;
+        while count < N {
+            let Some(item) = iter.next() else { break };
+            self.push_front(item);
+            count += 1;
+        }
+        // Pushing to the front reversed the new items; put them back in order.
+        let (mut i, mut j) = (0, count);
+        while i + 1 < j {
+            j -= 1;
+            self.swap(i, j);
+            i += 1;
+        }
+        let dropped = iter.map(drop).count();
+        self.record_pushes(dropped, self.len);
+    }
+
+    /// Clones every element of `slice` onto the back of the deque.
+    ///
+    /// As with repeated [`push_back`](Self::push_back), the oldest elements are
+    /// overwritten once the deque is full; if `slice` is longer than `N`, only
+    /// its last `N` elements are cloned. For `Copy` element types,
+    /// [`extend_from_copy_slice`](Self::extend_from_copy_slice) does the same
+    /// with at most two memory copies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<u8, 4> = StackArrayDeque::new();
+    /// deque.extend_from_slice(b"abc");
+    /// deque.extend_from_slice(b"de");
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), b"bcde");
+    /// ```
+    pub fn extend_from_slice(&mut self, slice: &[T])
+    where
+        T: Clone,
+    {
+        let Some((slice, old_len, pushed)) = self.make_room_for(slice) else {
+            return;
+        };
+        self.write_cloned(slice);
+        self.record_pushes(pushed, old_len);
+    }
+
+    /// Copies every element of `slice` onto the back of the deque.
+    ///
+    /// Behaves exactly like [`extend_from_slice`](Self::extend_from_slice),
+    /// but since `T` is `Copy` the elements are written with at most two
+    /// `memcpy`s, one on each side of the wrap point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut millivolts: StackArrayDeque<u16, 4> = StackArrayDeque::new();
+    /// millivolts.extend_from_copy_slice(&[3300, 3290, 3310]);
+    /// millivolts.extend_from_copy_slice(&[3305, 3295]);
+    /// assert!(millivolts.iter().eq(&[3290, 3310, 3305, 3295]));
+    /// ```
+    pub fn extend_from_copy_slice(&mut self, slice: &[T])
+    where
+        T: Copy,
+    {
+        let Some((slice, old_len, pushed)) = self.make_room_for(slice) else {
+            return;
+        };
+        self.copy_in(slice);
+        self.record_pushes(pushed, old_len);
+    }
+
+    /// Evicts from the front to free space at the back for `slice`.
+    ///
+    /// Returns the part of `slice` that will be kept, together with the length
+    /// before any eviction and the number of elements to count as pushed, or
+    /// `None` if nothing is to be written.
+    fn make_room_for<'s>(&mut self, slice: &'s [T]) -> Option<(&'s [T], usize, usize)> {
+        if slice.is_empty() || N == 0 {
+            return None;
+        }
+        let (old_len, pushed) = (self.len, slice.len());
+        let slice = if slice.len() >= N {
+            self.clear();
+            &slice[slice.len() - N..]
+        } else {
+            self.truncate_front(N - slice.len());
+            slice
+        };
+        Some((slice, old_len, pushed))
+    }
+
+    /// Clones every element of `slice` onto the back of the deque without
+    /// overwriting anything.
+    ///
+    /// # Errors
+    ///
+    /// If `slice` is longer than the free space, nothing is written and the
+    /// number of elements that would have fit is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<u8, 4> = StackArrayDeque::new();
+    /// assert_eq!(deque.try_extend_from_slice(b"abc"), Ok(()));
+    /// assert_eq!(deque.try_extend_from_slice(b"de"), Err(1));
+    /// assert_eq!(deque.len(), 3);
+    /// ```
+    pub fn try_extend_from_slice(&mut self, slice: &[T]) -> Result<(), usize>
+    where
+        T: Clone,
+    {
+        let free = N - self.len;
+        if slice.len() > free {
+            return Err(free);
+        }
+        self.write_cloned(slice);
+        self.record_pushes(slice.len(), self.len - slice.len());
+        Ok(())
+    }
+
+    /// Clones `src` into the free slots after the back element, splitting the
+    /// copy at the end of the array. Each slot is counted as it is written so
+    /// a panicking clone leaks nothing. The caller must ensure `src` fits.
+    fn write_cloned(&mut self, src: &[T])
+    where
+        T: Clone,
+    {
+        debug_assert!(self.len + src.len() <= N);
+        if src.is_empty() {
+            return;
+        }
+        let start = self.physical_index(self.len);
+        let (head, tail) = src.split_at((N - start).min(src.len()));
+        for (slot, item) in self.data[start..].iter_mut().zip(head) {
+            slot.write(item.clone());
+            self.len += 1;
+        }
+        for (slot, item) in self.data.iter_mut().zip(tail) {
+            slot.write(item.clone());
+            self.len += 1;
+        }
+    }
+
+    /// Copies `src` into the free slots after the back element, splitting the
+    /// copy at the end of the array. The caller must ensure `src` fits.
+    fn copy_in(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        debug_assert!(self.len + src.len() <= N);
+        let start = self.physical_index(self.len);
+        let (head, tail) = src.split_at((N - start).min(src.len()));
+        let ptr = self.data.as_mut_ptr() as *mut T;
+        unsafe {
+            ptr::copy_nonoverlapping(head.as_ptr(), ptr.add(start), head.len());
+            ptr::copy_nonoverlapping(tail.as_ptr(), ptr, tail.len());
+        }
+        self.len += src.len();
+    }
+
+    /// Moves all elements of `other` to the back of this deque, leaving `other`
+    /// empty.
+    ///
+    /// If the combined length exceeds `N`, the oldest elements are dropped
+    /// first (from the front of `self`, then from the front of `other`), just
+    /// as if each element of `other` had been pushed with
+    /// [`push_back`](Self::push_back). Use [`try_append`](Self::try_append) to
+    /// reject the transfer instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut a: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// let mut b: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// a.extend([1, 2]);
+    /// b.extend([3, 4, 5]);
+    /// a.append(&mut b);
+    /// assert!(b.is_empty());
+    /// assert_eq!(a.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5]);
+    /// ```
+    pub fn append<const M: usize>(&mut self, other: &mut StackArrayDeque<T, M>) {
+        let (old_len, pushed) = (self.len, other.len);
+        other.truncate_front(N);
+        let overflow = (self.len + other.len).saturating_sub(N);
+        self.truncate_front(self.len - overflow);
+        self.move_from(other);
+        self.record_pushes(pushed, old_len);
+    }
+
+    /// Moves all elements of `other` to the back of this deque, leaving `other`
+    /// empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CapacityError`] and leaves both deques unchanged if the
+    /// combined length exceeds `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut a: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// let mut b: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// a.extend([1, 2]);
+    /// b.extend([3, 4]);
+    /// assert!(a.try_append(&mut b).is_err());
+    /// b.pop_back();
+    /// assert!(a.try_append(&mut b).is_ok());
+    /// assert!(a.is_full());
+    /// ```
+    pub fn try_append<const M: usize>(
+        &mut self,
+        other: &mut StackArrayDeque<T, M>,
+    ) -> Result<(), CapacityError> {
+        if self.len + other.len > N {
+            return Err(CapacityError {
+                len: self.len + other.len,
+                capacity: N,
+            });
+        }
+        let pushed = other.len;
+        self.move_from(other);
+        self.record_pushes(pushed, self.len - pushed);
+        Ok(())
+    }
+
+    /// Splits the deque in two at `at`, returning a new deque holding the
+    /// elements from `at` to the back. `self` keeps the elements `[0, at)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    /// let tail = deque.split_off(1);
+    /// assert_eq!(deque.len(), 1);
+    /// assert_eq!(tail.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "Index out of bounds");
+        let mut tail = Self::new();
+        for i in at..self.len {
+            let value = unsafe { self.data[self.physical_index(i)].assume_init_read() };
+            tail.data[i - at].write(value);
+        }
+        tail.len = self.len - at;
+        self.len = at;
+        tail
+    }
+
+    /// Moves every element of `other` to the back of `self`, leaving `other`
+    /// empty. The caller must ensure the combined length fits in `N`.
+    fn move_from<const M: usize>(&mut self, other: &mut StackArrayDeque<T, M>) {
+        debug_assert!(self.len + other.len <= N);
+        for i in 0..other.len {
+            let value = unsafe { other.data[other.physical_index(i)].assume_init_read() };
+            let to = self.physical_index(self.len + i);
+            self.data[to].write(value);
+        }
+        self.len += other.len;
+        other.len = 0;
+        other.idx = 0;
+    }
+
+    /// Rearranges the buffer so the contents occupy a single contiguous run
+    /// and returns it as a slice.
+    fn make_contiguous(&mut self) -> &mut [T] {
+        if self.idx + self.len > N {
+            // Rotating the whole array only moves bytes around, so the
+            // uninitialized slots can be shuffled along with the live ones.
+            self.data.rotate_left(self.idx);
+            self.idx = 0;
+        }
+        let ptr = self.data.as_mut_ptr() as *mut T;
+        unsafe { slice::from_raw_parts_mut(ptr.add(self.idx), self.len) }
+    }
+
+    /// Maps a logical index (0 is front) to its slot in the buffer.
+    /// Updates the `stats` counters after `pushed` elements were stored in a
+    /// deque that held `old_len` elements. Whatever the length fell short of
+    /// `old_len + pushed` is counted as evicted.
+    #[inline]
+    fn record_pushes(&mut self, pushed: usize, old_len: usize) {
+        #[cfg(feature = "stats")]
+        self.stats
+            .record(pushed, old_len + pushed - self.len, self.len);
+        #[cfg(not(feature = "stats"))]
+        let _ = (pushed, old_len);
+    }
+
+    #[inline]
+    fn physical_index(&self, index: usize) -> usize {
+        self.wrap_add(self.idx, index)
+    }
+
+    /// Returns the slot `b` places after slot `a`, where `a < N` and
+    /// `b <= N`, with a compare and subtract rather than `%`.
+    #[inline]
+    fn wrap_add(&self, a: usize, b: usize) -> usize {
+        let idx = a + b;
+        if idx >= N { idx - N } else { idx }
+    }
+
+    /// Returns the slot `b` places before slot `a`, where `a < N` and
+    /// `b <= N`.
+    #[inline]
+    fn wrap_sub(&self, a: usize, b: usize) -> usize {
+        if a >= b { a - b } else { a + N - b }
+    }
+
+    /// Maps a position counted from the back (0 is back) to its slot, or
+    /// returns `None` if it is out of bounds.
+    #[inline]
+    fn back_slot(&self, index: usize) -> Option<usize> {
+        (index < self.len).then(|| self.physical_index(self.len - 1 - index))
+    }
+
+    /// Moves `n` elements from the front to the back, one slot at a time.
+    fn rotate_left_inner(&mut self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        if self.is_full() {
+            self.idx = self.wrap_add(self.idx, n);
+            return;
+        }
+        for _ in 0..n {
+            unsafe { self.move_slot(0, self.len) };
+            self.idx = self.wrap_add(self.idx, 1);
+        }
+    }
+
+    /// Moves `n` elements from the back to the front, one slot at a time.
+    fn rotate_right_inner(&mut self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        if self.is_full() {
+            self.idx = self.wrap_sub(self.idx, n);
+            return;
+        }
+        for _ in 0..n {
+            self.idx = self.wrap_sub(self.idx, 1);
+            unsafe { self.move_slot(self.len, 0) };
+        }
+    }
+
+    /// Moves the element at logical index `from` into logical index `to`.
+    ///
+    /// # Safety
+    ///
+    /// `from` must hold an initialized element, and the caller must treat it as
+    /// uninitialized afterwards. Whatever was in `to` is overwritten without
+    /// being dropped.
+    #[inline]
+    unsafe fn move_slot(&mut self, from: usize, to: usize) {
+        let value = unsafe { self.data[self.physical_index(from)].assume_init_read() };
+        let to = self.physical_index(to);
+        self.data[to].write(value);
+    }
+}
+
+impl<const N: usize> StackArrayDeque<u8, N> {
+    /// Copies as many bytes of `src` as fit into the free space at the back,
+    /// never overwriting buffered bytes, and returns how many were copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut fifo: StackArrayDeque<u8, 4> = StackArrayDeque::new();
+    /// assert_eq!(fifo.push_slice(b"abc"), 3);
+    /// assert_eq!(fifo.push_slice(b"def"), 1);
+    /// assert!(fifo.iter().eq(b"abcd"));
+    /// ```
+    pub fn push_slice(&mut self, src: &[u8]) -> usize {
+        let n = src.len().min(N - self.len);
+        self.copy_in(&src[..n]);
+        self.record_pushes(n, self.len - n);
+        n
+    }
+
+    /// Copies all of `src` onto the back, discarding the oldest bytes to make
+    /// room as [`push_back`](Self::push_back) would. If `src` is longer than
+    /// `N`, only its last `N` bytes are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut ring: StackArrayDeque<u8, 4> = StackArrayDeque::new();
+    /// ring.push_slice_overwrite(b"abc");
+    /// ring.push_slice_overwrite(b"de");
+    /// assert!(ring.iter().eq(b"bcde"));
+    /// ring.push_slice_overwrite(b"fghijk");
+    /// assert!(ring.iter().eq(b"hijk"));
+    /// ```
+    pub fn push_slice_overwrite(&mut self, src: &[u8]) {
+        self.extend_from_copy_slice(src);
+    }
+
+    /// Moves bytes from the front into `dst` until either it is full or the
+    /// deque is empty, and returns how many were moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut fifo: StackArrayDeque<u8, 4> = StackArrayDeque::new();
+    /// fifo.push_slice(b"abc");
+    /// let mut buf = [0; 2];
+    /// assert_eq!(fifo.pop_slice(&mut buf), 2);
+    /// assert_eq!(&buf, b"ab");
+    /// assert_eq!(fifo.pop_slice(&mut buf), 1);
+    /// assert_eq!(fifo.pop_slice(&mut buf), 0);
+    /// ```
+    pub fn pop_slice(&mut self, dst: &mut [u8]) -> usize {
+        let n = dst.len().min(self.len);
+        let (front, back) = self.as_slices();
+        let split = n.min(front.len());
+        dst[..split].copy_from_slice(&front[..split]);
+        dst[split..n].copy_from_slice(&back[..n - split]);
+        self.truncate_front(self.len - n);
+        n
+    }
+
+    /// Returns the contents as a string slice, rearranging the buffer so it is
+    /// contiguous.
+    ///
+    /// Meant for deques filled through [`fmt::Write`], where overwriting the
+    /// oldest bytes can cut a multi-byte character in half: any continuation
+    /// bytes at the front are skipped, and the text is cut short at the first
+    /// invalid UTF-8 sequence, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    /// use core::fmt::Write;
+    ///
+    /// let mut log: StackArrayDeque<u8, 4> = StackArrayDeque::new();
+    /// write!(log, "1°C!").unwrap();
+    /// assert_eq!(log.as_str_lossy(), "°C!");
+    /// write!(log, "!").unwrap();
+    /// // Only the last byte of "°" survived; it is skipped.
+    /// assert_eq!(log.as_str_lossy(), "C!!");
+    /// ```
+    pub fn as_str_lossy(&mut self) -> &str {
+        let bytes = self.make_contiguous();
+        let partial = bytes
+            .iter()
+            .take(3)
+            .take_while(|&&b| b & 0xC0 == 0x80)
+            .count();
+        let bytes = &bytes[partial..];
+        match core::str::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(err) => unsafe { core::str::from_utf8_unchecked(&bytes[..err.valid_up_to()]) },
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for StackArrayDeque<T, N> {
+    /// Properly drops all contained elements.
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for StackArrayDeque<T, N> {
+    /// Creates an empty deque.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StackArrayDeque<T, N> {
+    /// Formats the deque as a debug list showing all elements from front to back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format, const N: usize> defmt::Format for StackArrayDeque<T, N> {
+    /// Formats the deque as a list (front to back), like its `Debug` output.
+    fn format(&self, fmt: defmt::Formatter<'_>) {
+        defmt::write!(fmt, "[");
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                defmt::write!(fmt, ", ");
+            }
+            defmt::write!(fmt, "{}", item);
+        }
+        defmt::write!(fmt, "]");
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for StackArrayDeque<T, N> {
+    /// Creates a deep copy of the deque with the same capacity and elements.
+    ///
+    /// The two contiguous halves are cloned straight into the front of the new
+    /// buffer, which compiles to a memcpy for `Copy` types. If an element's
+    /// `clone` panics, the elements cloned so far are dropped.
+    fn clone(&self) -> Self {
+        let mut new = StackArrayDeque::new();
+        let (front, back) = self.as_slices();
+        new.write_cloned(front);
+        new.write_cloned(back);
+        #[cfg(feature = "stats")]
+        {
+            new.stats = self.stats;
+        }
+        new
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for StackArrayDeque<T, N> {
+    /// Compares two deques for equality based on their elements and order.
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for StackArrayDeque<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for StackArrayDeque<T, N> {
+    /// Compares the deques lexicographically from front to back, with the
+    /// shorter deque ordered first when one is a prefix of the other.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for StackArrayDeque<T, N> {
+    /// Compares the deques lexicographically from front to back, with the
+    /// shorter deque ordered first when one is a prefix of the other.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for StackArrayDeque<T, N> {
+    /// Hashes the length followed by each element from front to back, so equal
+    /// deques hash identically regardless of where the buffer wraps.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        self.iter().for_each(|item| item.hash(state));
+    }
+}
+
+impl<T, const N: usize> Index<usize> for StackArrayDeque<T, N> {
+    type Output = T;
+
+    /// Provides indexed access to elements in the deque.
+    ///
+    /// Index 0 corresponds to the front element, index 1 to the second element, etc.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds (>= len()).
+    fn index(&self, i: usize) -> &Self::Output {
+        assert!(i < self.len);
+        let idx = self.physical_index(i);
+        unsafe { self.data[idx].assume_init_ref() }
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for StackArrayDeque<T, N> {
+    /// Provides mutable indexed access to elements in the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds (>= len()).
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        assert!(i < self.len);
+        let idx = self.physical_index(i);
+        unsafe { self.data[idx].assume_init_mut() }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize, const N: usize> Serialize for StackArrayDeque<T, N> {
+    /// Serializes the deque as a sequence of its elements from front to back.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        for item in self.iter() {
+            seq.serialize_element(item)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for StackArrayDeque<T, N> {
+    /// Deserializes a sequence into a `StackArrayDeque`, erroring if it exceeds capacity.
+    ///
+    /// Elements are written straight into the inline buffer, so this needs
+    /// neither `std` nor a heap allocator.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(StackArrayDequeVisitor(PhantomData))
+    }
+}
+
+/// Visits a sequence of at most `N` elements.
+#[cfg(feature = "serde")]
+struct StackArrayDequeVisitor<T, const N: usize>(PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for StackArrayDequeVisitor<T, N> {
+    type Value = StackArrayDeque<T, N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of at most {N} elements")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        if let Some(len) = seq.size_hint().filter(|&len| len > N) {
+            return Err(de::Error::invalid_length(len, &self));
+        }
+        // Elements already pushed are dropped along with `deque` if a later
+        // one fails to deserialize.
+        let mut deque = StackArrayDeque::new();
+        while let Some(item) = seq.next_element()? {
+            if deque.is_full() {
+                return Err(de::Error::invalid_length(N + 1, &self));
+            }
+            deque.push_back(item);
+        }
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<T: Encode, const N: usize> Encode for StackArrayDeque<T, N> {
+    /// Encodes the length followed by the elements from front to back.
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        (self.len as u64).encode(encoder)?;
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the length prefix written by `Encode`, failing with
+/// [`DecodeError::ArrayLengthMismatch`] if it exceeds `N`.
+#[cfg(feature = "bincode")]
+fn bincode_decode_len<const N: usize, D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
+    let len = u64::decode(decoder)?;
+    match usize::try_from(len) {
+        Ok(len) if len <= N => Ok(len),
+        _ => Err(DecodeError::ArrayLengthMismatch {
+            required: N,
+            found: usize::try_from(len).unwrap_or(usize::MAX),
+        }),
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<Context, T: Decode<Context>, const N: usize> Decode<Context> for StackArrayDeque<T, N> {
+    /// Decodes a deque, erroring if the encoded length exceeds `N`.
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = bincode_decode_len::<N, _>(decoder)?;
+        let mut deque = StackArrayDeque::new();
+        for _ in 0..len {
+            deque.push_back(T::decode(decoder)?);
+        }
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<'de, Context, T: BorrowDecode<'de, Context>, const N: usize> BorrowDecode<'de, Context>
+    for StackArrayDeque<T, N>
+{
+    /// Decodes a deque, erroring if the encoded length exceeds `N`.
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let len = bincode_decode_len::<N, _>(decoder)?;
+        let mut deque = StackArrayDeque::new();
+        for _ in 0..len {
+            deque.push_back(T::borrow_decode(decoder)?);
+        }
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T, const N: usize> StackArrayDeque<T, N> {
+    /// Overwrites every slot not currently holding an element with zeros.
+    ///
+    /// Removing or moving elements does not scrub the slots they leave behind:
+    /// after [`pop_front`](Self::pop_front), [`truncate`](Self::truncate),
+    /// [`rotate_left`](Self::rotate_left) and similar operations,
+    /// stale copies of the old values stay in the array until overwritten.
+    /// Call this afterwards to wipe them. Overwriting pushes replace the
+    /// evicted element in place and leave no copy behind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut nonces: StackArrayDeque<[u8; 32], 4> = StackArrayDeque::new();
+    /// nonces.push_back([1; 32]);
+    /// nonces.push_back([2; 32]);
+    /// nonces.pop_front();
+    /// nonces.zeroize_spare_capacity();
+    /// assert_eq!(nonces.front(), Some(&[2; 32]));
+    /// ```
+    pub fn zeroize_spare_capacity(&mut self) {
+        for i in self.len..N {
+            self.data[self.physical_index(i)].zeroize();
+        }
+    }
+}
+
+/// Zeroizes every element, empties the deque, and then wipes the whole array
+/// with [`zeroize_spare_capacity`](StackArrayDeque::zeroize_spare_capacity),
+/// so stale copies left by earlier operations are cleared as well.
+///
+/// To scrub a deque automatically when it is dropped, wrap it in
+/// [`zeroize::Zeroizing`].
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::StackArrayDeque;
+/// use zeroize::{Zeroize, Zeroizing};
+///
+/// let mut shares: StackArrayDeque<[u8; 32], 4> = StackArrayDeque::new();
+/// shares.push_back([7; 32]);
+/// shares.zeroize();
+/// assert!(shares.is_empty());
+///
+/// let mut recent = Zeroizing::new(StackArrayDeque::<[u8; 32], 4>::new());
+/// recent.push_back([9; 32]);
+/// // Zeroized here, as `recent` goes out of scope.
+/// ```
+#[cfg(feature = "zeroize")]
+impl<T: Zeroize, const N: usize> Zeroize for StackArrayDeque<T, N> {
+    /// Zeroizes all elements and the spare capacity, leaving the deque empty.
+    fn zeroize(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back).for_each(Zeroize::zeroize);
+        self.clear();
+        self.zeroize_spare_capacity();
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: Arbitrary<'a>, const N: usize> Arbitrary<'a> for StackArrayDeque<T, N> {
+    /// Generates a deque with an arbitrary front position, so that wrapped
+    /// layouts are exercised, and as many elements as the input allows.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut deque = StackArrayDeque::new();
+        if N > 0 {
+            deque.idx = u.int_in_range(0..=N - 1)?;
+        }
+        let len = u.arbitrary_len::<T>()?.min(N);
+        for _ in 0..len {
+            deque.push_back(T::arbitrary(u)?);
+        }
+        Ok(deque)
+    }
+
+    /// Bounds the number of input bytes consumed by [`arbitrary`](Self::arbitrary).
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Self::try_size_hint(depth).unwrap_or_default()
+    }
+
+    /// Like [`size_hint`](Self::size_hint), but reports hitting the recursion
+    /// limit.
+    fn try_size_hint(depth: usize) -> Result<(usize, Option<usize>), MaxRecursionReached> {
+        size_hint::try_recursion_guard(depth, |depth| {
+            let (_, element) = T::try_size_hint(depth)?;
+            let header = (0, Some(ARBITRARY_HEADER_BYTES));
+            let elements = (0, element.map(|e| e * N));
+            Ok(size_hint::and(header, elements))
+        })
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T: quickcheck::Arbitrary, const N: usize> quickcheck::Arbitrary for StackArrayDeque<T, N> {
+    /// Generates a deque with an arbitrary fill level and front position, so
+    /// that wrapped layouts are exercised.
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let len = <usize as quickcheck::Arbitrary>::arbitrary(g) % (N + 1);
+        let mut deque = StackArrayDeque::new();
+        if N > 0 {
+            deque.idx = <usize as quickcheck::Arbitrary>::arbitrary(g) % N;
+        }
+        for _ in 0..len {
+            deque.push_back(<T as quickcheck::Arbitrary>::arbitrary(g));
+        }
+        deque
+    }
+
+    /// Yields the same contents unwrapped, then deques with shrunk contents.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let from_vec = |items: Vec<T>| {
+            let mut deque = StackArrayDeque::new();
+            deque.extend(items);
+            deque
+        };
+        let items = self.to_vec();
+        let unwrapped = (self.idx != 0).then(|| from_vec(items.clone()));
+        Box::new(unwrapped.into_iter().chain(items.shrink().map(from_vec)))
+    }
+}
+
+/// Formatted text is appended as UTF-8 bytes with the usual overwrite
+/// semantics, so the deque always holds the most recent `N` bytes of output.
+/// Use [`as_str_lossy`](StackArrayDeque::as_str_lossy) to read it back.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::StackArrayDeque;
+/// use core::fmt::Write;
+///
+/// let mut log: StackArrayDeque<u8, 8> = StackArrayDeque::new();
+/// for t in [19, 20, 21] {
+///     write!(log, "temp={t};").unwrap();
+/// }
+/// assert_eq!(log.as_str_lossy(), "temp=21;");
+/// ```
+impl<const N: usize> fmt::Write for StackArrayDeque<u8, N> {
+    /// Appends the bytes of `s`, discarding the oldest bytes if needed.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_slice_overwrite(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::ErrorType for StackArrayDeque<u8, N> {
+    type Error = crate::FullError;
+}
+
+/// Reads pop bytes from the front of the deque. Reading from an empty deque
+/// returns `Ok(0)`.
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::Read for StackArrayDeque<u8, N> {
+    /// Moves up to `buf.len()` bytes from the front of the deque into `buf`.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.pop_slice(buf))
+    }
+}
+
+/// Writes append bytes to the back of the deque without ever overwriting
+/// buffered data, accepting only as many bytes as there is free space for.
+///
+/// Writing a non-empty buffer to a full deque fails with
+/// [`FullError`](crate::FullError), whose kind is
+/// [`WriteZero`](embedded_io::ErrorKind::WriteZero), so `write_all` returns
+/// an error if the deque fills up before everything is written. Reads never
+/// fail.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "embedded-io")] {
+/// use array_deque::StackArrayDeque;
+/// use embedded_io::{Read, Write, WriteReady};
+///
+/// let mut fifo: StackArrayDeque<u8, 4> = StackArrayDeque::new();
+/// assert_eq!(fifo.write(b"abcdef"), Ok(4));
+/// assert_eq!(fifo.write_ready(), Ok(false));
+/// assert_eq!(fifo.write(b"g"), Err(array_deque::FullError));
+/// let mut buf = [0; 8];
+/// assert_eq!(fifo.read(&mut buf), Ok(4));
+/// assert_eq!(&buf[..4], b"abcd");
+/// # }
+/// ```
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::Write for StackArrayDeque<u8, N> {
+    /// Appends as much of `buf` as fits and returns how many bytes that was,
+    /// failing if `buf` is non-empty and no byte fits.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        match self.push_slice(buf) {
+            0 if !buf.is_empty() => Err(crate::FullError),
+            n => Ok(n),
+        }
+    }
+
+    /// Does nothing, as there is nothing to flush.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::ReadReady for StackArrayDeque<u8, N> {
+    /// Returns `true` if there is at least one byte to read.
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_empty())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::WriteReady for StackArrayDeque<u8, N> {
+    /// Returns `true` if there is room for at least one more byte.
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_full())
+    }
+}
+
+impl<T, const N: usize> Extend<T> for StackArrayDeque<T, N> {
+    /// Extends the deque with items from an iterator, pushing to the back.
+    ///
+    /// When the iterator reports an exact length (`size_hint` with equal bounds)
+    /// of at least `N`, the result is known up front: the current contents are
+    /// dropped, the leading items that would only be overwritten are consumed
+    /// and dropped without being written into the buffer, and the last `N`
+    /// items are stored. Every item is still produced exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+    /// dq.extend([1,2,3]);
+    /// assert_eq!(dq.len(), 3);
+    /// ```
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        if let (lower, Some(upper)) = iter.size_hint()
+            && lower == upper
+            && lower >= N
+        {
+            let old_len = self.len;
+            self.clear();
+            if lower > N {
+                iter.nth(lower - N - 1);
+            }
+            // The skipped items count as pushed and immediately overwritten.
+            self.record_pushes(lower - N, old_len);
+        }
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, const N: usize> Extend<&'a T> for StackArrayDeque<T, N> {
+    /// Extends the deque by pushing a copy of each referenced item to the back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let mut dq: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    /// dq.extend([1, 2, 3, 4].iter());
+    /// assert_eq!(dq[0], 2);
+    /// ```
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for StackArrayDeque<T, N> {
+    /// Creates a deque by pushing each item of the iterator to the back.
+    ///
+    /// Once the deque is full, each further item overwrites the oldest one,
+    /// as with [`extend`](Extend::extend), so an iterator of `n` items leaves
+    /// the last `min(n, N)` of them, front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let dq: StackArrayDeque<_, 4> = (0..3).collect();
+    /// assert_eq!(dq.len(), 3);
+    ///
+    /// let recent: StackArrayDeque<_, 3> = (1..=10).collect();
+    /// assert!(recent.iter().eq(&[8, 9, 10]));
+    /// ```
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut dq = StackArrayDeque::new();
+        dq.extend(iter);
+        dq
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for StackArrayDeque<T, N> {
+    /// Moves all `N` elements of the array into a full deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let dq = StackArrayDeque::from([1, 2, 3]);
+    /// assert!(dq.is_full());
+    /// assert_eq!(dq[0], 1);
+    /// ```
+    fn from(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+        // SAFETY: `[MaybeUninit<T>; N]` has the same layout as `[T; N]`, and the
+        // source is wrapped in `ManuallyDrop` so each element is moved exactly once.
+        let data = unsafe { ptr::read(&*array as *const [T; N] as *const [MaybeUninit<T>; N]) };
+        Self {
+            data,
+            len: N,
+            idx: 0,
+            #[cfg(feature = "stats")]
+            stats: Stats::new(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> TryFrom<Vec<T>> for StackArrayDeque<T, N> {
+    type Error = CapacityError;
+
+    /// Moves the vector's elements into the deque, failing if the input exceeds
+    /// capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StackArrayDeque;
+    ///
+    /// let dq: StackArrayDeque<_, 4> = StackArrayDeque::try_from(vec![1, 2]).unwrap();
+    /// assert_eq!(dq.len(), 2);
+    ///
+    /// let err = StackArrayDeque::<_, 1>::try_from(vec![1, 2]).unwrap_err();
+    /// assert_eq!(err.to_string(), "input length 2 exceeds target capacity 1");
+    /// ```
+    fn try_from(mut vec: Vec<T>) -> Result<Self, Self::Error> {
+        let len = vec.len();
+        if len > N {
+            return Err(CapacityError { len, capacity: N });
+        }
+
+        let mut deque = StackArrayDeque::new();
+        unsafe {
+            ptr::copy_nonoverlapping(vec.as_ptr(), deque.data.as_mut_ptr() as *mut T, len);
+            // The elements now belong to the deque; the vector only frees its buffer.
+            vec.set_len(0);
+        }
+        deque.len = len;
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> TryFrom<VecDeque<T>> for StackArrayDeque<T, N> {
+    type Error = CapacityError;
 
     /// Converts from `VecDeque` into `StackArrayDeque`, failing if input exceeds capacity.
     fn try_from(mut vec_deque: VecDeque<T>) -> Result<Self, Self::Error> {
@@ -506,434 +3107,2413 @@ impl<T, const N: usize> TryFrom<VecDeque<T>> for StackArrayDeque<T, N> {
             });
         }
 
-        let mut deque = StackArrayDeque::new();
-        while let Some(item) = vec_deque.pop_front() {
+        let mut deque = StackArrayDeque::new();
+        while let Some(item) = vec_deque.pop_front() {
+            deque.push_back(item);
+        }
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> From<StackArrayDeque<T, N>> for VecDeque<T> {
+    /// Converts this deque into a `VecDeque`, preserving order.
+    fn from(deque: StackArrayDeque<T, N>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, const N: usize> From<&StackArrayDeque<T, N>> for VecDeque<T> {
+    /// Clones elements into a `VecDeque`, preserving order.
+    fn from(deque: &StackArrayDeque<T, N>) -> Self {
+        deque.iter().cloned().collect()
+    }
+}
+
+/// An owning iterator that moves elements out of a `StackArrayDeque`.
+///
+/// This is returned by `into_iter()`.
+pub struct StackArrayDequeIntoIter<T, const N: usize> {
+    deque: StackArrayDeque<T, N>,
+}
+
+impl<T, const N: usize> Iterator for StackArrayDequeIntoIter<T, N> {
+    type Item = T;
+    /// Advances and returns the next element, front to back.
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    /// Returns the exact number of remaining elements.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+
+    /// Drops the first `n` remaining elements and returns the next one.
+    fn nth(&mut self, n: usize) -> Option<T> {
+        self.deque.truncate_front(self.deque.len.saturating_sub(n));
+        self.deque.pop_front()
+    }
+
+    /// Returns the number of remaining elements, dropping them.
+    fn count(self) -> usize {
+        self.deque.len
+    }
+
+    /// Returns the back element, dropping the rest.
+    fn last(mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for StackArrayDequeIntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for StackArrayDequeIntoIter<T, N> {}
+
+impl<T: Clone, const N: usize> Clone for StackArrayDequeIntoIter<T, N> {
+    /// Clones the elements that have not been yielded yet.
+    fn clone(&self) -> Self {
+        StackArrayDequeIntoIter {
+            deque: self.deque.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StackArrayDequeIntoIter<T, N> {
+    /// Formats the elements that have not been yielded yet.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StackArrayDequeIntoIter")
+            .field(&self.deque)
+            .finish()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for StackArrayDeque<T, N> {
+    type Item = T;
+    type IntoIter = StackArrayDequeIntoIter<T, N>;
+    /// Consumes the deque and returns an iterator over its elements.
+    fn into_iter(self) -> Self::IntoIter {
+        StackArrayDequeIntoIter { deque: self }
+    }
+}
+
+/// A borrowed iterator over `&T` from a `StackArrayDeque`.
+///
+/// This is returned by `iter()` and `&deque.into_iter()`.
+pub struct StackArrayDequeIter<'a, T, const N: usize> {
+    /// Remaining elements up to the wrap point
+    front: slice::Iter<'a, T>,
+    /// Remaining elements after the wrap point
+    back: slice::Iter<'a, T>,
+    _marker: PhantomData<&'a StackArrayDeque<T, N>>,
+}
+
+impl<'a, T, const N: usize> StackArrayDequeIter<'a, T, N> {
+    /// Creates an iterator over the contents `front` followed by `back`.
+    fn new(front: &'a [T], back: &'a [T]) -> Self {
+        StackArrayDequeIter {
+            front: front.iter(),
+            back: back.iter(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for StackArrayDequeIter<'a, T, N> {
+    type Item = &'a T;
+
+    /// Advances and returns the next reference, front to back.
+    fn next(&mut self) -> Option<&'a T> {
+        match self.front.next() {
+            Some(value) => Some(value),
+            None => {
+                // Step onto the second run so later calls take the first arm.
+                mem::swap(&mut self.front, &mut self.back);
+                self.front.next()
+            }
+        }
+    }
+
+    /// Returns the exact number of remaining elements.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len();
+        (remaining, Some(remaining))
+    }
+
+    /// Skips `n` elements in O(1) and returns the next reference.
+    fn nth(&mut self, n: usize) -> Option<&'a T> {
+        let front_len = self.front.len();
+        if n < front_len {
+            return self.front.nth(n);
+        }
+        self.front = mem::replace(&mut self.back, [].iter());
+        self.front.nth(n - front_len)
+    }
+
+    /// Returns the number of remaining elements in O(1).
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    /// Returns a reference to the back element in O(1).
+    fn last(mut self) -> Option<&'a T> {
+        self.next_back()
+    }
+
+    /// Folds over each run in turn, so the inner loops are over plain slices.
+    fn fold<B, F: FnMut(B, &'a T) -> B>(self, init: B, mut f: F) -> B {
+        let acc = self.front.fold(init, &mut f);
+        self.back.fold(acc, f)
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for StackArrayDequeIter<'a, T, N> {
+    /// Returns the next reference from the back.
+    fn next_back(&mut self) -> Option<&'a T> {
+        match self.back.next_back() {
+            Some(value) => Some(value),
+            None => self.front.next_back(),
+        }
+    }
+
+    /// Folds over each run in turn, back to front.
+    fn rfold<B, F: FnMut(B, &'a T) -> B>(self, init: B, mut f: F) -> B {
+        let acc = self.back.rfold(init, &mut f);
+        self.front.rfold(acc, f)
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for StackArrayDequeIter<'a, T, N> {
+    /// Returns the number of remaining elements in both runs.
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<'a, T, const N: usize> FusedIterator for StackArrayDequeIter<'a, T, N> {}
+
+impl<'a, T, const N: usize> Clone for StackArrayDequeIter<'a, T, N> {
+    /// Creates a copy of the iterator at the same position.
+    fn clone(&self) -> Self {
+        StackArrayDequeIter {
+            front: self.front.clone(),
+            back: self.back.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug, const N: usize> fmt::Debug for StackArrayDequeIter<'a, T, N> {
+    /// Formats the elements that have not been yielded yet.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StackArrayDequeIter")
+            .field(&self.front.as_slice())
+            .field(&self.back.as_slice())
+            .finish()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a StackArrayDeque<T, N> {
+    type Item = &'a T;
+    type IntoIter = StackArrayDequeIter<'a, T, N>;
+    /// Borrows the deque and returns an iterator over `&T`.
+    fn into_iter(self) -> Self::IntoIter {
+        let (front, back) = self.as_slices();
+        StackArrayDequeIter::new(front, back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestRng;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[cfg(not(feature = "std"))]
+    extern crate alloc;
+    #[cfg(not(feature = "std"))]
+    use alloc::{collections::VecDeque, format, string::String, sync::Arc, vec, vec::Vec};
+    #[cfg(feature = "std")]
+    use std::sync::Arc;
+
+    #[derive(Clone)]
+    struct DropCounter {
+        drops: Arc<AtomicUsize>,
+    }
+
+    impl DropCounter {
+        fn new(drops: Arc<AtomicUsize>) -> Self {
+            Self { drops }
+        }
+    }
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.drops.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// A value whose third clone panics, counting drops through its
+    /// [`DropCounter`].
+    #[cfg(feature = "std")]
+    struct Bomb {
+        clones: Arc<AtomicUsize>,
+        counter: DropCounter,
+    }
+
+    #[cfg(feature = "std")]
+    impl Bomb {
+        fn new(clones: &Arc<AtomicUsize>, drops: &Arc<AtomicUsize>) -> Self {
+            Bomb {
+                clones: clones.clone(),
+                counter: DropCounter::new(drops.clone()),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            if self.clones.fetch_add(1, Ordering::SeqCst) == 2 {
+                panic!("clone failed");
+            }
+            Bomb {
+                clones: self.clones.clone(),
+                counter: self.counter.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_front(), None);
+    }
+
+    #[test]
+    fn push_front_back() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_front(1);
+        deque.push_front(2);
+        deque.push_back(3);
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn clear() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn capacity() {
+        let deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        assert_eq!(deque.capacity(), 5);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn clone() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        let cloned_deque = deque.clone();
+        assert_eq!(cloned_deque.len(), 2);
+        assert_eq!(cloned_deque[0], 1);
+        assert_eq!(cloned_deque[1], 2);
+    }
+
+    #[test]
+    fn clone_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        let cloned = deque.clone();
+        assert_eq!(cloned, deque);
+        assert_eq!(cloned.as_slices(), (&[2, 3, 4, 5][..], &[][..]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn clone_panicking_drops_cloned_elements() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let clones = Arc::new(AtomicUsize::new(0));
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<Bomb, 4> = StackArrayDeque::new();
+        for _ in 0..6 {
+            deque.push_back(Bomb::new(&clones, &drops));
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+        let result = catch_unwind(AssertUnwindSafe(|| deque.clone()));
+        assert!(result.is_err());
+        // The two clones written before the panic are dropped with the
+        // partially built deque.
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+        assert_eq!(deque.len(), 4);
+    }
+
+    #[test]
+    fn index() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert_eq!(deque[0], 1);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds_panics() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.push_back(1);
+        let _ = deque[1];
+    }
+
+    #[test]
+    fn get_unchecked_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for i in 0..7 {
+            deque.push_back(i);
+        }
+        for i in 0..deque.len() {
+            assert_eq!(unsafe { *deque.get_unchecked(i) }, deque[i]);
+        }
+        for i in 0..deque.len() {
+            unsafe { *deque.get_unchecked_mut(i) *= 10 };
+        }
+        assert_eq!(contents(&deque), [30, 40, 50, 60]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Index out of bounds")]
+    fn get_unchecked_out_of_bounds_debug_panics() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        deque.push_back(1);
+        let _ = unsafe { deque.get_unchecked(1) };
+    }
+
+    #[test]
+    fn get_many_mut_across_wrap_point() {
+        let mut deque = StackArrayDeque::<i32, 4>::new();
+        for i in 0..7 {
+            deque.push_back(i);
+        }
+        // The front element sits in the last slot, the rest wrap to slot 0.
+        assert_eq!(deque.as_slices(), (&[3][..], &[4, 5, 6][..]));
+        let [back, front, mid] = deque.get_many_mut([3, 0, 1]).unwrap();
+        core::mem::swap(front, back);
+        *mid += 100;
+        assert_eq!(contents(&deque), [6, 104, 5, 3]);
+        let [] = deque.get_many_mut([]).unwrap();
+    }
+
+    #[test]
+    fn get_many_mut_rejects_bad_indices() {
+        let mut deque = StackArrayDeque::<i32, 4>::new();
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        assert_eq!(
+            deque.get_many_mut([2, 0, 2]),
+            Err(GetManyMutError::OverlappingIndices)
+        );
+        assert_eq!(
+            deque.get_many_mut([1, 4]),
+            Err(GetManyMutError::IndexOutOfBounds)
+        );
+        assert_eq!(
+            deque.get_many_mut([usize::MAX]),
+            Err(GetManyMutError::IndexOutOfBounds)
+        );
+        assert_eq!(contents(&deque), [2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn hash_ignores_physical_layout() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut wrapped: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        for i in 0..5 {
+            wrapped.push_back(i);
+        }
+        let unwrapped: StackArrayDeque<i32, 3> = [2, 3, 4].into_iter().collect();
+        assert_eq!(wrapped, unwrapped);
+        assert_eq!(hash_of(&wrapped), hash_of(&unwrapped));
+
+        let mut shorter: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        shorter.push_back(2);
+        shorter.push_back(3);
+        assert_ne!(hash_of(&shorter), hash_of(&unwrapped));
+    }
+
+    #[test]
+    fn ord_ignores_physical_layout() {
+        use core::cmp::Ordering;
+
+        let mut wrapped: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        for i in 0..5 {
+            wrapped.push_back(i);
+        }
+        let unwrapped: StackArrayDeque<i32, 3> = [2, 3, 4].into_iter().collect();
+        assert_eq!(wrapped.cmp(&unwrapped), Ordering::Equal);
+        assert_eq!(wrapped.partial_cmp(&unwrapped), Some(Ordering::Equal));
+
+        let prefix: StackArrayDeque<i32, 3> = [2, 3].into_iter().collect();
+        assert!(prefix < wrapped);
+        let bigger: StackArrayDeque<i32, 3> = [2, 4].into_iter().collect();
+        assert!(bigger > wrapped);
+
+        let mut sorted = vec![bigger.clone(), wrapped.clone(), prefix.clone()];
+        sorted.sort();
+        assert_eq!(sorted, vec![prefix, wrapped, bigger]);
+    }
+
+    #[test]
+    fn filled() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::filled(7);
+        assert!(deque.is_full());
+        assert_eq!(contents(&deque), [7, 7, 7]);
+        deque.push_back(1);
+        assert_eq!(contents(&deque), [7, 7, 1]);
+
+        let single: StackArrayDeque<char, 1> = StackArrayDeque::filled('x');
+        assert_eq!(contents(&single), ['x']);
+        assert!(StackArrayDeque::<char, 0>::filled('x').is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn filled_panicking_clone_drops_written_slots() {
+        use std::panic::catch_unwind;
+
+        let clones = Arc::new(AtomicUsize::new(0));
+        let drops = Arc::new(AtomicUsize::new(0));
+        let bomb = Bomb::new(&clones, &drops);
+        let result = catch_unwind(move || StackArrayDeque::<Bomb, 5>::filled(bomb));
+        assert!(result.is_err());
+        // Two clones were written before the third panicked; both are dropped
+        // along with the original value.
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn from_fn() {
+        let deque: StackArrayDeque<usize, 5> = StackArrayDeque::from_fn(|i| i * 10);
+        assert!(deque.is_full());
+        assert_eq!(contents(&deque), [0, 10, 20, 30, 40]);
+        assert!(StackArrayDeque::<usize, 0>::from_fn(|i| i).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_fn_panic_drops_constructed_elements() {
+        use std::panic::catch_unwind;
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        let counter = drops.clone();
+        let result = catch_unwind(move || {
+            StackArrayDeque::<DropCounter, 5>::from_fn(|i| {
+                if i == 3 {
+                    panic!("init failed");
+                }
+                DropCounter::new(counter.clone())
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn fill_drops_previous_contents_wrapped() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<DropCounter, 4> = StackArrayDeque::new();
+        for _ in 0..6 {
+            deque.push_back(DropCounter::new(drops.clone()));
+        }
+        deque.pop_front();
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+
+        let fresh = Arc::new(AtomicUsize::new(0));
+        deque.fill(DropCounter::new(fresh.clone()));
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+        assert!(deque.is_full());
+        assert_eq!(fresh.load(Ordering::SeqCst), 0);
+        drop(deque);
+        assert_eq!(fresh.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn fill_with_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        let mut next = 10;
+        deque.fill_with(|| {
+            next += 1;
+            next
+        });
+        assert_eq!(contents(&deque), [11, 12, 13, 14]);
+        deque.push_back(15);
+        assert_eq!(contents(&deque), [12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn resize_grow_and_shrink_wrapped() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<DropCounter, 4> = StackArrayDeque::new();
+        for _ in 0..5 {
+            deque.push_back(DropCounter::new(drops.clone()));
+        }
+        deque.pop_front();
+        deque.pop_front();
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+
+        deque.resize(4, DropCounter::new(drops.clone()));
+        assert!(deque.is_full());
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+
+        deque.resize(1, DropCounter::new(drops.clone()));
+        assert_eq!(deque.len(), 1);
+        // Three discarded from the tail, plus the unused `value`.
+        assert_eq!(drops.load(Ordering::SeqCst), 7);
+
+        let mut ints: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [1, 2, 3, 4, 5] {
+            ints.push_back(item);
+        }
+        ints.resize(2, 0);
+        ints.resize(4, 9);
+        assert_eq!(contents(&ints), [2, 3, 9, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "new_len exceeds capacity")]
+    fn resize_over_capacity_panics() {
+        let mut deque: StackArrayDeque<i32, 2> = StackArrayDeque::new();
+        deque.resize(3, 0);
+    }
+
+    #[test]
+    fn extend_exact_size_skips_overwritten_items() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<(i32, DropCounter), 3> = StackArrayDeque::new();
+        deque.push_back((100, DropCounter::new(drops.clone())));
+
+        deque.extend((0..10).map(|i| (i, DropCounter::new(drops.clone()))));
+        assert_eq!(drops.load(Ordering::SeqCst), 8);
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [7, 8, 9]);
+
+        deque.extend(
+            (10..15)
+                .filter(|_| true)
+                .map(|i| (i, DropCounter::new(drops.clone()))),
+        );
+        assert_eq!(drops.load(Ordering::SeqCst), 13);
+        assert_eq!(
+            deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            [12, 13, 14]
+        );
+    }
+
+    #[test]
+    fn extend_front_preserves_order() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.push_back(4);
+        deque.push_back(5);
+        deque.extend_front([1, 2, 3]);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+        let mut unsized_source: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        unsized_source.push_back(9);
+        unsized_source.extend_front((1..4).filter(|_| true));
+        assert_eq!(
+            unsized_source.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 9]
+        );
+    }
+
+    #[test]
+    fn extend_front_overflow_evicts_from_back() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        for i in [7, 8, 9, 10, 11, 12] {
+            deque.push_back(i);
+        }
+        deque.extend_front([1, 2]);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 8, 9, 10]);
+
+        deque.extend_front((0..8).filter(|_| true));
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        deque.extend_front(core::iter::empty());
+        assert_eq!(deque.len(), 5);
+    }
+
+    #[test]
+    fn extend_front_drops_each_element_once() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<(i32, DropCounter), 3> = StackArrayDeque::new();
+        deque.push_back((100, DropCounter::new(drops.clone())));
+        deque.extend_front((0..4).map(|i| (i, DropCounter::new(drops.clone()))));
+        // The old element and the fourth new item do not fit.
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [0, 1, 2]);
+        drop(deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn extend_by_reference() {
+        let source = [1, 2, 3, 4, 5];
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        deque.extend(source[..2].iter());
+        deque.extend(&source[2..]);
+        assert_eq!(contents(&deque), [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn extend_from_slice_straddles_wrap_point() {
+        let mut deque: StackArrayDeque<u8, 5> = StackArrayDeque::new();
+        deque.extend([0, 0, 0, 1, 2]);
+        for _ in 0..3 {
+            deque.pop_front();
+        }
+        deque.extend_from_slice(&[3, 4, 5]);
+        assert!(!deque.as_slices().1.is_empty());
+        assert_eq!(contents(&deque), [1, 2, 3, 4, 5]);
+
+        deque.extend_from_slice(&[6, 7]);
+        assert_eq!(contents(&deque), [3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn extend_from_copy_slice_matches_extend_from_slice() {
+        for offset in 0..5 {
+            for (len, add) in [(0, 3), (2, 2), (3, 4), (5, 7), (4, 0)] {
+                let mut cloned: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+                for _ in 0..offset {
+                    cloned.push_back(0);
+                    cloned.pop_front();
+                }
+                cloned.extend(0..len);
+                let mut copied = cloned.clone();
+                let slice: Vec<i32> = (10..10 + add).collect();
+                cloned.extend_from_slice(&slice);
+                copied.extend_from_copy_slice(&slice);
+                assert_eq!(contents(&copied), contents(&cloned));
+            }
+        }
+    }
+
+    #[test]
+    fn extend_from_slice_longer_than_capacity() {
+        let mut deque: StackArrayDeque<u8, 3> = StackArrayDeque::new();
+        for item in [1, 2, 3, 4] {
+            deque.push_back(item);
+        }
+        deque.extend_from_slice(&[5, 6, 7, 8, 9]);
+        assert_eq!(contents(&deque), [7, 8, 9]);
+
+        let mut empty: StackArrayDeque<u8, 0> = StackArrayDeque::new();
+        empty.extend_from_slice(&[1, 2]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn try_extend_from_slice_never_overwrites() {
+        let mut deque: StackArrayDeque<u8, 5> = StackArrayDeque::new();
+        deque.extend([0, 0, 0, 1, 2]);
+        for _ in 0..3 {
+            deque.pop_front();
+        }
+        assert_eq!(deque.try_extend_from_slice(&[3, 4, 5, 6]), Err(3));
+        assert_eq!(contents(&deque), [1, 2]);
+
+        assert_eq!(deque.try_extend_from_slice(&[3, 4, 5]), Ok(()));
+        assert_eq!(contents(&deque), [1, 2, 3, 4, 5]);
+        assert_eq!(deque.try_extend_from_slice(&[6]), Err(0));
+        assert_eq!(deque.try_extend_from_slice(&[]), Ok(()));
+        assert_eq!(contents(&deque), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn append_between_capacities_wrapped() {
+        let mut a: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        a.extend([0, 0, 0, 1, 2]);
+        for _ in 0..3 {
+            a.pop_front();
+        }
+        let mut b: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        for item in [0, 3, 4, 5] {
+            b.push_back(item);
+        }
+
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(contents(&a), [1, 2, 3, 4, 5]);
+
+        b.push_back(6);
+        assert_eq!(contents(&b), [6]);
+    }
+
+    #[test]
+    fn range_within_bounds() {
+        // Physical layout is [5, 6, 7, 3, 4] with the front at slot 3.
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend(0..8);
+        assert_eq!(deque.range(2..2).next(), None);
+        assert_eq!(deque.range(5..).count(), 0);
+        assert!(deque.range(..).eq(deque.iter()));
+        assert!(deque.range(1..4).eq(&[4, 5, 6]));
+        assert!(deque.range(..=2).rev().eq(&[5, 4, 3]));
+    }
+
+    #[test]
+    fn range_mut_across_wrap_point() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend(0..8);
+        deque.range_mut(1..4).for_each(|x| *x = -*x);
+        assert_eq!(
+            [deque[0], deque[1], deque[2], deque[3], deque[4]],
+            [3, -4, -5, -6, 7]
+        );
+        assert_eq!(deque.range_mut(4..4).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 6 out of range for slice of length 5")]
+    fn range_past_end_panics() {
+        let deque = StackArrayDeque::from([1, 2, 3, 4, 5]);
+        let _ = deque.range(..6);
+    }
+
+    #[test]
+    #[cfg(feature = "stats")]
+    fn stats_count_overwrites() {
+        let mut deque: StackArrayDeque<u8, 4> = StackArrayDeque::new();
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        deque.push_front(9);
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (7, 3));
+        deque.extend(0..10);
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (17, 13));
+        deque.push_slice_overwrite(b"abcdef");
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (23, 19));
+
+        let mut other: StackArrayDeque<u8, 8> = StackArrayDeque::new();
+        other.extend(*b"ghijkl");
+        deque.truncate(1);
+        deque.append(&mut other);
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (29, 22));
+        assert_eq!(deque.push_slice(b"x"), 0);
+        assert_eq!(deque.try_push_back(0), Err(0));
+        deque.clear();
+        deque.fill(0);
+        assert_eq!((deque.total_pushed(), deque.total_evicted()), (33, 22));
+        assert_eq!(deque.max_len_seen(), 4);
+        deque.reset_stats();
+        assert_eq!((deque.total_pushed(), deque.max_len_seen()), (0, 4));
+    }
+
+    #[test]
+    fn append_overflow_drops_oldest() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut a: StackArrayDeque<(i32, DropCounter), 3> = StackArrayDeque::new();
+        let mut b: StackArrayDeque<(i32, DropCounter), 5> = StackArrayDeque::new();
+        for i in 0..2 {
+            a.push_back((i, DropCounter::new(drops.clone())));
+        }
+        for i in 10..14 {
+            b.push_back((i, DropCounter::new(drops.clone())));
+        }
+
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+        assert_eq!(a.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [11, 12, 13]);
+        drop(a);
+        drop(b);
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn try_append_overflow_leaves_both_unchanged() {
+        let mut a: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        let mut b: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        a.extend([1, 2]);
+        b.extend([3, 4]);
+        assert_eq!(
+            a.try_append(&mut b),
+            Err(CapacityError {
+                len: 4,
+                capacity: 3,
+            })
+        );
+        assert_eq!(contents(&a), [1, 2]);
+        assert_eq!(contents(&b), [3, 4]);
+
+        b.pop_front();
+        assert_eq!(a.try_append(&mut b), Ok(()));
+        assert_eq!(contents(&a), [1, 2, 4]);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn split_off_wrapped() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<(i32, DropCounter), 4> = StackArrayDeque::new();
+        for i in 0..6 {
+            deque.push_back((i, DropCounter::new(drops.clone())));
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+        let tail = deque.split_off(1);
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [2]);
+        assert_eq!(tail.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [3, 4, 5]);
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+        assert!(deque.split_off(1).is_empty());
+        assert_eq!(deque.split_off(0).len(), 1);
+        assert!(deque.is_empty());
+        drop(tail);
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds")]
+    fn split_off_out_of_bounds_panics() {
+        let mut deque: StackArrayDeque<i32, 2> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.split_off(2);
+    }
+
+    #[test]
+    fn index_mut() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        deque[0] = 10;
+        assert_eq!(deque[0], 10);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_mut_out_of_bounds_panics() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque[1] = 99;
+    }
+
+    #[test]
+    fn get_back_counts_from_back() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        assert_eq!(deque.get_back(0), None);
+        assert_eq!(deque.get_back_mut(0), None);
+        assert_eq!(deque.get_back(usize::MAX), None);
+
+        let mut deque = StackArrayDeque::from([7]);
+        assert_eq!(deque.get_back(0), Some(&7));
+        assert_eq!(deque.get_back(1), None);
+        *deque.get_back_mut(0).unwrap() = 8;
+        assert_eq!(deque.back(), Some(&8));
+
+        // Elements 3..8, split into [3, 4] and [5, 6, 7] by the wrap point.
+        let mut deque = {
+            let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+            deque.extend(0..8);
+            deque
+        };
+        assert_eq!(deque.get_back(0), deque.back());
+        assert_eq!([deque.get_back(2), deque.get_back(3)], [Some(&5), Some(&4)]);
+        assert_eq!(deque.get_back(4), Some(&3));
+        assert_eq!(deque.get_back(5), None);
+        *deque.get_back_mut(3).unwrap() = 40;
+        assert_eq!(deque[1], 40);
+    }
+
+    #[test]
+    fn spare_capacity_commits_across_wrap_point() {
+        let mut deque: StackArrayDeque<String, 5> = StackArrayDeque::new();
+        deque.extend(["a", "b", "c", "d"].map(String::from));
+        deque.pop_front();
+        deque.pop_front();
+        deque.pop_front();
+        // The front is `d` at slot 3; slot 4 is free, then slots 0 to 2.
+        let (first, second) = deque.spare_capacity_mut();
+        assert_eq!((first.len(), second.len()), (1, 3));
+        first[0].write(String::from("e"));
+        second[0].write(String::from("f"));
+        unsafe { deque.advance_back(2) };
+        assert!(deque.iter().eq(["d", "e", "f"]));
+
+        let (first, second) = deque.spare_capacity_mut();
+        assert_eq!((first.len(), second.len()), (2, 0));
+        first[0].write(String::from("b"));
+        first[1].write(String::from("c"));
+        unsafe { deque.advance_front(2) };
+        assert!(deque.iter().eq(["b", "c", "d", "e", "f"]));
+        assert!(deque.is_full());
+        let (first, second) = deque.spare_capacity_mut();
+        assert!(first.is_empty() && second.is_empty());
+        unsafe {
+            deque.advance_back(0);
+            deque.advance_front(0);
+        }
+        assert_eq!(deque.len(), 5);
+    }
+
+    #[test]
+    fn advance_front_wraps_below_slot_zero() {
+        let mut deque: StackArrayDeque<String, 5> = StackArrayDeque::new();
+        deque.push_back(String::from("c"));
+        let (first, second) = deque.spare_capacity_mut();
+        assert_eq!((first.len(), second.len()), (4, 0));
+        first[2].write(String::from("a"));
+        first[3].write(String::from("b"));
+        unsafe { deque.advance_front(2) };
+        assert!(deque.iter().eq(["a", "b", "c"]));
+        assert_eq!(deque.pop_front().as_deref(), Some("a"));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot advance past the end of the free space")]
+    fn advance_back_past_free_space_panics() {
+        let mut deque: StackArrayDeque<String, 5> = StackArrayDeque::new();
+        deque.push_back(String::new());
+        unsafe { deque.advance_back(5) };
+    }
+
+    #[test]
+    fn matches_vecdeque_under_random_operations() {
+        fn check<const N: usize>(rng: &mut TestRng) {
+            let cap = N;
+            let mut deque: StackArrayDeque<u32, N> = StackArrayDeque::new();
+            let mut model = VecDeque::new();
+            for step in 0..2_000u32 {
+                match rng.below(7) {
+                    0 => {
+                        if model.len() == cap {
+                            model.pop_front();
+                        }
+                        model.push_back(step);
+                        deque.push_back(step);
+                    }
+                    1 => {
+                        if model.len() == cap {
+                            model.pop_back();
+                        }
+                        model.push_front(step);
+                        deque.push_front(step);
+                    }
+                    2 => assert_eq!(deque.pop_back(), model.pop_back()),
+                    3 => assert_eq!(deque.pop_front(), model.pop_front()),
+                    4 if !model.is_empty() => {
+                        let i = rng.below(model.len());
+                        assert_eq!(deque.remove(i), model.remove(i));
+                    }
+                    5 if !model.is_empty() => {
+                        let i = rng.below(model.len());
+                        deque[i] = step;
+                        model[i] = step;
+                    }
+                    _ => {
+                        let clone = deque.clone();
+                        assert!(clone.into_iter().eq(model.iter().copied()));
+                    }
+                }
+                assert_eq!(deque.len(), model.len());
+                assert!(deque.iter().eq(model.iter()));
+                assert!((0..model.len()).all(|i| deque[i] == model[i]));
+            }
+        }
+
+        let mut rng = TestRng::new(0x9e37_79b9);
+        check::<1>(&mut rng);
+        check::<2>(&mut rng);
+        check::<5>(&mut rng);
+        check::<7>(&mut rng);
+    }
+
+    #[test]
+    fn iter_matches_index_order_across_wrap() {
+        fn check<const N: usize>() {
+            for offset in 0..N {
+                for len in 0..=N {
+                    let mut deque = StackArrayDeque::<usize, N>::new();
+                    for _ in 0..offset {
+                        deque.push_back(0);
+                        deque.pop_front();
+                    }
+                    deque.extend(0..len);
+                    let expected: Vec<&usize> = (0..len).map(|i| &deque[i]).collect();
+                    assert!(deque.iter().eq(expected.iter().copied()));
+                    assert!(
+                        (&deque)
+                            .into_iter()
+                            .rev()
+                            .eq(expected.iter().rev().copied())
+                    );
+                    for n in 0..=len {
+                        let mut iter = deque.iter();
+                        assert_eq!(iter.nth(n), expected.get(n).copied());
+                        assert_eq!(iter.len(), len.saturating_sub(n + 1));
+                    }
+                    assert!(deque.clone().into_iter().eq(expected.iter().map(|&&x| x)));
+                }
+            }
+        }
+        check::<1>();
+        check::<4>();
+        check::<6>();
+    }
+
+    #[test]
+    fn front_mut_back_mut_empty() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        assert_eq!(deque.front_mut(), None);
+        assert_eq!(deque.back_mut(), None);
+    }
+
+    #[test]
+    fn front_mut_back_mut_single_element() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        *deque.front_mut().unwrap() += 10;
+        *deque.back_mut().unwrap() += 100;
+        assert_eq!(deque.len(), 1);
+        assert_eq!(deque[0], 111);
+    }
+
+    #[test]
+    fn pop_if_checks_predicate() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<DropCounter, 2> = StackArrayDeque::new();
+        assert!(deque.pop_front_if(|_| unreachable!()).is_none());
+        assert!(deque.pop_back_if(|_| unreachable!()).is_none());
+        deque.push_back(DropCounter::new(drops.clone()));
+        deque.push_back(DropCounter::new(drops.clone()));
+
+        assert!(deque.pop_front_if(|_| false).is_none());
+        assert!(deque.pop_back_if(|_| false).is_none());
+        assert_eq!((deque.len(), drops.load(Ordering::SeqCst)), (2, 0));
+
+        let popped = deque.pop_back_if(|_| true);
+        assert!(popped.is_some());
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(popped);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        assert!(deque.pop_front_if(|_| true).is_some());
+        assert!(deque.is_empty());
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn pop_while_stops_at_first_rejected() {
+        // Elements 3..9, split into [3, 4, 5] and [6, 7, 8] by the wrap point.
+        let mut deque: StackArrayDeque<i32, 6> = StackArrayDeque::new();
+        deque.extend(0..9);
+        assert_eq!(deque.pop_front_while(|_| false).count(), 0);
+        assert_eq!(deque.pop_back_while(|x| *x > 8).count(), 0);
+        assert_eq!(deque.len(), 6);
+
+        // Exactly up to the wrap boundary, from each end.
+        assert!(deque.pop_front_while(|x| *x < 6).eq([3, 4, 5]));
+        let mut calls = 0;
+        let popped = deque.pop_back_while(|x| {
+            calls += 1;
+            *x != 7
+        });
+        assert!(popped.eq([8]));
+        assert_eq!(calls, 2);
+        assert!(deque.iter().eq(&[6, 7]));
+
+        assert!(deque.pop_back_while(|_| true).eq([7, 6]));
+        assert!(deque.is_empty());
+        assert_eq!(deque.pop_front_while(|_| true).next(), None);
+    }
+
+    #[test]
+    fn pop_while_abandoned_keeps_rest() {
+        let mut deque: StackArrayDeque<i32, 6> = StackArrayDeque::new();
+        deque.extend(0..9);
+        let mut popped = deque.pop_front_while(|_| true);
+        assert_eq!(popped.next(), Some(3));
+        assert_eq!(popped.next(), Some(4));
+        drop(popped);
+        assert!(deque.iter().eq(&[5, 6, 7, 8]));
+    }
+
+    #[test]
+    fn map_keeps_order() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend(0..8);
+        let mapped: StackArrayDeque<u8, 5> = deque.map(|x| x as u8 * 2);
+        assert!(mapped.iter().eq(&[6, 8, 10, 12, 14]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn map_panic_drops_both_sides() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let source_drops = Arc::new(AtomicUsize::new(0));
+        let mapped_drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<(usize, DropCounter), 6> = StackArrayDeque::new();
+        for i in 0..6 {
+            deque.push_back((i, DropCounter::new(source_drops.clone())));
+        }
+        deque.pop_front();
+        deque.push_back((6, DropCounter::new(source_drops.clone())));
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            deque.map(|(i, _)| {
+                if i == 4 {
+                    panic!("map failed");
+                }
+                DropCounter::new(mapped_drops.clone())
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(source_drops.load(Ordering::SeqCst), 7);
+        assert_eq!(mapped_drops.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn from_iter_keeps_newest() {
+        let shorter: StackArrayDeque<i32, 5> = (0..3).collect();
+        assert!(shorter.iter().eq(&[0, 1, 2]));
+        let equal: StackArrayDeque<i32, 3> = (0..3).collect();
+        assert!(equal.iter().eq(&[0, 1, 2]));
+        let longer: StackArrayDeque<i32, 4> = (0..1_000).filter(|x| x % 2 == 0).collect();
+        assert!(longer.iter().eq(&[992, 994, 996, 998]));
+    }
+
+    #[test]
+    fn front_mut_back_mut_wrapped() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_back(4);
+        deque.push_back(5);
+
+        *deque.front_mut().unwrap() = 30;
+        *deque.back_mut().unwrap() = 50;
+        assert_eq!(deque[0], 30);
+        assert_eq!(deque[1], 4);
+        assert_eq!(deque[2], 50);
+    }
+
+    #[test]
+    fn iter_clone_fused_debug() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3] {
+            deque.push_back(item);
+        }
+        let mut iter = (&deque).into_iter();
+        assert_eq!(iter.next(), Some(&1));
+
+        let cloned = iter.clone();
+        assert_eq!(cloned.copied().collect::<VecDeque<_>>(), [2, 3]);
+        assert_eq!(format!("{:?}", iter), "StackArrayDequeIter([2], [3])");
+
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        fn assert_fused<I: FusedIterator + Clone>(_: &I) {}
+        assert_fused(&iter);
+    }
+
+    #[test]
+    fn into_iter_clone_fused_debug() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.extend([1, 2, 3]);
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next(), Some(1));
+
+        let cloned = iter.clone();
+        assert_eq!(cloned.collect::<VecDeque<_>>(), [2, 3]);
+        assert_eq!(format!("{:?}", iter), "StackArrayDequeIntoIter([2, 3])");
+
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_nth_count_last() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend(0..8);
+        let mut iter = (&deque).into_iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.nth(1), Some(&4));
+        assert_eq!(iter.nth(1), Some(&6));
+        assert_eq!(iter.clone().count(), 1);
+        assert_eq!(iter.clone().last(), Some(&7));
+        assert_eq!(iter.nth(1), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.clone().last(), None);
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn into_iter_nth_count_last_drop_counts() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let make = || {
+            let mut deque: StackArrayDeque<DropCounter, 6> = StackArrayDeque::new();
+            for _ in 0..6 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            deque.into_iter()
+        };
+
+        {
+            let mut iter = make();
+            let third = iter.nth(2);
+            assert!(third.is_some());
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
+            assert_eq!(iter.len(), 3);
+            assert!(iter.nth(5).is_none());
+            assert_eq!(drops.load(Ordering::SeqCst), 5);
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+
+        assert_eq!(make().count(), 6);
+        assert_eq!(drops.load(Ordering::SeqCst), 12);
+
+        let last = make().last();
+        assert_eq!(drops.load(Ordering::SeqCst), 17);
+        drop(last);
+        assert_eq!(drops.load(Ordering::SeqCst), 18);
+    }
+
+    #[test]
+    fn iter_empty() {
+        let deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_full() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_partial() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn is_empty() {
+        let deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn is_full() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        assert!(!deque.is_full());
+        deque.push_back(1);
+        deque.push_back(2);
+        assert!(!deque.is_full());
+        deque.push_back(3);
+        assert!(deque.is_full());
+    }
+
+    #[test]
+    fn clear_empty() {
+        let mut deque: StackArrayDeque<(), 3> = StackArrayDeque::new();
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn clear_non_empty() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn overflow_behavior_push_back() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert!(deque.is_full());
+
+        // This should overwrite the front element (1)
+        deque.push_back(4);
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 2);
+        assert_eq!(deque[1], 3);
+        assert_eq!(deque[2], 4);
+    }
+
+    #[test]
+    fn overflow_behavior_push_front() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert!(deque.is_full());
+
+        // This should overwrite the back element (3)
+        deque.push_front(0);
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 0);
+        assert_eq!(deque[1], 1);
+        assert_eq!(deque[2], 2);
+    }
+
+    #[test]
+    fn default() {
+        let deque: StackArrayDeque<i32, 5> = StackArrayDeque::default();
+        assert!(deque.is_empty());
+        assert_eq!(deque.capacity(), 5);
+    }
+
+    #[test]
+    fn debug_format() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        let debug_str = format!("{:?}", deque);
+        assert_eq!(debug_str, "[1, 2]");
+    }
+
+    #[test]
+    fn partial_eq() {
+        let mut deque1: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        let mut deque2: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+
+        assert_eq!(deque1, deque2);
+
+        deque1.push_back(1);
+        deque1.push_back(2);
+
+        deque2.push_back(1);
+        deque2.push_back(2);
+
+        assert_eq!(deque1, deque2);
+
+        deque2.push_back(3);
+        assert_ne!(deque1, deque2);
+    }
+
+    #[test]
+    fn circular_behavior() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+
+        // Fill the deque
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+
+        // Remove from front and add to back (should wrap around)
+        assert_eq!(deque.pop_front(), Some(1));
+        deque.push_back(4);
+
+        assert_eq!(deque[0], 2);
+        assert_eq!(deque[1], 3);
+        assert_eq!(deque[2], 4);
+
+        // Remove from back and add to front (should wrap around)
+        assert_eq!(deque.pop_back(), Some(4));
+        deque.push_front(0);
+
+        assert_eq!(deque[0], 0);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
+    }
+
+    #[test]
+    fn mixed_operations() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+
+        deque.push_back(1);
+        deque.push_front(0);
+        deque.push_back(2);
+        deque.push_front(-1);
+
+        assert_eq!(deque.len(), 4);
+        assert!(deque.is_full());
+
+        assert_eq!(deque[0], -1);
+        assert_eq!(deque[1], 0);
+        assert_eq!(deque[2], 1);
+        assert_eq!(deque[3], 2);
+
+        assert_eq!(deque.pop_front(), Some(-1));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.len(), 2);
+
+        assert_eq!(deque[0], 0);
+        assert_eq!(deque[1], 1);
+    }
+
+    #[test]
+    fn single_capacity() {
+        let mut deque: StackArrayDeque<i32, 1> = StackArrayDeque::new();
+        assert_eq!(deque.capacity(), 1);
+
+        deque.push_back(1);
+        assert_eq!(deque.len(), 1);
+        assert!(deque.is_full());
+        assert_eq!(deque[0], 1);
+
+        // Pushing another element should overwrite
+        deque.push_back(2);
+        assert_eq!(deque.len(), 1);
+        assert_eq!(deque[0], 2);
+
+        assert_eq!(deque.pop_front(), Some(2));
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<DropCounter, 0> = StackArrayDeque::new();
+        assert_eq!(deque.capacity(), 0);
+        assert_eq!(deque.len(), 0);
+        assert!(deque.is_empty());
+        assert!(deque.is_full());
+
+        deque.push_back(DropCounter::new(drops.clone()));
+        deque.push_front(DropCounter::new(drops.clone()));
+        deque.insert(0, DropCounter::new(drops.clone()));
+        deque.extend([DropCounter::new(drops.clone())]);
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+        assert!(
+            deque
+                .try_push_back(DropCounter::new(drops.clone()))
+                .is_err()
+        );
+        assert!(
+            deque
+                .try_push_front(DropCounter::new(drops.clone()))
+                .is_err()
+        );
+        assert!(
+            deque
+                .try_insert(0, DropCounter::new(drops.clone()))
+                .is_err()
+        );
+        assert_eq!(drops.load(Ordering::SeqCst), 7);
+        assert!(deque.is_empty());
+
+        assert!(deque.pop_back().is_none());
+        assert!(deque.pop_front().is_none());
+        assert!(deque.remove(0).is_none());
+        assert!(deque.swap_remove_back(0).is_none());
+        assert!(deque.swap_remove_front(0).is_none());
+        assert!(deque.front().is_none());
+        assert!(deque.back().is_none());
+        assert!(deque.front_mut().is_none());
+        assert!(deque.back_mut().is_none());
+        assert_eq!(deque.iter().count(), 0);
+        assert_eq!((&deque).into_iter().count(), 0);
+        assert!(deque.as_slices().0.is_empty() && deque.as_slices().1.is_empty());
+        assert!(deque.as_mut_slices().0.is_empty());
+        assert_eq!(deque.partition_point(|_| true), 0);
+        deque.rotate_left(0);
+        deque.rotate_right(0);
+        deque.truncate(0);
+        deque.truncate_front(0);
+        deque.clear();
+        assert_eq!(
+            deque.binary_search_by(|_| core::cmp::Ordering::Less),
+            Err(0)
+        );
+        deque.sort_unstable_by(|_, _| core::cmp::Ordering::Equal);
+
+        let mut ints: StackArrayDeque<i32, 0> = StackArrayDeque::default();
+        ints.push_back(1);
+        ints.insert_sorted(1);
+        assert!(!ints.contains(&1));
+        assert_eq!(ints.index_of(&1), None);
+        assert_eq!(ints.binary_search(&1), Err(0));
+        ints.sort_unstable();
+        assert_eq!(format!("{:?}", ints), "[]");
+        assert_eq!(ints.clone(), ints);
+        let collected: StackArrayDeque<i32, 0> = (0..3).collect();
+        assert!(collected.is_empty());
+        #[cfg(feature = "alloc")]
+        {
+            assert!(VecDeque::from(collected).is_empty());
+            assert!(StackArrayDeque::<i32, 0>::try_from(VecDeque::from([1])).is_err());
+        }
+        assert_eq!(ints.into_iter().next(), None);
+    }
+
+    #[test]
+    fn push_back_overwrite_drops_replaced_element() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque: StackArrayDeque<DropCounter, 2> = StackArrayDeque::new();
+            deque.push_back(DropCounter::new(drops.clone()));
+            deque.push_back(DropCounter::new(drops.clone()));
+            deque.push_back(DropCounter::new(drops.clone()));
+
+            assert_eq!(drops.load(Ordering::SeqCst), 1);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+    }
+
+    fn contents<T: Clone, const N: usize>(deque: &StackArrayDeque<T, N>) -> VecDeque<T> {
+        deque.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert_shifts_shorter_side() {
+        let mut deque: StackArrayDeque<i32, 6> = StackArrayDeque::new();
+        deque.extend([1, 2, 4, 5]);
+        deque.insert(2, 3);
+        assert_eq!(contents(&deque), [1, 2, 3, 4, 5]);
+        deque.insert(0, 0);
+        assert_eq!(contents(&deque), [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn insert_on_full_evicts_back() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        deque.extend([1, 2, 3]);
+        deque.insert(1, 9);
+        assert_eq!(contents(&deque), [1, 9, 2]);
+        deque.insert(3, 7);
+        assert_eq!(contents(&deque), [1, 9, 7]);
+        assert_eq!(deque.try_insert(0, 0), Err(0));
+    }
+
+    #[test]
+    fn insert_at_wrap_point() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend([0, 0, 0]);
+        for _ in 0..3 {
+            deque.pop_front();
+        }
+        // Front sits at slot 3; logical index 2 is physical slot 0.
+        deque.extend([1, 2, 4]);
+        deque.insert(2, 3);
+        assert_eq!(contents(&deque), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn remove_at_wrap_point() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend([0, 0, 0]);
+        for _ in 0..3 {
+            deque.pop_front();
+        }
+        deque.extend([1, 2, 3, 4, 5]);
+        // Logical index 2 is physical slot 0.
+        assert_eq!(deque.remove(2), Some(3));
+        assert_eq!(contents(&deque), [1, 2, 4, 5]);
+        assert_eq!(deque.remove(1), Some(2));
+        assert_eq!(contents(&deque), [1, 4, 5]);
+        assert_eq!(deque.remove(3), None);
+    }
+
+    #[test]
+    fn insert_remove_drop_counts() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque: StackArrayDeque<DropCounter, 3> = StackArrayDeque::new();
+            for _ in 0..3 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            deque.insert(1, DropCounter::new(drops.clone()));
+            assert_eq!(drops.load(Ordering::SeqCst), 1);
+            drop(deque.remove(0));
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn swap_across_wrap_point() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        // Physical layout is [4, 5, 2, 3] with the front at slot 2.
+        deque.swap(0, 3);
+        assert_eq!(contents(&deque), [5, 3, 4, 2]);
+    }
+
+    #[test]
+    fn swap_remove_front_and_back() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4, 5, 6] {
+            deque.push_back(item);
+        }
+        assert_eq!(deque.swap_remove_back(1), Some(3));
+        assert_eq!(contents(&deque), [2, 6, 4, 5]);
+        assert_eq!(deque.swap_remove_front(2), Some(4));
+        assert_eq!(contents(&deque), [6, 2, 5]);
+        assert_eq!(deque.swap_remove_front(0), Some(6));
+        assert_eq!(deque.swap_remove_back(1), Some(5));
+        assert_eq!(contents(&deque), [2]);
+        assert_eq!(deque.swap_remove_back(1), None);
+    }
+
+    #[test]
+    fn swap_remove_only_element() {
+        let mut deque: StackArrayDeque<i32, 2> = StackArrayDeque::new();
+        deque.push_back(1);
+        assert_eq!(deque.swap_remove_back(0), Some(1));
+        assert!(deque.is_empty());
+        deque.push_back(2);
+        assert_eq!(deque.swap_remove_front(0), Some(2));
+        assert!(deque.is_empty());
+        assert_eq!(deque.swap_remove_front(0), None);
+    }
+
+    #[test]
+    fn swap_remove_drop_counts() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque: StackArrayDeque<DropCounter, 4> = StackArrayDeque::new();
+            for _ in 0..4 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            let removed = deque.swap_remove_front(2);
+            let removed_back = deque.swap_remove_back(0);
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
+            drop(removed);
+            drop(removed_back);
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn truncate_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4, 5] {
             deque.push_back(item);
         }
-        Ok(deque)
+        deque.truncate(3);
+        assert_eq!(contents(&deque), [2, 3, 4]);
+        deque.push_back(6);
+        assert_eq!(contents(&deque), [2, 3, 4, 6]);
+        deque.truncate(4);
+        assert_eq!(contents(&deque), [2, 3, 4, 6]);
     }
-}
 
-impl<T, const N: usize> From<StackArrayDeque<T, N>> for VecDeque<T> {
-    /// Converts this deque into a `VecDeque`, preserving order.
-    fn from(deque: StackArrayDeque<T, N>) -> Self {
-        deque.into_iter().collect()
+    #[test]
+    fn truncate_front_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        deque.truncate_front(3);
+        assert_eq!(contents(&deque), [3, 4, 5]);
+        deque.push_front(2);
+        assert_eq!(contents(&deque), [2, 3, 4, 5]);
+        deque.truncate_front(1);
+        assert_eq!(contents(&deque), [5]);
     }
-}
 
-impl<T: Clone, const N: usize> From<&StackArrayDeque<T, N>> for VecDeque<T> {
-    /// Clones elements into a `VecDeque`, preserving order.
-    fn from(deque: &StackArrayDeque<T, N>) -> Self {
-        deque.iter().cloned().collect()
-    }
-}
+    #[test]
+    fn truncate_to_zero_is_clear() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque: StackArrayDeque<DropCounter, 3> = StackArrayDeque::new();
+            for _ in 0..3 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            deque.truncate(0);
+            assert!(deque.is_empty());
+            assert_eq!(drops.load(Ordering::SeqCst), 3);
 
-/// An owning iterator that moves elements out of a `StackArrayDeque`.
-///
-/// This is returned by `into_iter()`.
-pub struct StackArrayDequeIntoIter<T, const N: usize> {
-    deque: StackArrayDeque<T, N>,
-}
+            for _ in 0..3 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            deque.truncate_front(0);
+            assert!(deque.is_empty());
+            assert_eq!(drops.load(Ordering::SeqCst), 6);
+        }
 
-impl<T, const N: usize> Iterator for StackArrayDequeIntoIter<T, N> {
-    type Item = T;
-    /// Advances and returns the next element, front to back.
-    fn next(&mut self) -> Option<T> {
-        self.deque.pop_front()
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
     }
-}
 
-impl<T, const N: usize> IntoIterator for StackArrayDeque<T, N> {
-    type Item = T;
-    type IntoIter = StackArrayDequeIntoIter<T, N>;
-    /// Consumes the deque and returns an iterator over its elements.
-    fn into_iter(self) -> Self::IntoIter {
-        StackArrayDequeIntoIter { deque: self }
+    #[test]
+    fn push_slice_stops_when_full() {
+        let mut deque: StackArrayDeque<u8, 5> = StackArrayDeque::new();
+        for byte in *b"xxxab" {
+            deque.push_back(byte);
+        }
+        deque.truncate_front(2);
+        assert_eq!(deque.push_slice(b"cdefg"), 3);
+        assert_eq!(deque.as_slices(), (&b"ab"[..], &b"cde"[..]));
+        assert_eq!(deque.push_slice(b"f"), 0);
+        assert_eq!(StackArrayDeque::<u8, 0>::new().push_slice(b"a"), 0);
     }
-}
 
-/// A borrowed iterator over `&T` from a `StackArrayDeque`.
-///
-/// This is returned by `iter()` and `&deque.into_iter()`.
-pub struct StackArrayDequeIter<'a, T, const N: usize> {
-    deque: &'a StackArrayDeque<T, N>,
-    pos: usize,
-}
+    #[test]
+    fn push_slice_overwrite_keeps_newest() {
+        let mut deque: StackArrayDeque<u8, 5> = StackArrayDeque::new();
+        deque.push_slice(b"abcd");
+        deque.push_slice_overwrite(b"efg");
+        assert_eq!(deque.as_slices(), (&b"cde"[..], &b"fg"[..]));
+        deque.push_slice_overwrite(b"hijklmn");
+        assert_eq!(contents(&deque), b"jklmn");
 
-impl<'a, T, const N: usize> Iterator for StackArrayDequeIter<'a, T, N> {
-    type Item = &'a T;
-    /// Advances and returns the next reference, front to back.
-    fn next(&mut self) -> Option<&'a T> {
-        if self.pos >= self.deque.len {
-            None
-        } else {
-            let idx = (self.deque.idx + self.pos) % N;
-            self.pos += 1;
-            Some(unsafe { self.deque.data[idx].assume_init_ref() })
-        }
+        let mut empty: StackArrayDeque<u8, 0> = StackArrayDeque::new();
+        empty.push_slice_overwrite(b"abc");
+        assert!(empty.is_empty());
     }
-}
 
-impl<'a, T, const N: usize> IntoIterator for &'a StackArrayDeque<T, N> {
-    type Item = &'a T;
-    type IntoIter = StackArrayDequeIter<'a, T, N>;
-    /// Borrows the deque and returns an iterator over `&T`.
-    fn into_iter(self) -> Self::IntoIter {
-        StackArrayDequeIter {
-            deque: self,
-            pos: 0,
-        }
+    #[test]
+    fn pop_slice_straddles_wrap_point() {
+        let mut deque: StackArrayDeque<u8, 5> = StackArrayDeque::new();
+        deque.push_slice(b"abcd");
+        deque.push_slice_overwrite(b"efg");
+        assert_eq!(deque.as_slices(), (&b"cde"[..], &b"fg"[..]));
+
+        let mut buf = [0; 4];
+        assert_eq!(deque.pop_slice(&mut buf), 4);
+        assert_eq!(&buf, b"cdef");
+        assert_eq!(deque.pop_slice(&mut buf), 1);
+        assert_eq!(buf[0], b'g');
+        assert_eq!(deque.pop_slice(&mut buf), 0);
+        assert!(deque.is_empty());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use core::sync::atomic::{AtomicUsize, Ordering};
+    #[test]
+    #[cfg(feature = "embedded-io")]
+    fn embedded_io_round_trip() {
+        use embedded_io::{Read, ReadReady, Write, WriteReady};
 
-    #[cfg(not(feature = "std"))]
-    use alloc::{format, sync::Arc};
-    #[cfg(feature = "std")]
-    use std::sync::Arc;
+        let mut deque: StackArrayDeque<u8, 5> = StackArrayDeque::new();
+        assert_eq!(deque.read_ready(), Ok(false));
+        assert_eq!(deque.write(b"abc"), Ok(3));
+        let mut buf = [0; 2];
+        assert_eq!(deque.read(&mut buf), Ok(2));
+        assert_eq!(&buf, b"ab");
 
-    #[derive(Clone)]
-    struct DropCounter {
-        drops: Arc<AtomicUsize>,
-    }
+        assert_eq!(deque.write(b"defghi"), Ok(4));
+        assert_eq!(deque.write_ready(), Ok(false));
+        assert_eq!(deque.write(b"j"), Err(crate::FullError));
+        assert_eq!(deque.write(b""), Ok(0));
+        assert_eq!(deque.as_slices(), (&b"cde"[..], &b"fg"[..]));
 
-    impl DropCounter {
-        fn new(drops: Arc<AtomicUsize>) -> Self {
-            Self { drops }
-        }
+        let mut buf = [0; 8];
+        assert_eq!(deque.read(&mut buf), Ok(5));
+        assert_eq!(&buf[..5], b"cdefg");
+        assert_eq!(deque.read(&mut buf), Ok(0));
+        assert_eq!(deque.read_ready(), Ok(false));
+        assert_eq!(deque.write_ready(), Ok(true));
+        deque.flush().unwrap();
+
+        // `write_all` reports the full deque instead of panicking on `Ok(0)`.
+        let err = deque.write_all(b"xyz123").unwrap_err();
+        assert_eq!(
+            embedded_io::Error::kind(&err),
+            embedded_io::ErrorKind::WriteZero
+        );
+        assert_eq!(deque.len(), 5);
+        assert!(deque.write_all(b"").is_ok());
     }
 
-    impl Drop for DropCounter {
-        fn drop(&mut self) {
-            self.drops.fetch_add(1, Ordering::SeqCst);
+    #[test]
+    fn fmt_write_keeps_newest_text() {
+        use core::fmt::Write;
+
+        let mut deque: StackArrayDeque<u8, 10> = StackArrayDeque::new();
+        write!(deque, "abc").unwrap();
+        assert_eq!(deque.as_str_lossy(), "abc");
+        for i in 0..5 {
+            write!(deque, "[{i}]").unwrap();
         }
+        assert_eq!(deque.as_str_lossy(), "][2][3][4]");
+        assert!(deque.is_full());
     }
 
     #[test]
-    fn push_pop() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        assert_eq!(deque.pop_front(), Some(1));
-        assert_eq!(deque.pop_back(), Some(3));
-        assert_eq!(deque.pop_front(), Some(2));
-        assert_eq!(deque.pop_front(), None);
+    fn as_str_lossy_skips_partial_codepoint() {
+        use core::fmt::Write;
+
+        let mut deque: StackArrayDeque<u8, 6> = StackArrayDeque::new();
+        write!(deque, "ab").unwrap();
+        // "€" is three bytes; after this write only its last byte remains.
+        write!(deque, "€xyzw").unwrap();
+        assert_eq!(deque.len(), 6);
+        assert_eq!(deque.as_str_lossy(), "xyzw");
+
+        deque.clear();
+        deque.push_slice(b"ok\xFFno");
+        assert_eq!(deque.as_str_lossy(), "ok");
+
+        let mut deque: StackArrayDeque<u8, 3> = StackArrayDeque::new();
+        write!(deque, "é").unwrap();
+        write!(deque, "€").unwrap();
+        assert_eq!(deque.as_str_lossy(), "€");
+        write!(deque, "a").unwrap();
+        assert_eq!(deque.as_str_lossy(), "a");
     }
 
     #[test]
-    fn push_front_back() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_front(1);
-        deque.push_front(2);
-        deque.push_back(3);
-        assert_eq!(deque.pop_front(), Some(2));
-        assert_eq!(deque.pop_back(), Some(3));
-        assert_eq!(deque.pop_front(), Some(1));
-        assert_eq!(deque.pop_front(), None);
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary_generates_wrapped_deques() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut rng = TestRng::new(0x2545_f491);
+        let mut wrapped = 0;
+        for _ in 0..64 {
+            let data: Vec<u8> = (0..48).map(|_| rng.next_u16() as u8).collect();
+            let deque =
+                <StackArrayDeque<u16, 8>>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            assert!(deque.len() <= deque.capacity());
+            assert_eq!(deque.iter().count(), deque.len());
+            if !deque.as_slices().1.is_empty() {
+                wrapped += 1;
+            }
+        }
+        assert!(wrapped > 0);
+        assert_eq!(<StackArrayDeque<u16, 8>>::size_hint(0).0, 0);
     }
 
     #[test]
-    fn iter() {
-        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), Some(&3));
-        assert_eq!(iter.next(), None);
+    #[cfg(feature = "quickcheck")]
+    fn quickcheck_push_pop_round_trip() {
+        fn prop(mut deque: StackArrayDeque<i32, 6>, x: i32) -> bool {
+            if deque.is_full() {
+                return true;
+            }
+            let before = deque.to_vec();
+            deque.push_back(x);
+            let back = deque.pop_back();
+            deque.push_front(x);
+            let front = deque.pop_front();
+            back == Some(x) && front == Some(x) && deque.to_vec() == before
+        }
+        quickcheck::quickcheck(prop as fn(StackArrayDeque<i32, 6>, i32) -> bool);
     }
 
     #[test]
-    fn clear() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.clear();
-        assert!(deque.is_empty());
-        assert_eq!(deque.len(), 0);
+    #[cfg(feature = "quickcheck")]
+    fn quickcheck_iter_matches_index() {
+        fn prop(deque: StackArrayDeque<i32, 6>) -> bool {
+            deque.iter().enumerate().all(|(i, x)| *x == deque[i])
+        }
+        quickcheck::quickcheck(prop as fn(StackArrayDeque<i32, 6>) -> bool);
     }
 
     #[test]
-    fn capacity() {
-        let deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        assert_eq!(deque.capacity(), 5);
-        assert!(deque.is_empty());
+    #[cfg(feature = "quickcheck")]
+    fn quickcheck_shrink_unwraps_then_shrinks() {
+        use quickcheck::Arbitrary;
+
+        let mut deque: StackArrayDeque<i32, 6> = Default::default();
+        for i in 0..8 {
+            deque.push_back(i);
+        }
+        deque.pop_back();
+        let mut shrunk = deque.shrink();
+        let first = shrunk.next().unwrap();
+        assert_eq!(first, deque);
+        assert!(first.as_slices().1.is_empty());
+        for candidate in shrunk {
+            assert!(candidate.capacity() <= deque.capacity());
+            assert!(candidate.len() <= deque.len());
+        }
     }
 
     #[test]
-    fn clone() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        let cloned_deque = deque.clone();
-        assert_eq!(cloned_deque.len(), 2);
-        assert_eq!(cloned_deque[0], 1);
-        assert_eq!(cloned_deque[1], 2);
+    fn copy_to_slice_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        let mut buf = [0; 4];
+        deque.copy_to_slice(&mut buf);
+        assert_eq!(buf, [2, 3, 4, 5]);
+        assert_eq!(contents(&deque), [2, 3, 4, 5]);
+
+        let mut strings = vec![String::new(); 4];
+        let mut words: StackArrayDeque<String, 4> = StackArrayDeque::new();
+        for item in deque.iter().chain(&[6, 7]) {
+            words.push_back(format!("{item}"));
+        }
+        words.clone_into_slice(&mut strings);
+        assert_eq!(strings, ["4", "5", "6", "7"]);
     }
 
     #[test]
-    fn index() {
-        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        assert_eq!(deque[0], 1);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+    #[should_panic(expected = "destination length does not match deque length")]
+    fn copy_to_slice_larger_dst_panics() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        deque.extend([1, 2, 3]);
+        deque.copy_to_slice(&mut [0; 4]);
     }
 
     #[test]
-    #[should_panic]
-    fn index_out_of_bounds_panics() {
-        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        deque.push_back(1);
-        let _ = deque[1];
+    #[should_panic(expected = "destination length does not match deque length")]
+    fn clone_into_slice_smaller_dst_panics() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        deque.extend([1, 2, 3]);
+        deque.clone_into_slice(&mut [0; 2]);
     }
 
     #[test]
-    fn index_mut() {
-        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        deque[0] = 10;
-        assert_eq!(deque[0], 10);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+    #[cfg(feature = "alloc")]
+    fn to_vec_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        let vec = deque.to_vec();
+        assert_eq!(vec, [2, 3, 4, 5]);
+        assert_eq!(vec.capacity(), 4);
+        assert_eq!(contents(&deque), [2, 3, 4, 5]);
+        assert!(StackArrayDeque::<i32, 0>::new().to_vec().is_empty());
     }
 
     #[test]
-    #[should_panic]
-    fn index_mut_out_of_bounds_panics() {
-        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque[1] = 99;
+    fn into_array_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        assert_eq!(deque.into_array().ok(), Some([2, 3, 4, 5]));
+
+        let mut pushed_front: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        pushed_front.push_front(3);
+        pushed_front.push_front(2);
+        pushed_front.push_front(1);
+        assert_eq!(pushed_front.into_array().ok(), Some([1, 2, 3]));
     }
 
     #[test]
-    fn iter_empty() {
-        let deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), None);
+    fn into_array_not_full_returns_deque() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<DropCounter, 3> = StackArrayDeque::new();
+        deque.push_back(DropCounter::new(drops.clone()));
+        deque.push_back(DropCounter::new(drops.clone()));
+
+        let Err(deque) = deque.into_array() else {
+            panic!("a deque that is not full must be returned");
+        };
+        assert_eq!(deque.len(), 2);
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
     }
 
     #[test]
-    fn iter_full() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), Some(&3));
-        assert_eq!(iter.next(), None);
+    fn into_array_drops_each_element_once() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<DropCounter, 2> = StackArrayDeque::new();
+        for _ in 0..3 {
+            deque.push_back(DropCounter::new(drops.clone()));
+        }
+        let array = deque.into_array().ok().unwrap();
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        drop(array);
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
     }
 
     #[test]
-    fn iter_partial() {
-        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), None);
+    fn into_partial_array_wrapped() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4] {
+            deque.push_back(item);
+        }
+        deque.pop_front();
+        assert_eq!(
+            deque.into_partial_array(),
+            [Some(2), Some(3), Some(4), None]
+        );
     }
 
     #[test]
-    fn is_empty() {
-        let deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
-        assert!(deque.is_empty());
-        assert_eq!(deque.len(), 0);
+    fn contains_and_index_of_empty() {
+        let deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        assert!(!deque.contains(&0));
+        assert_eq!(deque.index_of(&0), None);
+        assert_eq!(deque.as_slices(), (&[][..], &[][..]));
     }
 
     #[test]
-    fn is_full() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        assert!(!deque.is_full());
-        deque.push_back(1);
-        deque.push_back(2);
-        assert!(!deque.is_full());
-        deque.push_back(3);
-        assert!(deque.is_full());
+    fn contains_and_index_of_at_wrap_boundary() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [0, 1, 2, 3, 4] {
+            deque.push_back(item);
+        }
+        // Physical layout is [4, 1, 2, 3]: 3 is the last slot, 4 the first.
+        assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[4][..]));
+        assert!(deque.contains(&3));
+        assert!(deque.contains(&4));
+        assert!(!deque.contains(&0));
+        assert_eq!(deque.index_of(&3), Some(2));
+        assert_eq!(deque.index_of(&4), Some(3));
+        assert_eq!(deque.index_of(&0), None);
     }
 
-    #[test]
-    fn clear_empty() {
-        let mut deque: StackArrayDeque<(), 3> = StackArrayDeque::new();
-        deque.clear();
-        assert!(deque.is_empty());
-        assert_eq!(deque.len(), 0);
+    #[test]
+    fn binary_search_wrapped() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        for item in [0, 10, 20, 30, 40, 50, 60] {
+            deque.push_back(item);
+        }
+        assert_eq!(deque.binary_search(&20), Ok(0));
+        assert_eq!(deque.binary_search(&50), Ok(3));
+        assert_eq!(deque.binary_search(&45), Err(3));
+        assert_eq!(deque.binary_search(&70), Err(5));
+        assert_eq!(deque.binary_search_by(|x| x.cmp(&60)), Ok(4));
     }
 
     #[test]
-    fn clear_non_empty() {
+    fn insert_sorted_into_empty() {
         let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.clear();
-        assert!(deque.is_empty());
-        assert_eq!(deque.len(), 0);
+        deque.insert_sorted(7);
+        assert_eq!(contents(&deque), [7]);
     }
 
     #[test]
-    fn overflow_behavior_push_back() {
+    fn insert_sorted_when_full() {
         let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        assert!(deque.is_full());
+        for item in [0, 0, 2, 4, 6] {
+            deque.push_back(item);
+        }
+        deque.pop_back();
+        deque.insert_sorted(4);
+        assert_eq!(contents(&deque), [2, 4, 4]);
 
-        // This should overwrite the front element (1)
-        deque.push_back(4);
-        assert_eq!(deque.len(), 3);
-        assert_eq!(deque[0], 2);
-        assert_eq!(deque[1], 3);
-        assert_eq!(deque[2], 4);
+        // Better than the current worst: the worst is evicted.
+        deque.insert_sorted(3);
+        assert_eq!(contents(&deque), [2, 3, 4]);
+
+        // Worse than (or equal to) the current worst: discarded.
+        deque.insert_sorted(4);
+        deque.insert_sorted(9);
+        assert_eq!(contents(&deque), [2, 3, 4]);
     }
 
     #[test]
-    fn overflow_behavior_push_front() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
-        assert!(deque.is_full());
+    fn insert_sorted_is_stable() {
+        #[derive(Debug, Clone)]
+        struct Job(u8, char);
+        impl PartialEq for Job {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Job {}
+        impl PartialOrd for Job {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Job {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
 
-        // This should overwrite the back element (3)
-        deque.push_front(0);
-        assert_eq!(deque.len(), 3);
-        assert_eq!(deque[0], 0);
-        assert_eq!(deque[1], 1);
-        assert_eq!(deque[2], 2);
+        let mut deque: StackArrayDeque<Job, 4> = StackArrayDeque::new();
+        deque.insert_sorted(Job(1, 'a'));
+        deque.insert_sorted(Job(2, 'b'));
+        deque.insert_sorted(Job(1, 'c'));
+        deque.insert_sorted(Job(1, 'd'));
+        let tags: VecDeque<_> = deque.iter().map(|j| j.1).collect();
+        assert_eq!(tags, ['a', 'c', 'd', 'b']);
     }
 
     #[test]
-    fn default() {
-        let deque: StackArrayDeque<i32, 5> = StackArrayDeque::default();
+    fn sort_unstable_single_capacity() {
+        let mut deque: StackArrayDeque<i32, 1> = StackArrayDeque::new();
+        deque.sort_unstable();
         assert!(deque.is_empty());
-        assert_eq!(deque.capacity(), 5);
+        for item in [2, 1] {
+            deque.push_back(item);
+        }
+        deque.sort_unstable();
+        assert_eq!(contents(&deque), [1]);
     }
 
     #[test]
-    fn debug_format() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        deque.push_back(1);
-        deque.push_back(2);
-        let debug_str = format!("{:?}", deque);
-        assert_eq!(debug_str, "[1, 2]");
+    fn sort_unstable_full_wrapped() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        for item in [0, 0, 0, 8, 2, 6, 4, 1] {
+            deque.push_back(item);
+        }
+        assert_eq!(deque.as_slices(), (&[8, 2][..], &[6, 4, 1][..]));
+        deque.sort_unstable();
+        assert_eq!(contents(&deque), [1, 2, 4, 6, 8]);
+        deque.push_back(9);
+        assert_eq!(contents(&deque), [2, 4, 6, 8, 9]);
     }
 
     #[test]
-    fn partial_eq() {
-        let mut deque1: StackArrayDeque<i32, 3> = StackArrayDeque::new();
-        let mut deque2: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    fn sort_unstable_by_reverse_sorted_input() {
+        let mut deque: StackArrayDeque<i32, 6> = StackArrayDeque::new();
+        deque.extend([0, 0, 0, 0]);
+        for _ in 0..4 {
+            deque.pop_front();
+        }
+        for i in (0..5).rev() {
+            deque.push_back(i);
+        }
+        deque.sort_unstable_by(|a, b| a.cmp(b));
+        assert_eq!(contents(&deque), [0, 1, 2, 3, 4]);
+        deque.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(contents(&deque), [4, 3, 2, 1, 0]);
+    }
 
-        assert_eq!(deque1, deque2);
+    #[test]
+    #[cfg(feature = "std")]
+    fn sort_unstable_by_panicking_comparator() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
 
-        deque1.push_back(1);
-        deque1.push_back(2);
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque: StackArrayDeque<(u8, DropCounter), 6> = StackArrayDeque::new();
+            for i in [0, 0, 5, 3, 9, 1, 7, 2] {
+                deque.push_back((i, DropCounter::new(drops.clone())));
+            }
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
 
-        deque2.push_back(1);
-        deque2.push_back(2);
+            let mut calls = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                deque.sort_unstable_by(|a, b| {
+                    calls += 1;
+                    if calls == 4 {
+                        panic!("comparator failure");
+                    }
+                    a.0.cmp(&b.0)
+                });
+            }));
+            assert!(result.is_err());
+            assert_eq!(deque.len(), 6);
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
 
-        assert_eq!(deque1, deque2);
+            let mut keys: VecDeque<u8> = deque.iter().map(|e| e.0).collect();
+            keys.make_contiguous().sort_unstable();
+            assert_eq!(keys, [1, 2, 3, 5, 7, 9]);
+        }
 
-        deque2.push_back(3);
-        assert_ne!(deque1, deque2);
+        assert_eq!(drops.load(Ordering::SeqCst), 8);
     }
 
     #[test]
-    fn circular_behavior() {
-        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+    fn rotate_zero_and_len_are_noops() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend([1, 2, 3]);
+        deque.rotate_left(0);
+        deque.rotate_right(0);
+        assert_eq!(contents(&deque), [1, 2, 3]);
+        deque.rotate_left(3);
+        assert_eq!(contents(&deque), [1, 2, 3]);
+        deque.rotate_right(3);
+        assert_eq!(contents(&deque), [1, 2, 3]);
+    }
 
-        // Fill the deque
-        deque.push_back(1);
-        deque.push_back(2);
-        deque.push_back(3);
+    #[test]
+    #[should_panic]
+    fn rotate_more_than_len_panics() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend([1, 2, 3]);
+        deque.rotate_left(4);
+    }
 
-        // Remove from front and add to back (should wrap around)
-        assert_eq!(deque.pop_front(), Some(1));
-        deque.push_back(4);
+    #[test]
+    fn rotate_partial_across_physical_end() {
+        let mut deque: StackArrayDeque<i32, 5> = StackArrayDeque::new();
+        deque.extend([0, 0, 0]);
+        for _ in 0..3 {
+            deque.pop_front();
+        }
+        // Front sits at slot 3, so the contents wrap around the end.
+        deque.extend([1, 2, 3, 4]);
+        deque.rotate_left(1);
+        assert_eq!(contents(&deque), [2, 3, 4, 1]);
+        deque.rotate_left(3);
+        assert_eq!(contents(&deque), [1, 2, 3, 4]);
+        deque.rotate_right(1);
+        assert_eq!(contents(&deque), [4, 1, 2, 3]);
+        deque.rotate_right(3);
+        assert_eq!(contents(&deque), [1, 2, 3, 4]);
+    }
 
-        assert_eq!(deque[0], 2);
-        assert_eq!(deque[1], 3);
-        assert_eq!(deque[2], 4);
+    #[test]
+    fn rotate_full_buffer() {
+        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+        for item in [1, 2, 3, 4, 5] {
+            deque.push_back(item);
+        }
+        deque.rotate_left(3);
+        assert_eq!(contents(&deque), [5, 2, 3, 4]);
+        deque.rotate_right(1);
+        assert_eq!(contents(&deque), [4, 5, 2, 3]);
+    }
 
-        // Remove from back and add to front (should wrap around)
-        assert_eq!(deque.pop_back(), Some(4));
-        deque.push_front(0);
+    #[test]
+    fn rotate_does_not_drop() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let mut deque: StackArrayDeque<DropCounter, 4> = StackArrayDeque::new();
+            for _ in 0..3 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            deque.rotate_left(1);
+            deque.rotate_right(2);
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
+        }
 
-        assert_eq!(deque[0], 0);
-        assert_eq!(deque[1], 2);
-        assert_eq!(deque[2], 3);
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
     }
 
     #[test]
-    fn mixed_operations() {
-        let mut deque: StackArrayDeque<i32, 4> = StackArrayDeque::new();
+    fn try_extend_stops_at_first_rejected_element() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut deque: StackArrayDeque<(i32, DropCounter), 3> = StackArrayDeque::new();
+        deque.push_back((0, DropCounter::new(drops.clone())));
 
-        deque.push_back(1);
-        deque.push_front(0);
-        deque.push_back(2);
-        deque.push_front(-1);
+        let items = (1..6).map(|i| (i, DropCounter::new(drops.clone())));
+        let err = deque.try_extend(items).unwrap_err();
+        assert_eq!(err.element.0, 3);
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [0, 1, 2]);
 
-        assert_eq!(deque.len(), 4);
-        assert!(deque.is_full());
+        let rest: Vec<_> = err.remaining.map(|(i, _)| i).collect();
+        assert_eq!(rest, [4, 5]);
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
 
-        assert_eq!(deque[0], -1);
-        assert_eq!(deque[1], 0);
-        assert_eq!(deque[2], 1);
-        assert_eq!(deque[3], 2);
+        assert!(deque.try_extend(core::iter::empty()).is_ok());
+        deque.pop_front();
+        assert!(
+            deque
+                .try_extend([(9, DropCounter::new(drops.clone()))])
+                .is_ok()
+        );
+        assert_eq!(deque.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [1, 2, 9]);
+    }
 
-        assert_eq!(deque.pop_front(), Some(-1));
-        assert_eq!(deque.pop_back(), Some(2));
-        assert_eq!(deque.len(), 2);
+    #[test]
+    fn try_push_back_and_front() {
+        let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
+        assert_eq!(deque.try_push_back(2), Ok(()));
+        assert_eq!(deque.try_push_front(1), Ok(()));
+        assert_eq!(deque.try_push_back(3), Ok(()));
+        assert_eq!(deque.try_push_back(4), Err(4));
+        assert_eq!(deque.try_push_front(0), Err(0));
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[0], 1);
+        assert_eq!(deque[1], 2);
+        assert_eq!(deque[2], 3);
 
-        assert_eq!(deque[0], 0);
-        assert_eq!(deque[1], 1);
+        // The head index must not move on rejection.
+        assert_eq!(deque.pop_front(), Some(1));
+        deque.push_back(4);
+        assert_eq!(deque[0], 2);
+        assert_eq!(deque[2], 4);
     }
 
     #[test]
-    fn single_capacity() {
+    fn try_push_single_capacity() {
         let mut deque: StackArrayDeque<i32, 1> = StackArrayDeque::new();
-        assert_eq!(deque.capacity(), 1);
-
-        deque.push_back(1);
-        assert_eq!(deque.len(), 1);
-        assert!(deque.is_full());
+        assert_eq!(deque.try_push_front(1), Ok(()));
+        assert_eq!(deque.try_push_back(2), Err(2));
+        assert_eq!(deque.try_push_front(3), Err(3));
         assert_eq!(deque[0], 1);
-
-        // Pushing another element should overwrite
-        deque.push_back(2);
-        assert_eq!(deque.len(), 1);
+        assert_eq!(deque.pop_back(), Some(1));
+        assert_eq!(deque.try_push_back(2), Ok(()));
         assert_eq!(deque[0], 2);
-
-        assert_eq!(deque.pop_front(), Some(2));
-        assert!(deque.is_empty());
     }
 
     #[test]
-    fn push_back_overwrite_drops_replaced_element() {
+    fn try_push_on_full_does_not_drop_or_overwrite() {
         let drops = Arc::new(AtomicUsize::new(0));
         {
             let mut deque: StackArrayDeque<DropCounter, 2> = StackArrayDeque::new();
             deque.push_back(DropCounter::new(drops.clone()));
             deque.push_back(DropCounter::new(drops.clone()));
-            deque.push_back(DropCounter::new(drops.clone()));
 
-            assert_eq!(drops.load(Ordering::SeqCst), 1);
+            let back = deque.try_push_back(DropCounter::new(drops.clone()));
+            let front = deque.try_push_front(DropCounter::new(drops.clone()));
+            assert!(back.is_err());
+            assert!(front.is_err());
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
+            assert_eq!(deque.len(), 2);
+
+            drop(back);
+            drop(front);
+            assert_eq!(drops.load(Ordering::SeqCst), 2);
         }
 
-        assert_eq!(drops.load(Ordering::SeqCst), 3);
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
     }
 
     #[test]
@@ -954,6 +5534,50 @@ mod tests {
     }
 
     #[test]
+    fn into_iter_drop_counts_by_consumption() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let make = || {
+            let mut deque: StackArrayDeque<DropCounter, 8> = StackArrayDeque::new();
+            for _ in 0..8 {
+                deque.push_back(DropCounter::new(drops.clone()));
+            }
+            deque.into_iter()
+        };
+
+        // Zero consumption.
+        drop(make());
+        assert_eq!(drops.load(Ordering::SeqCst), 8);
+
+        // Partial consumption.
+        {
+            let mut iter = make();
+            drop(iter.next());
+            drop(iter.next());
+            drop(iter.next());
+            assert_eq!(drops.load(Ordering::SeqCst), 11);
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 16);
+
+        // Full consumption.
+        for item in make() {
+            drop(item);
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 24);
+    }
+
+    #[test]
+    fn into_iter_partial_consumption_of_heap_values() {
+        let mut deque: StackArrayDeque<Vec<u8>, 8> = StackArrayDeque::new();
+        for i in 0..10 {
+            deque.push_back(vec![i; 16]);
+        }
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next(), Some(vec![2; 16]));
+        assert_eq!(iter.next(), Some(vec![3; 16]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
     fn try_from_vecdeque_within_capacity() {
         let vec_deque: VecDeque<_> = [1, 2, 3].into_iter().collect();
         let deque: StackArrayDeque<_, 3> = StackArrayDeque::try_from(vec_deque).unwrap();
@@ -965,6 +5589,56 @@ mod tests {
     }
 
     #[test]
+    fn from_array_is_full() {
+        let mut deque = StackArrayDeque::from([1, 2, 3]);
+        assert!(deque.is_full());
+        assert_eq!(contents(&deque), [1, 2, 3]);
+
+        deque.push_back(4);
+        assert_eq!(contents(&deque), [2, 3, 4]);
+    }
+
+    #[test]
+    fn from_array_drops_each_element_once() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let deque = StackArrayDeque::from([
+            DropCounter::new(drops.clone()),
+            DropCounter::new(drops.clone()),
+        ]);
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(deque);
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_from_vec_exact_and_under_full() {
+        let exact: StackArrayDeque<_, 3> = StackArrayDeque::try_from(vec![1, 2, 3]).unwrap();
+        assert!(exact.is_full());
+        assert_eq!(contents(&exact), [1, 2, 3]);
+
+        let mut partial: StackArrayDeque<_, 4> = StackArrayDeque::try_from(vec![1, 2]).unwrap();
+        assert_eq!(partial.len(), 2);
+        partial.push_front(0);
+        assert_eq!(contents(&partial), [0, 1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_from_vec_over_capacity_errors() {
+        let result: Result<StackArrayDeque<_, 3>, CapacityError> =
+            StackArrayDeque::try_from(vec![1, 2, 3, 4]);
+        assert_eq!(
+            result,
+            Err(CapacityError {
+                len: 4,
+                capacity: 3,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
     fn try_from_vecdeque_over_capacity_errors() {
         let vec_deque: VecDeque<_> = [1, 2, 3, 4].into_iter().collect();
         let result: Result<StackArrayDeque<_, 3>, CapacityError> =
@@ -980,6 +5654,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
     fn into_vecdeque_preserves_order() {
         let mut deque: StackArrayDeque<i32, 3> = StackArrayDeque::new();
         deque.push_back(1);
@@ -990,4 +5665,146 @@ mod tests {
         let expected: VecDeque<_> = [1, 2, 3].into_iter().collect();
         assert_eq!(vec_deque, expected);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_deserialize_without_alloc() {
+        let (deque, _): (StackArrayDeque<u32, 4>, _) =
+            serde_json_core::from_str("[1,2,3]").unwrap();
+        assert_eq!(contents(&deque), [1, 2, 3]);
+
+        let deque: StackArrayDeque<u32, 4> = postcard::from_bytes(&[2, 7, 8]).unwrap();
+        assert_eq!(contents(&deque), [7, 8]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_deserialize_rejects_more_than_capacity() {
+        let result = serde_json_core::from_str::<StackArrayDeque<u32, 2>>("[1,2,3]");
+        assert!(result.is_err());
+
+        // The length prefix alone is enough to reject the sequence.
+        let result = postcard::from_bytes::<StackArrayDeque<u32, 2>>(&[3, 1, 2, 3]);
+        assert!(matches!(result, Err(postcard::Error::SerdeDeCustom)));
+    }
+
+    #[test]
+    #[cfg(feature = "bincode")]
+    fn bincode_round_trip_rotated() {
+        let config = bincode::config::standard();
+        let mut deque: StackArrayDeque<u16, 4> = StackArrayDeque::new();
+        deque.extend([1, 2, 3, 4, 5, 6]);
+        deque.pop_front();
+        let mut buf = [0; 16];
+        let written = bincode::encode_into_slice(&deque, &mut buf, config).unwrap();
+
+        let (decoded, read): (StackArrayDeque<u16, 4>, _) =
+            bincode::decode_from_slice(&buf[..written], config).unwrap();
+        assert_eq!(read, written);
+        assert_eq!(contents(&decoded), [4, 5, 6]);
+
+        let (borrowed, _): (StackArrayDeque<u16, 4>, _) =
+            bincode::borrow_decode_from_slice(&buf[..written], config).unwrap();
+        assert_eq!(contents(&borrowed), [4, 5, 6]);
+    }
+
+    #[test]
+    #[cfg(feature = "bincode")]
+    fn bincode_rejects_more_than_capacity() {
+        let config = bincode::config::standard();
+        let bytes = bincode::encode_to_vec([1u8, 2, 3].as_slice(), config).unwrap();
+        let result = bincode::decode_from_slice::<StackArrayDeque<u8, 2>, _>(&bytes, config);
+        assert!(matches!(
+            result,
+            Err(bincode::error::DecodeError::ArrayLengthMismatch {
+                required: 2,
+                found: 3
+            })
+        ));
+    }
+
+    /// Reads every slot of the array, whether or not it holds an element.
+    ///
+    /// Only meaningful once every slot has been written at least once.
+    #[cfg(feature = "zeroize")]
+    fn raw_slots<T: Copy, const N: usize>(deque: &StackArrayDeque<T, N>) -> [T; N] {
+        deque.data.map(|slot| unsafe { slot.assume_init() })
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize_spare_capacity_clears_popped_slots() {
+        let mut deque: StackArrayDeque<[u8; 32], 4> = StackArrayDeque::new();
+        for i in 1..=6 {
+            deque.push_back([i; 32]);
+        }
+        deque.pop_front();
+        deque.pop_back();
+        assert_eq!(raw_slots(&deque), [[5; 32], [6; 32], [3; 32], [4; 32]]);
+
+        deque.zeroize_spare_capacity();
+        assert_eq!(raw_slots(&deque), [[5; 32], [0; 32], [0; 32], [4; 32]]);
+        assert_eq!(contents(&deque), [[4; 32], [5; 32]]);
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize_wipes_elements_and_stale_slots() {
+        let mut deque: StackArrayDeque<u32, 3> = StackArrayDeque::new();
+        deque.extend([1, 2, 3, 4]);
+        deque.pop_front();
+        deque.zeroize();
+        assert!(deque.is_empty());
+        assert_eq!(raw_slots(&deque), [0; 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "defmt")]
+    fn defmt_format_writes_elements_front_to_back() {
+        let mut deque: StackArrayDeque<u32, 3> = StackArrayDeque::new();
+        deque.extend([0xdead_0001, 0xdead_0002, 0xdead_0003, 0xdead_0004]);
+        defmt::export::fetch_bytes();
+        defmt::write!(defmt::export::make_formatter(), "{}", deque);
+        let bytes = defmt::export::fetch_bytes();
+
+        let mut rest = &bytes[..];
+        for value in [0xdead_0002u32, 0xdead_0003, 0xdead_0004] {
+            let needle = value.to_le_bytes();
+            let at = rest.windows(4).position(|w| w == needle).unwrap();
+            rest = &rest[at + 4..];
+        }
+        assert!(!rest.windows(4).any(|w| w == 0xdead_0001u32.to_le_bytes()));
+    }
+
+    #[cfg(feature = "serde")]
+    static FAILING_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Deserializes from a `u32`, failing on 99 and counting drops.
+    #[cfg(feature = "serde")]
+    struct FailsOn99;
+
+    #[cfg(feature = "serde")]
+    impl<'de> Deserialize<'de> for FailsOn99 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            match u32::deserialize(deserializer)? {
+                99 => Err(de::Error::custom("got 99")),
+                _ => Ok(FailsOn99),
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl Drop for FailsOn99 {
+        fn drop(&mut self) {
+            FAILING_DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_deserialize_error_drops_initialized_elements() {
+        let result = serde_json_core::from_str::<StackArrayDeque<FailsOn99, 4>>("[1,2,99]");
+        assert!(result.is_err());
+        assert_eq!(FAILING_DROPS.load(Ordering::SeqCst), 2);
+    }
 }
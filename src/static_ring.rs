@@ -0,0 +1,271 @@
+use crate::StackArrayDeque;
+use core::cell::RefCell;
+use core::fmt;
+use critical_section::Mutex;
+
+/// A [`StackArrayDeque`] that can be declared as a `static` and shared between
+/// interrupt handlers and the main loop.
+///
+/// Every method takes `&self` and runs inside a
+/// [`critical_section::with`] block, so no `static mut` or `unsafe` is needed
+/// at the call site. What a critical section does is up to the
+/// `critical-section` implementation linked into the final binary; on
+/// single-core microcontrollers it typically disables interrupts, and on
+/// multi-core chips it also takes a spinlock.
+///
+/// # Blocking
+///
+/// The buffer itself never waits: [`push`](Self::push) overwrites the oldest
+/// element when full, [`try_push`](Self::try_push) hands the value back, and
+/// [`pop`](Self::pop) returns `None` when empty. The only blocking is the
+/// critical section, which is held for a single O(1) operation, plus the
+/// closure passed to [`with`](Self::with). Interrupts that arrive meanwhile
+/// are delayed until it ends, so keep those closures short. Elements evicted
+/// or popped are returned to the caller and dropped outside the critical
+/// section.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::StaticRingBuffer;
+///
+/// static RX: StaticRingBuffer<u8, 64> = StaticRingBuffer::new();
+///
+/// // In the UART interrupt handler:
+/// fn on_byte_received(byte: u8) {
+///     RX.push(byte);
+/// }
+///
+/// // In the main loop:
+/// on_byte_received(b'a');
+/// on_byte_received(b'b');
+/// assert_eq!(RX.pop(), Some(b'a'));
+/// assert_eq!(RX.pop(), Some(b'b'));
+/// assert_eq!(RX.pop(), None);
+/// ```
+pub struct StaticRingBuffer<T, const N: usize> {
+    /// The deque, only accessed inside a critical section
+    inner: Mutex<RefCell<StackArrayDeque<T, N>>>,
+}
+
+impl<T, const N: usize> StaticRingBuffer<T, N> {
+    /// Creates an empty buffer. Being `const`, it can initialize a `static`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StaticRingBuffer;
+    ///
+    /// static EVENTS: StaticRingBuffer<u32, 8> = StaticRingBuffer::new();
+    /// assert!(EVENTS.is_empty());
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(StackArrayDeque::new())),
+        }
+    }
+
+    /// Runs `f` with exclusive access to the underlying deque, inside a single
+    /// critical section.
+    ///
+    /// Use this to batch several operations so no interrupt can observe the
+    /// buffer in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called re-entrantly from within `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StaticRingBuffer;
+    ///
+    /// static SAMPLES: StaticRingBuffer<i16, 4> = StaticRingBuffer::new();
+    /// SAMPLES.with(|deque| deque.extend([1, 2, 3]));
+    /// let sum: i16 = SAMPLES.with(|deque| deque.iter().sum());
+    /// assert_eq!(sum, 6);
+    /// ```
+    pub fn with<R>(&self, f: impl FnOnce(&mut StackArrayDeque<T, N>) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.inner.borrow_ref_mut(cs)))
+    }
+
+    /// Appends `value` to the back of the buffer. If the buffer is full, the
+    /// front element is evicted and returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StaticRingBuffer;
+    ///
+    /// static LOG: StaticRingBuffer<char, 2> = StaticRingBuffer::new();
+    /// assert_eq!(LOG.push('a'), None);
+    /// assert_eq!(LOG.push('b'), None);
+    /// assert_eq!(LOG.push('c'), Some('a'));
+    /// ```
+    pub fn push(&self, value: T) -> Option<T> {
+        self.with(|deque| {
+            let evicted = if deque.is_full() {
+                deque.pop_front()
+            } else {
+                None
+            };
+            deque.push_back(value);
+            evicted
+        })
+    }
+
+    /// Appends `value` to the back of the buffer, or returns it if the buffer
+    /// is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::StaticRingBuffer;
+    ///
+    /// static QUEUE: StaticRingBuffer<u8, 1> = StaticRingBuffer::new();
+    /// assert_eq!(QUEUE.try_push(1), Ok(()));
+    /// assert_eq!(QUEUE.try_push(2), Err(2));
+    /// ```
+    pub fn try_push(&self, value: T) -> Result<(), T> {
+        self.with(|deque| deque.try_push_back(value))
+    }
+
+    /// Removes and returns the front element, or `None` if the buffer is empty.
+    pub fn pop(&self) -> Option<T> {
+        self.with(|deque| deque.pop_front())
+    }
+
+    /// Returns the number of elements in the buffer.
+    pub fn len(&self) -> usize {
+        self.with(|deque| deque.len())
+    }
+
+    /// Returns `true` if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the buffer holds `N` elements.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the maximum number of elements the buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Removes all elements from the buffer.
+    ///
+    /// The elements are dropped inside the critical section.
+    pub fn clear(&self) {
+        self.with(|deque| deque.clear());
+    }
+
+    /// Consumes the buffer and returns the underlying deque.
+    pub fn into_inner(self) -> StackArrayDeque<T, N> {
+        self.inner.into_inner().into_inner()
+    }
+
+    /// Returns a mutable reference to the underlying deque. No critical
+    /// section is needed, since `&mut self` guarantees exclusive access.
+    pub fn get_mut(&mut self) -> &mut StackArrayDeque<T, N> {
+        self.inner.get_mut().get_mut()
+    }
+}
+
+impl<T, const N: usize> Default for StaticRingBuffer<T, N> {
+    /// Creates an empty buffer.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> From<StackArrayDeque<T, N>> for StaticRingBuffer<T, N> {
+    /// Wraps an existing deque.
+    fn from(deque: StackArrayDeque<T, N>) -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(deque)),
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StaticRingBuffer<T, N> {
+    /// Formats the elements front to back, inside a critical section.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with(|deque| f.debug_tuple("StaticRingBuffer").field(deque).finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::thread;
+    use std::vec::Vec;
+
+    #[test]
+    fn push_evicts_oldest_when_full() {
+        let ring: StaticRingBuffer<u8, 3> = StaticRingBuffer::new();
+        for byte in 0..3 {
+            assert_eq!(ring.push(byte), None);
+        }
+        assert!(ring.is_full());
+        assert_eq!(ring.push(3), Some(0));
+        assert_eq!(ring.try_push(4), Err(4));
+        assert!(ring.into_inner().iter().eq(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn with_batches_operations() {
+        let mut ring = StaticRingBuffer::from(StackArrayDeque::from([1, 2, 3, 4]));
+        let drained: Vec<i32> = ring
+            .with(|deque| [deque.pop_front(), deque.pop_front()])
+            .into_iter()
+            .flatten()
+            .collect();
+        assert_eq!(drained, [1, 2]);
+        assert_eq!(ring.len(), 2);
+        ring.get_mut().push_front(0);
+        assert_eq!(std::format!("{ring:?}"), "StaticRingBuffer([0, 3, 4])");
+        ring.clear();
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn reentrant_with_panics() {
+        let ring: StaticRingBuffer<u8, 1> = StaticRingBuffer::new();
+        ring.with(|_| ring.pop());
+    }
+
+    #[test]
+    fn static_shared_between_threads() {
+        const ITEMS: u32 = 100_000;
+        static RING: StaticRingBuffer<u32, 16> = StaticRingBuffer::new();
+
+        // The spawned thread plays the interrupt handler.
+        let producer = thread::spawn(|| {
+            for i in 0..ITEMS {
+                let mut item = i;
+                while let Err(back) = RING.try_push(item) {
+                    item = back;
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut expected = 0;
+        while expected < ITEMS {
+            match RING.pop() {
+                Some(item) => {
+                    assert_eq!(item, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert!(RING.is_empty());
+    }
+}
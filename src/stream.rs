@@ -0,0 +1,248 @@
+use crate::{ArrayDeque, CapacityError, OverflowPolicy};
+use allocator_api2::alloc::{Allocator, Global};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use futures_sink::Sink;
+
+/// A [`Stream`] that drains a borrowed [`ArrayDeque`] from the front.
+///
+/// Each poll pops the front element, and the stream ends as soon as the deque
+/// is empty. It never returns [`Poll::Pending`].
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::{ArrayDeque, DequeStream};
+/// use futures::StreamExt;
+///
+/// let mut window = ArrayDeque::new(3);
+/// window.extend([1, 2, 3, 4]);
+/// let sum = futures::executor::block_on(
+///     DequeStream::new(&mut window).fold(0, |acc, x| async move { acc + x }),
+/// );
+/// assert_eq!(sum, 9);
+/// assert!(window.is_empty());
+/// ```
+#[derive(Debug)]
+pub struct DequeStream<'a, T, A: Allocator = Global> {
+    /// The deque being drained
+    deque: &'a mut ArrayDeque<T, A>,
+}
+
+impl<'a, T, A: Allocator> DequeStream<'a, T, A> {
+    /// Creates a stream that pops elements from the front of `deque`.
+    pub fn new(deque: &'a mut ArrayDeque<T, A>) -> Self {
+        Self { deque }
+    }
+}
+
+impl<T, A: Allocator> Stream for DequeStream<'_, T, A> {
+    type Item = T;
+
+    /// Pops the front element, or ends the stream if the deque is empty.
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Poll::Ready(self.get_mut().deque.pop_front())
+    }
+
+    /// Returns the exact number of elements left in the deque.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len(), Some(self.deque.len()))
+    }
+}
+
+/// A [`Sink`] that pushes items onto the back of a borrowed [`ArrayDeque`].
+///
+/// What happens when the deque is full depends on how the sink was created:
+///
+/// - [`new`](Self::new) applies backpressure: `poll_ready` on a full deque
+///   fails with a [`CapacityError`] rather than returning
+///   [`Poll::Pending`], since nothing could drain the deque and wake the task
+///   while the sink holds it.
+/// - [`overwriting`](Self::overwriting) is always ready and evicts the oldest
+///   element, as [`push_back`](ArrayDeque::push_back) does. If the deque's
+///   [`OverflowPolicy`] forbids eviction, or its capacity is zero, it fails
+///   like `new` instead, so no item is ever dropped silently.
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::{ArrayDeque, DequeSink};
+/// use futures::SinkExt;
+///
+/// let mut recent = ArrayDeque::new(2);
+/// futures::executor::block_on(async {
+///     let mut sink = DequeSink::overwriting(&mut recent);
+///     for i in 0..5 {
+///         sink.send(i).await.unwrap();
+///     }
+/// });
+/// assert_eq!(recent, [3, 4]);
+/// ```
+#[derive(Debug)]
+pub struct DequeSink<'a, T, A: Allocator = Global> {
+    /// The deque being filled
+    deque: &'a mut ArrayDeque<T, A>,
+    /// Whether a full deque evicts its oldest element instead of erroring
+    overwrite: bool,
+}
+
+impl<'a, T, A: Allocator> DequeSink<'a, T, A> {
+    /// Creates a sink that refuses items once `deque` is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_deque::{ArrayDeque, DequeSink};
+    /// use futures::SinkExt;
+    ///
+    /// let mut queue = ArrayDeque::new(1);
+    /// futures::executor::block_on(async {
+    ///     let mut sink = DequeSink::new(&mut queue);
+    ///     sink.send('a').await.unwrap();
+    ///     assert!(sink.send('b').await.is_err());
+    /// });
+    /// assert_eq!(queue, ['a']);
+    /// ```
+    pub fn new(deque: &'a mut ArrayDeque<T, A>) -> Self {
+        Self {
+            deque,
+            overwrite: false,
+        }
+    }
+
+    /// Creates a sink that evicts the oldest element once `deque` is full,
+    /// provided the deque's [`OverflowPolicy`] is
+    /// [`OverwriteOldest`](OverflowPolicy::OverwriteOldest).
+    pub fn overwriting(deque: &'a mut ArrayDeque<T, A>) -> Self {
+        Self {
+            deque,
+            overwrite: true,
+        }
+    }
+
+    /// Fails if the deque is full and the next item would not evict its
+    /// oldest element.
+    fn check_capacity(&self) -> Result<(), CapacityError> {
+        let evicts = self.overwrite
+            && self.deque.policy() == OverflowPolicy::OverwriteOldest
+            && self.deque.capacity() != 0;
+        if evicts || !self.deque.is_full() {
+            Ok(())
+        } else {
+            Err(CapacityError {
+                len: self.deque.len() + 1,
+                capacity: self.deque.capacity(),
+            })
+        }
+    }
+}
+
+impl<T, A: Allocator> Sink<T> for DequeSink<'_, T, A> {
+    type Error = CapacityError;
+
+    /// Reports whether another item can be accepted right now.
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(self.check_capacity())
+    }
+
+    /// Pushes `item` onto the back of the deque.
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        this.check_capacity()?;
+        this.deque.push_back(item);
+        Ok(())
+    }
+
+    /// Does nothing, as items are stored as soon as they are sent.
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// Does nothing; the deque keeps its contents.
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::{SinkExt, StreamExt, stream};
+
+    #[cfg(not(feature = "std"))]
+    extern crate alloc;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn stream_drains_wrapped_deque() {
+        let mut deque = ArrayDeque::new(4);
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        let mut stream = DequeStream::new(&mut deque);
+        assert_eq!(stream.size_hint(), (4, Some(4)));
+        let items: Vec<i32> = block_on(stream.by_ref().collect());
+        assert_eq!(items, [2, 3, 4, 5]);
+        assert_eq!(block_on(stream.next()), None);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn sink_backpressure_errors_when_full() {
+        let mut deque = ArrayDeque::new(3);
+        let result = block_on(DequeSink::new(&mut deque).send_all(&mut stream::iter(0..5).map(Ok)));
+        assert_eq!(
+            result,
+            Err(CapacityError {
+                len: 4,
+                capacity: 3
+            })
+        );
+        assert_eq!(deque, [0, 1, 2]);
+    }
+
+    #[test]
+    fn sink_overwriting_keeps_newest() {
+        let mut deque = ArrayDeque::new(3);
+        block_on(DequeSink::overwriting(&mut deque).send_all(&mut stream::iter(0..5).map(Ok)))
+            .unwrap();
+        assert_eq!(deque, [2, 3, 4]);
+    }
+
+    #[test]
+    fn sink_overwriting_honors_policy() {
+        for policy in [OverflowPolicy::Reject, OverflowPolicy::Panic] {
+            let mut deque = ArrayDeque::with_policy(3, policy);
+            let mut sink = DequeSink::overwriting(&mut deque);
+            let result = block_on(sink.send_all(&mut stream::iter(0..5).map(Ok)));
+            assert_eq!(
+                result,
+                Err(CapacityError {
+                    len: 4,
+                    capacity: 3
+                })
+            );
+            assert_eq!(deque, [0, 1, 2]);
+        }
+
+        let mut empty = ArrayDeque::new(0);
+        assert!(block_on(DequeSink::overwriting(&mut empty).send(1)).is_err());
+    }
+
+    #[test]
+    fn stream_into_sink_round_trip() {
+        let mut source = ArrayDeque::from([1, 2, 3]);
+        let mut target = ArrayDeque::new(3);
+        block_on(
+            DequeStream::new(&mut source)
+                .map(Ok)
+                .forward(DequeSink::new(&mut target)),
+        )
+        .unwrap();
+        assert!(source.is_empty());
+        assert_eq!(target, [1, 2, 3]);
+    }
+}
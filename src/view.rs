@@ -0,0 +1,249 @@
+use crate::{resolve_range, sub_slices, sub_slices_mut};
+use core::fmt;
+use core::iter::Chain;
+use core::ops::{Index, IndexMut, RangeBounds};
+use core::slice;
+
+/// A borrowed view of a contiguous logical range of an
+/// [`ArrayDeque`](crate::ArrayDeque), like a `&[T]` for a buffer that may wrap.
+///
+/// The elements are stored as two slices, the first followed by the second;
+/// the second is empty unless the range crosses the point where the deque
+/// wraps around its buffer. Views are cheap to copy and can be narrowed
+/// further with [`slice`](Self::slice).
+///
+/// Returned by [`ArrayDeque::slice`](crate::ArrayDeque::slice).
+///
+/// # Examples
+///
+/// ```
+/// use array_deque::ArrayDeque;
+///
+/// let mut window = ArrayDeque::new(4);
+/// window.extend(*b"xxhead-body");
+/// let view = window.slice(1..);
+/// assert_eq!(view.len(), 3);
+/// assert_eq!(view[0], b'o');
+/// assert!(view.slice(1..).iter().eq(b"dy"));
+/// ```
+pub struct ArrayDequeView<'a, T> {
+    /// Elements up to the wrap point
+    front: &'a [T],
+    /// Elements after the wrap point
+    back: &'a [T],
+}
+
+impl<'a, T> ArrayDequeView<'a, T> {
+    /// Creates a view over the contents `front` followed by `back`.
+    pub(crate) fn new(front: &'a [T], back: &'a [T]) -> Self {
+        ArrayDequeView { front, back }
+    }
+
+    /// Returns the number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Returns `true` if the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is out
+    /// of bounds.
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        match self.front.get(index) {
+            Some(value) => Some(value),
+            None => self.back.get(index - self.front.len()),
+        }
+    }
+
+    /// Returns an iterator over the elements of the view, front to back.
+    pub fn iter(&self) -> Chain<slice::Iter<'a, T>, slice::Iter<'a, T>> {
+        self.front.iter().chain(self.back)
+    }
+
+    /// Returns the elements of the view as a pair of slices.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        (self.front, self.back)
+    }
+
+    /// Returns a narrower view of `range`, relative to the start of this view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is inverted or extends past the end of the view.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> ArrayDequeView<'a, T> {
+        let range = resolve_range(range, self.len());
+        let (front, back) = sub_slices((self.front, self.back), range.start, range.end);
+        ArrayDequeView { front, back }
+    }
+}
+
+impl<T> Clone for ArrayDequeView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ArrayDequeView<'_, T> {}
+
+impl<T> Index<usize> for ArrayDequeView<'_, T> {
+    type Output = T;
+
+    /// Returns a reference to the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Index out of bounds")
+    }
+}
+
+impl<'a, T> IntoIterator for ArrayDequeView<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    /// Returns an iterator over the elements of the view, front to back.
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for ArrayDequeView<'_, T> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len()
+            && self.front == &other[..self.front.len()]
+            && self.back == &other[self.front.len()..]
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for ArrayDequeView<'_, T> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArrayDequeView<'_, T> {
+    /// Formats the elements as a list, front to back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// A mutable view of a contiguous logical range of an
+/// [`ArrayDeque`](crate::ArrayDeque), like a `&mut [T]` for a buffer that may
+/// wrap.
+///
+/// Returned by [`ArrayDeque::slice_mut`](crate::ArrayDeque::slice_mut).
+pub struct ArrayDequeViewMut<'a, T> {
+    /// Elements up to the wrap point
+    front: &'a mut [T],
+    /// Elements after the wrap point
+    back: &'a mut [T],
+}
+
+impl<'a, T> ArrayDequeViewMut<'a, T> {
+    /// Creates a view over the contents `front` followed by `back`.
+    pub(crate) fn new(front: &'a mut [T], back: &'a mut [T]) -> Self {
+        ArrayDequeViewMut { front, back }
+    }
+
+    /// Returns the number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Returns `true` if the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is out
+    /// of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        match self.front.get(index) {
+            Some(value) => Some(value),
+            None => self.back.get(index - self.front.len()),
+        }
+    }
+
+    /// Returns a mutable reference to the element at `index`, or `None` if it
+    /// is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let front_len = self.front.len();
+        match self.front.get_mut(index) {
+            Some(value) => Some(value),
+            None => self.back.get_mut(index - front_len),
+        }
+    }
+
+    /// Returns an iterator over the elements of the view, front to back.
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        self.front.iter().chain(self.back.iter())
+    }
+
+    /// Returns an iterator that allows modifying each element of the view.
+    pub fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        self.front.iter_mut().chain(self.back.iter_mut())
+    }
+
+    /// Returns the elements of the view as a pair of slices.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        (self.front, self.back)
+    }
+
+    /// Returns the elements of the view as a pair of mutable slices.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        (self.front, self.back)
+    }
+
+    /// Reborrows the view as a shared [`ArrayDequeView`].
+    pub fn as_view(&self) -> ArrayDequeView<'_, T> {
+        ArrayDequeView::new(self.front, self.back)
+    }
+
+    /// Returns a narrower mutable view of `range`, relative to the start of
+    /// this view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is inverted or extends past the end of the view.
+    pub fn slice_mut<R: RangeBounds<usize>>(&mut self, range: R) -> ArrayDequeViewMut<'_, T> {
+        let range = resolve_range(range, self.len());
+        let (front, back) = sub_slices_mut((self.front, self.back), range.start, range.end);
+        ArrayDequeViewMut { front, back }
+    }
+}
+
+impl<T> Index<usize> for ArrayDequeViewMut<'_, T> {
+    type Output = T;
+
+    /// Returns a reference to the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for ArrayDequeViewMut<'_, T> {
+    /// Returns a mutable reference to the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Index out of bounds")
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArrayDequeViewMut<'_, T> {
+    /// Formats the elements as a list, front to back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_view().fmt(f)
+    }
+}
@@ -0,0 +1,286 @@
+use crate::sub_slices;
+use core::fmt;
+use core::iter::FusedIterator;
+
+/// An iterator over overlapping windows of a deque.
+///
+/// Each window is a pair of slices, like the one returned by `as_slices`: the
+/// elements of the window are those of the first slice followed by those of
+/// the second. The second slice is empty unless the window straddles the point
+/// where the deque wraps around its buffer.
+///
+/// Returned by [`ArrayDeque::windows`](crate::ArrayDeque::windows) and
+/// [`StackArrayDeque::windows`](crate::StackArrayDeque::windows).
+pub struct Windows<'a, T> {
+    /// Contents of the deque, as returned by `as_slices`
+    slices: (&'a [T], &'a [T]),
+    /// Number of elements in each window
+    size: usize,
+    /// Start of the next window from the front
+    start: usize,
+    /// One past the start of the next window from the back
+    end: usize,
+}
+
+impl<'a, T> Windows<'a, T> {
+    /// Creates an iterator over the windows of `size` elements of the deque
+    /// whose contents are `front` followed by `back`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub(crate) fn new(front: &'a [T], back: &'a [T], size: usize) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        let len = front.len() + back.len();
+        Windows {
+            slices: (front, back),
+            size,
+            start: 0,
+            end: if len < size { 0 } else { len - size + 1 },
+        }
+    }
+
+    /// Returns the window starting at logical index `start`.
+    fn window(&self, start: usize) -> (&'a [T], &'a [T]) {
+        sub_slices(self.slices, start, start + self.size)
+    }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = (&'a [T], &'a [T]);
+
+    /// Returns the next window from the front.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+        let window = self.window(self.start);
+        self.start += 1;
+        Some(window)
+    }
+
+    /// Returns the exact number of remaining windows.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+
+    /// Skips `n` windows in O(1) and returns the next one.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.start = self.start.saturating_add(n).min(self.end);
+        self.next()
+    }
+
+    /// Returns the number of remaining windows in O(1).
+    fn count(self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns the last window in O(1).
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<T> DoubleEndedIterator for Windows<'_, T> {
+    /// Returns the next window from the back.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(self.window(self.end))
+    }
+}
+
+impl<T> ExactSizeIterator for Windows<'_, T> {}
+
+impl<T> FusedIterator for Windows<'_, T> {}
+
+impl<T> Clone for Windows<'_, T> {
+    /// Creates a copy of the iterator at the same position.
+    fn clone(&self) -> Self {
+        Windows {
+            slices: self.slices,
+            size: self.size,
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Windows<'_, T> {
+    /// Formats the window size and the underlying pair of slices.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Windows")
+            .field("slices", &self.slices)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+/// An iterator over overlapping windows of `K` elements of a deque, copied
+/// into arrays.
+///
+/// Returned by [`ArrayDeque::array_windows`](crate::ArrayDeque::array_windows)
+/// and [`StackArrayDeque::array_windows`](crate::StackArrayDeque::array_windows).
+pub struct ArrayWindows<'a, T, const K: usize> {
+    /// The underlying windows, each of `K` elements
+    windows: Windows<'a, T>,
+}
+
+impl<'a, T: Copy, const K: usize> ArrayWindows<'a, T, K> {
+    /// Creates an iterator over the `K`-element windows of the deque whose
+    /// contents are `front` followed by `back`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is zero.
+    pub(crate) fn new(front: &'a [T], back: &'a [T]) -> Self {
+        ArrayWindows {
+            windows: Windows::new(front, back, K),
+        }
+    }
+
+    /// Copies a window of `K` elements into an array.
+    fn copied((first, second): (&[T], &[T])) -> [T; K] {
+        core::array::from_fn(|i| match first.get(i) {
+            Some(&value) => value,
+            None => second[i - first.len()],
+        })
+    }
+}
+
+impl<T: Copy, const K: usize> Iterator for ArrayWindows<'_, T, K> {
+    type Item = [T; K];
+
+    /// Returns a copy of the next window from the front.
+    fn next(&mut self) -> Option<[T; K]> {
+        self.windows.next().map(Self::copied)
+    }
+
+    /// Returns the exact number of remaining windows.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+
+    /// Skips `n` windows in O(1) and returns a copy of the next one.
+    fn nth(&mut self, n: usize) -> Option<[T; K]> {
+        self.windows.nth(n).map(Self::copied)
+    }
+
+    /// Returns the number of remaining windows in O(1).
+    fn count(self) -> usize {
+        self.windows.count()
+    }
+
+    /// Returns a copy of the last window in O(1).
+    fn last(self) -> Option<[T; K]> {
+        self.windows.last().map(Self::copied)
+    }
+}
+
+impl<T: Copy, const K: usize> DoubleEndedIterator for ArrayWindows<'_, T, K> {
+    /// Returns a copy of the next window from the back.
+    fn next_back(&mut self) -> Option<[T; K]> {
+        self.windows.next_back().map(Self::copied)
+    }
+}
+
+impl<T: Copy, const K: usize> ExactSizeIterator for ArrayWindows<'_, T, K> {}
+
+impl<T: Copy, const K: usize> FusedIterator for ArrayWindows<'_, T, K> {}
+
+impl<T, const K: usize> Clone for ArrayWindows<'_, T, K> {
+    /// Creates a copy of the iterator at the same position.
+    fn clone(&self) -> Self {
+        ArrayWindows {
+            windows: self.windows.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, const K: usize> fmt::Debug for ArrayWindows<'_, T, K> {
+    /// Formats the underlying pair of slices.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArrayWindows")
+            .field("slices", &self.windows.slices)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::StackArrayDeque;
+
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    extern crate alloc;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    use std::vec::Vec;
+
+    /// Returns a full deque of `3..8` whose front sits at slot 3, so its
+    /// contents are split into `[3, 4]` and `[5, 6, 7]`.
+    fn wrapped() -> StackArrayDeque<i32, 5> {
+        let mut deque = StackArrayDeque::new();
+        for i in 0..8 {
+            deque.push_back(i);
+        }
+        deque
+    }
+
+    #[test]
+    fn windows_straddle_wrap_point() {
+        let deque = wrapped();
+        assert_eq!(deque.as_slices(), (&[3, 4][..], &[5, 6, 7][..]));
+        let mut windows = deque.windows(3);
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows.next(), Some((&[3, 4][..], &[5][..])));
+        assert_eq!(windows.next(), Some((&[4][..], &[5, 6][..])));
+        assert_eq!(windows.next(), Some((&[5, 6, 7][..], &[][..])));
+        assert_eq!(windows.next(), None);
+    }
+
+    #[test]
+    fn windows_from_both_ends() {
+        let deque = wrapped();
+        let mut windows = deque.windows(2);
+        assert_eq!(windows.next_back(), Some((&[6, 7][..], &[][..])));
+        assert_eq!(windows.nth(1), Some((&[4][..], &[5][..])));
+        assert_eq!(windows.clone().count(), 1);
+        assert_eq!(windows.next_back(), Some((&[5, 6][..], &[][..])));
+        assert_eq!(windows.next(), None);
+        assert_eq!(windows.next_back(), None);
+    }
+
+    #[test]
+    fn windows_longer_than_deque_are_empty() {
+        let deque = wrapped();
+        assert_eq!(deque.windows(5).count(), 1);
+        assert_eq!(deque.windows(6).next(), None);
+        assert_eq!(StackArrayDeque::<i32, 0>::new().windows(1).len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn windows_of_zero_panics() {
+        wrapped().windows(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn array_windows_of_zero_panics() {
+        wrapped().array_windows::<0>();
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn array_windows_copy_elements() {
+        let deque = wrapped();
+        let sums: Vec<i32> = deque.array_windows().map(|[a, b, c]| a + b + c).collect();
+        assert_eq!(sums, [12, 15, 18]);
+        let windows: Vec<[i32; 4]> = deque.array_windows().rev().collect();
+        assert_eq!(windows, [[4, 5, 6, 7], [3, 4, 5, 6]]);
+    }
+}
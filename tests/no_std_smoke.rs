@@ -0,0 +1,25 @@
+//! Exercises the crate from a `no_std` crate. Built with
+//! `--no-default-features`, this checks that `StackArrayDeque` is usable
+//! without `std` or `alloc`.
+
+#![no_std]
+
+use array_deque::StackArrayDeque;
+
+#[test]
+fn stack_deque_without_std() {
+    let mut deque: StackArrayDeque<u32, 3> = StackArrayDeque::new();
+    deque.extend([1, 2, 3, 4]);
+    assert_eq!(deque.pop_front(), Some(2));
+    assert!(deque.iter().eq(&[3, 4]));
+    assert_eq!(deque.try_push_back(5), Ok(()));
+    assert_eq!(deque.try_push_back(6), Err(6));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn array_deque_with_alloc() {
+    let mut deque = array_deque::ArrayDeque::new(2);
+    deque.extend([1, 2, 3]);
+    assert_eq!(deque, [2, 3]);
+}
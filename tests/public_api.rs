@@ -0,0 +1,86 @@
+//! Names every public item through the crate root, so a type that exists but
+//! is not re-exported fails to compile here.
+
+#![cfg_attr(feature = "unstable", feature(allocator_api))]
+
+use array_deque::{
+    CapacityError, GetManyMutError, StackArrayDeque, StackArrayDequeIntoIter, StackArrayDequeIter,
+    TryExtendError,
+};
+
+#[test]
+fn stack_items_are_exported() {
+    let deque = StackArrayDeque::from([1, 2, 3]);
+    let iter: StackArrayDequeIter<'_, i32, 3> = (&deque).into_iter();
+    assert!(iter.eq(&[1, 2, 3]));
+    let into_iter: StackArrayDequeIntoIter<i32, 3> = deque.into_iter();
+    assert!(into_iter.eq([1, 2, 3]));
+
+    let mut full = StackArrayDeque::<i32, 1>::from([0]);
+    let err: TryExtendError<_> = full.try_extend([1, 2]).unwrap_err();
+    assert_eq!(err.element, 1);
+    let err = CapacityError {
+        len: 2,
+        capacity: 1,
+    };
+    assert_eq!(err.capacity, 1);
+    let err: GetManyMutError = full.get_many_mut([0, 0]).unwrap_err();
+    assert_eq!(err, GetManyMutError::OverlappingIndices);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn alloc_items_are_exported() {
+    use array_deque::{
+        Allocator, ArrayDeque, ArrayDequeIntoIter, ArrayDequeIter, Global, SlidingMinMax,
+        TryReserveError,
+    };
+
+    fn assert_allocator<A: Allocator>(_: &A) {}
+    assert_allocator(&Global);
+
+    let deque: ArrayDeque<i32, Global> = ArrayDeque::from([1, 2]);
+    let iter: ArrayDequeIter<'_, i32> = (&deque).into_iter();
+    assert!(iter.eq(&[1, 2]));
+    let into_iter: ArrayDequeIntoIter<i32> = deque.into_iter();
+    assert!(into_iter.eq([1, 2]));
+
+    let err: TryReserveError = ArrayDeque::<u64>::try_new(usize::MAX).unwrap_err();
+    assert_eq!(err, TryReserveError::CapacityOverflow);
+
+    let mut window = SlidingMinMax::new(2);
+    window.push(1);
+    assert_eq!(window.max(), Some(&1));
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[test]
+fn serde_items_are_exported() {
+    use array_deque::{ArrayDeque, ArrayDequeSeed, serde_with_capacity};
+    use serde::de::DeserializeSeed;
+
+    let mut json = serde_json::Deserializer::from_str("[1]");
+    let deque: ArrayDeque<i32> = ArrayDequeSeed::with_capacity(2)
+        .deserialize(&mut json)
+        .unwrap();
+    let value = serde_with_capacity::serialize(&deque, serde_json::value::Serializer).unwrap();
+    assert_eq!(value["cap"], 2);
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn tokio_items_are_exported() {
+    let mut deque = array_deque::ArrayDeque::new(1);
+    let ring: array_deque::AsyncRing<'_> = array_deque::AsyncRing::new(&mut deque);
+    assert!(ring.get_ref().is_empty());
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn futures_items_are_exported() {
+    use array_deque::{ArrayDeque, DequeSink, DequeStream};
+
+    let mut deque = ArrayDeque::<i32>::new(1);
+    let _sink: DequeSink<'_, i32> = DequeSink::new(&mut deque);
+    let _stream: DequeStream<'_, i32> = DequeStream::new(&mut deque);
+}